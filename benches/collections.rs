@@ -0,0 +1,121 @@
+//!
+//! Benchmarks of [SymbolMap] and [SymbolSet] lookups, against `HashMap` and `HashSet`.
+//!
+//! Static symbols are looked up in dense per-namespace storage, without hashing. The `*_by_uid` benchmarks
+//! look up symbols of another namespace instance sharing a uid.
+//!
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dyn_symbol::collections::{SymbolMap, SymbolSet};
+use dyn_symbol::{namespace, Symbol};
+use std::collections::{HashMap, HashSet};
+
+/// Not zero-sized, so that the instances have different addresses.
+#[allow(dead_code)]
+struct Html(u8);
+
+impl namespace::Static for Html {
+    fn namespace_name(&self) -> &str {
+        "html"
+    }
+
+    fn namespace_uid(&self) -> Option<u128> {
+        Some(0x17)
+    }
+
+    fn symbol_count(&self) -> u32 {
+        64
+    }
+
+    fn symbol_name(&self, _: u32) -> &str {
+        "attr"
+    }
+}
+
+/// Other namespaces in the map, so that finding the namespace takes a few steps.
+struct Other<const N: u8>;
+
+impl<const N: u8> namespace::Static for Other<N> {
+    fn namespace_name(&self) -> &str {
+        "other"
+    }
+
+    fn symbol_count(&self) -> u32 {
+        64
+    }
+
+    fn symbol_name(&self, _: u32) -> &str {
+        "attr"
+    }
+}
+
+static HTML: Html = Html(0);
+static HTML_AGAIN: Html = Html(1);
+
+fn keys(ns: &'static Html) -> Vec<Symbol> {
+    (0..1000)
+        .map(|i| Symbol::Static(ns, (i * 7919) % 64))
+        .collect()
+}
+
+fn all_keys() -> Vec<Symbol> {
+    let others: [&'static dyn namespace::Static; 3] = [&Other::<0>, &Other::<1>, &Other::<2>];
+    others
+        .iter()
+        .flat_map(|ns| (0..64).map(move |id| Symbol::Static(*ns, id)))
+        .chain((0..64).map(|id| Symbol::Static(&HTML, id)))
+        .collect()
+}
+
+fn bench_map(c: &mut Criterion) {
+    let symbol_map: SymbolMap<u32> = all_keys().into_iter().zip(0..).collect();
+    let hash_map: HashMap<Symbol, u32> = all_keys().into_iter().zip(0..).collect();
+    let (keys, keys_by_uid) = (keys(&HTML), keys(&HTML_AGAIN));
+
+    c.bench_function("symbol_map_get", |bench| {
+        bench.iter(|| {
+            keys.iter()
+                .filter_map(|key| symbol_map.get(black_box(key)))
+                .sum::<u32>()
+        })
+    });
+    c.bench_function("symbol_map_get_by_uid", |bench| {
+        bench.iter(|| {
+            keys_by_uid
+                .iter()
+                .filter_map(|key| symbol_map.get(black_box(key)))
+                .sum::<u32>()
+        })
+    });
+    c.bench_function("hash_map_get", |bench| {
+        bench.iter(|| {
+            keys.iter()
+                .filter_map(|key| hash_map.get(black_box(key)))
+                .sum::<u32>()
+        })
+    });
+}
+
+fn bench_set(c: &mut Criterion) {
+    let symbol_set: SymbolSet = all_keys().into_iter().collect();
+    let hash_set: HashSet<Symbol> = all_keys().into_iter().collect();
+    let keys = keys(&HTML);
+
+    c.bench_function("symbol_set_contains", |bench| {
+        bench.iter(|| {
+            keys.iter()
+                .filter(|&key| symbol_set.contains(black_box(key)))
+                .count()
+        })
+    });
+    c.bench_function("hash_set_contains", |bench| {
+        bench.iter(|| {
+            keys.iter()
+                .filter(|&key| hash_set.contains(black_box(key)))
+                .count()
+        })
+    });
+}
+
+criterion_group!(benches, bench_map, bench_set);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+//!
+//! Benchmarks of symbol comparisons.
+//!
+//! Symbols from the same static namespace instance, and clones of the same dynamic symbol, are compared by pointer
+//! identity. The `*_by_uid` benchmarks compare symbols from two namespace instances sharing a uid,
+//! which takes the slower path through the namespace identities.
+//!
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dyn_symbol::{namespace, Symbol};
+use std::sync::Arc;
+
+/// Not zero-sized, so that the two instances have different addresses.
+#[allow(dead_code)]
+struct Html(u8);
+
+impl namespace::Static for Html {
+    fn namespace_name(&self) -> &str {
+        "html"
+    }
+
+    fn namespace_uid(&self) -> Option<u128> {
+        Some(0x17)
+    }
+
+    fn symbol_count(&self) -> u32 {
+        64
+    }
+
+    fn symbol_name(&self, _: u32) -> &str {
+        "attr"
+    }
+}
+
+static HTML: Html = Html(0);
+static HTML_AGAIN: Html = Html(1);
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Word(String);
+
+impl namespace::DynValue for Word {
+    fn namespace_name(&self) -> &str {
+        "word"
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.0
+    }
+}
+
+fn symbols(ns: &'static Html) -> Vec<Symbol> {
+    (0..1000)
+        .map(|i| Symbol::Static(ns, (i * 7919) % 64))
+        .collect()
+}
+
+fn eq_count(a: &[Symbol], b: &[Symbol]) -> usize {
+    a.iter()
+        .zip(b)
+        .filter(|(a, b)| black_box(a) == black_box(b))
+        .count()
+}
+
+fn bench_static(c: &mut Criterion) {
+    let a = symbols(&HTML);
+    let b: Vec<_> = a.iter().rev().cloned().collect();
+    let b_by_uid = symbols(&HTML_AGAIN).into_iter().rev().collect::<Vec<_>>();
+
+    c.bench_function("static_eq", |bench| bench.iter(|| eq_count(&a, &b)));
+    c.bench_function("static_eq_by_uid", |bench| {
+        bench.iter(|| eq_count(&a, &b_by_uid))
+    });
+    c.bench_function("static_sort", |bench| {
+        bench.iter(|| {
+            let mut symbols = a.clone();
+            symbols.sort();
+            symbols
+        })
+    });
+    c.bench_function("static_sort_by_uid", |bench| {
+        bench.iter(|| {
+            let mut symbols: Vec<_> = a.iter().chain(&b_by_uid).cloned().collect();
+            symbols.sort();
+            symbols
+        })
+    });
+}
+
+fn bench_dynamic(c: &mut Criterion) {
+    let shared = Symbol::Dynamic(Arc::new(namespace::DynWrap(Word(
+        "a long word, so that comparing strings takes a while".into(),
+    ))));
+    let a: Vec<_> = (0..1000).map(|_| shared.clone()).collect();
+    let b: Vec<_> = (0..1000)
+        .map(|_| {
+            Symbol::from(namespace::DynWrap(Word(
+                "a long word, so that comparing strings takes a while".into(),
+            )))
+        })
+        .collect();
+
+    c.bench_function("dynamic_eq_shared", |bench| bench.iter(|| eq_count(&a, &a)));
+    c.bench_function("dynamic_eq_distinct", |bench| {
+        bench.iter(|| eq_count(&a, &b))
+    });
+}
+
+criterion_group!(benches, bench_static, bench_dynamic);
+criterion_main!(benches);
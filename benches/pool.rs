@@ -0,0 +1,81 @@
+//!
+//! Benchmarks of creating and dropping short-lived dynamic symbols, with and without a [SymbolPool].
+//!
+//! `dyn_wrap` allocates the symbol and its name every time. `pool` reuses name buffers of dropped symbols,
+//! and `pool_recycle` reuses whole symbols handed back with [SymbolPool::recycle].
+//!
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dyn_symbol::pool::SymbolPool;
+use dyn_symbol::{namespace, Symbol};
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Word(String);
+
+impl namespace::DynValue for Word {
+    fn namespace_name(&self) -> &str {
+        "word"
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.0
+    }
+}
+
+fn names() -> Vec<String> {
+    (0..1000).map(|i| format!("event-{}", i)).collect()
+}
+
+fn bench_create_and_drop(c: &mut Criterion) {
+    let names = names();
+    let pool = SymbolPool::new("word");
+
+    c.bench_function("dyn_wrap", |bench| {
+        bench.iter(|| {
+            for name in &names {
+                drop(black_box(Symbol::from(namespace::DynWrap(Word(
+                    name.clone(),
+                )))));
+            }
+        })
+    });
+    c.bench_function("pool", |bench| {
+        bench.iter(|| {
+            for name in &names {
+                drop(black_box(pool.symbol(name)));
+            }
+        })
+    });
+    c.bench_function("pool_recycle", |bench| {
+        bench.iter(|| {
+            for name in &names {
+                pool.recycle(black_box(pool.symbol(name)));
+            }
+        })
+    });
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let names = names();
+    let pool = SymbolPool::new("word");
+
+    c.bench_function("dyn_wrap_batch", |bench| {
+        bench.iter(|| {
+            names
+                .iter()
+                .map(|name| Symbol::from(namespace::DynWrap(Word(name.clone()))))
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("pool_batch", |bench| {
+        bench.iter(|| {
+            names
+                .iter()
+                .map(|name| pool.symbol(name))
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+criterion_group!(benches, bench_create_and_drop, bench_batch);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+///
+/// Convert an `UpperCamelCase` identifier into `SCREAMING_SNAKE_CASE`.
+///
+pub fn screaming_snake_case(ident: &str) -> String {
+    let mut output = String::with_capacity(ident.len() + 4);
+    let mut prev: Option<char> = None;
+    let mut chars = ident.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_uppercase() {
+            let next_is_lower = chars.peek().map(|n| n.is_lowercase()).unwrap_or(false);
+            match prev {
+                Some(p) if p.is_lowercase() || p.is_numeric() => output.push('_'),
+                Some(p) if p.is_uppercase() && next_is_lower => output.push('_'),
+                _ => {}
+            }
+        }
+        if c != '_' || !output.ends_with('_') {
+            output.extend(c.to_uppercase());
+        }
+        prev = Some(c);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screaming_snake_case() {
+        assert_eq!(screaming_snake_case("Class"), "CLASS");
+        assert_eq!(screaming_snake_case("TabIndex"), "TAB_INDEX");
+        assert_eq!(screaming_snake_case("HTMLElement"), "HTML_ELEMENT");
+        assert_eq!(screaming_snake_case("Aria2Level"), "ARIA2_LEVEL");
+        assert_eq!(screaming_snake_case("Already_Snake"), "ALREADY_SNAKE");
+    }
+}
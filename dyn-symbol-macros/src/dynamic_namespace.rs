@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+pub fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "DynamicNamespace can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut namespace_name: Option<syn::LitStr> = None;
+    let mut symbol_name_field: Option<syn::Member> = None;
+
+    for attr in &input.attrs {
+        if !attr.path().is_ident("namespace") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                namespace_name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("symbol_name_field") {
+                let field: syn::LitStr = meta.value()?.parse()?;
+                symbol_name_field = Some(field.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `name` or `symbol_name_field`"))
+            }
+        })?;
+    }
+
+    let namespace_name = namespace_name.ok_or_else(|| {
+        syn::Error::new(
+            input.span(),
+            "missing `#[namespace(name = \"...\")]` attribute",
+        )
+    })?;
+
+    let symbol_name_field = match symbol_name_field {
+        Some(field) => field,
+        None if fields.len() == 1 => match fields.iter().next().unwrap().ident.clone() {
+            Some(ident) => syn::Member::Named(ident),
+            None => syn::Member::Unnamed(0.into()),
+        },
+        None => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "`symbol_name_field` must be specified for types with multiple fields",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::dyn_symbol::namespace::Dynamic for #ident #ty_generics #where_clause {
+            fn namespace_name(&self) -> &str {
+                #namespace_name
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.#symbol_name_field
+            }
+
+            fn dyn_clone(&self) -> ::dyn_symbol::__private::Box<dyn ::dyn_symbol::namespace::Dynamic> {
+                ::dyn_symbol::__private::Box::new(::core::clone::Clone::clone(self))
+            }
+
+            fn dyn_eq(&self, rhs: &dyn ::dyn_symbol::namespace::Dynamic) -> bool {
+                self == rhs.downcast_ref::<Self>().unwrap()
+            }
+
+            fn dyn_cmp(&self, rhs: &dyn ::dyn_symbol::namespace::Dynamic) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(self, rhs.downcast_ref::<Self>().unwrap())
+            }
+
+            fn dyn_hash(&self, mut state: &mut dyn ::core::hash::Hasher) {
+                ::core::hash::Hash::hash(self, &mut state)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::dyn_symbol::Symbol #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                ::dyn_symbol::Symbol::Dynamic(::dyn_symbol::__private::Arc::new(value))
+            }
+        }
+    })
+}
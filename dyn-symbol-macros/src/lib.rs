@@ -0,0 +1,118 @@
+//!
+//! Procedural macros for [dyn-symbol](https://docs.rs/dyn-symbol).
+//!
+//! These macros are re-exported from `dyn_symbol` when its `macros` feature is enabled,
+//! and the generated code refers to items through the `::dyn_symbol` path.
+//!
+
+mod case;
+mod dynamic_namespace;
+mod static_namespace;
+mod static_symbols;
+
+///
+/// Declare a static namespace along with its symbols.
+///
+/// The macro generates a module named after the namespace. The module contains a unit struct
+/// `Namespace` implementing `dyn_symbol::namespace::Static`, and one `pub const` [Symbol] per
+/// declared symbol, with sequential ids starting at `0`.
+///
+/// The namespace name defaults to the module identifier, but can be overridden with a string literal.
+///
+/// ```
+/// use dyn_symbol_macros::static_namespace;
+///
+/// static_namespace! {
+///     pub my {
+///         FOO = "foo",
+///         BAR = "bar",
+///     }
+///
+///     pub(crate) data = "data-ns" {
+///         USER_ID = "user-id",
+///     }
+/// }
+///
+/// assert_eq!(format!("{:?}", my::FOO), "my::foo");
+/// assert_eq!(format!("{:?}", data::USER_ID), "data-ns::user-id");
+/// assert_ne!(my::FOO, my::BAR);
+/// assert_eq!(my::BAR.downcast_static::<my::Namespace>().unwrap().1, 1);
+/// ```
+///
+/// [Symbol]: https://docs.rs/dyn-symbol/latest/dyn_symbol/enum.Symbol.html
+///
+#[proc_macro]
+pub fn static_namespace(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let namespaces = syn::parse_macro_input!(input as static_namespace::Namespaces);
+    static_namespace::generate(namespaces).into()
+}
+
+///
+/// Derive a static namespace from a fieldless enum.
+///
+/// For an enum `E`, the derive generates:
+///
+/// * A unit struct `ENamespace` implementing `dyn_symbol::namespace::Static`, with the same visibility as `E`.
+/// * One associated `Symbol` constant per variant, named in `SCREAMING_SNAKE_CASE`.
+/// * `From<E> for Symbol` and `TryFrom<&Symbol> for E`. The conversion error is the unconverted symbol.
+///
+/// The namespace name defaults to the enum's name, and symbol names default to the variant names.
+/// Both can be overridden using the `static_symbols` attribute:
+///
+/// ```
+/// use dyn_symbol::Symbol;
+/// use dyn_symbol_macros::StaticSymbols;
+/// use std::convert::TryFrom;
+///
+/// #[derive(StaticSymbols, Clone, Copy, PartialEq, Debug)]
+/// #[static_symbols(namespace = "html")]
+/// enum Attr {
+///     #[static_symbols(name = "class")]
+///     Class,
+///     #[static_symbols(name = "tab-index")]
+///     TabIndex,
+/// }
+///
+/// assert_eq!(format!("{:?}", Attr::TAB_INDEX), "html::tab-index");
+/// assert_eq!(Symbol::from(Attr::Class), Attr::CLASS);
+/// assert_eq!(Attr::try_from(&Attr::TAB_INDEX), Ok(Attr::TabIndex));
+/// assert!(Attr::TAB_INDEX.downcast_static::<AttrNamespace>().is_some());
+/// ```
+///
+#[proc_macro_derive(StaticSymbols, attributes(static_symbols))]
+pub fn derive_static_symbols(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match static_symbols::derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+///
+/// Derive `dyn_symbol::namespace::Dynamic` for a type that already implements `Clone`, `Eq`, `Ord` and `Hash`.
+///
+/// The `namespace` attribute configures the namespace name, and which field holds the symbol name.
+/// The field may be omitted for types with exactly one field. A `From<T> for Symbol` conversion is also generated.
+///
+/// ```
+/// use dyn_symbol::Symbol;
+/// use dyn_symbol_macros::DynamicNamespace;
+///
+/// #[derive(DynamicNamespace, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// #[namespace(name = "data", symbol_name_field = "0")]
+/// struct Data(String);
+///
+/// let foo = Symbol::from(Data("foo".into()));
+///
+/// assert_eq!(foo, foo.clone());
+/// assert_eq!(format!("{:?}", foo), "data::foo");
+/// ```
+///
+#[proc_macro_derive(DynamicNamespace, attributes(namespace))]
+pub fn derive_dynamic_namespace(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match dynamic_namespace::derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+///
+/// One or more namespace declarations.
+///
+pub struct Namespaces(Vec<Namespace>);
+
+///
+/// `$vis $ident [= "name"] { $symbols }`
+///
+pub struct Namespace {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    name: Option<syn::LitStr>,
+    symbols: Punctuated<Symbol, syn::Token![,]>,
+}
+
+///
+/// `$ident = "name"`
+///
+pub struct Symbol {
+    attrs: Vec<syn::Attribute>,
+    ident: syn::Ident,
+    name: syn::LitStr,
+}
+
+impl Parse for Namespaces {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut namespaces = vec![];
+        while !input.is_empty() {
+            namespaces.push(input.parse()?);
+        }
+        Ok(Self(namespaces))
+    }
+}
+
+impl Parse for Namespace {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        let name = if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let content;
+        syn::braced!(content in input);
+        let symbols = content.parse_terminated(Symbol::parse, syn::Token![,])?;
+
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            name,
+            symbols,
+        })
+    }
+}
+
+impl Parse for Symbol {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let ident = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let name = input.parse()?;
+
+        Ok(Self { attrs, ident, name })
+    }
+}
+
+pub fn generate(namespaces: Namespaces) -> TokenStream {
+    namespaces.0.into_iter().map(generate_namespace).collect()
+}
+
+fn generate_namespace(namespace: Namespace) -> TokenStream {
+    let Namespace {
+        attrs,
+        vis,
+        ident,
+        name,
+        symbols,
+    } = namespace;
+
+    let namespace_name = name.unwrap_or_else(|| syn::LitStr::new(&ident.to_string(), ident.span()));
+
+    let symbol_names = symbols.iter().map(|symbol| &symbol.name);
+    let by_name_arms = symbols.iter().enumerate().map(|(id, symbol)| {
+        let name = &symbol.name;
+        let id = id as u32;
+        quote! { #name => ::core::option::Option::Some(#id), }
+    });
+    let symbol_consts = symbols.iter().enumerate().map(|(id, symbol)| {
+        let Symbol { attrs, ident, .. } = symbol;
+        let id = id as u32;
+
+        quote! {
+            #(#attrs)*
+            pub const #ident: ::dyn_symbol::Symbol = ::dyn_symbol::Symbol::Static(&Namespace, #id);
+        }
+    });
+
+    quote! {
+        #(#attrs)*
+        #vis mod #ident {
+            /// The static namespace type.
+            pub struct Namespace;
+
+            impl Namespace {
+                const SYMBOL_NAMES: &'static [&'static str] = &[#(#symbol_names),*];
+            }
+
+            impl ::dyn_symbol::namespace::Static for Namespace {
+                fn namespace_name(&self) -> &str {
+                    #namespace_name
+                }
+
+                fn symbol_count(&self) -> u32 {
+                    Self::SYMBOL_NAMES.len() as u32
+                }
+
+                fn symbol_name(&self, id: u32) -> &str {
+                    Self::SYMBOL_NAMES[id as usize]
+                }
+
+                fn unique_names(&self) -> bool {
+                    true
+                }
+
+                fn symbol_by_name(&self, name: &str) -> ::core::option::Option<u32> {
+                    match name {
+                        #(#by_name_arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            #(#symbol_consts)*
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+use crate::case::screaming_snake_case;
+
+struct Variant {
+    ident: syn::Ident,
+    const_ident: syn::Ident,
+    name: syn::LitStr,
+}
+
+pub fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let data = match &input.data {
+        syn::Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "StaticSymbols can only be derived for enums",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "StaticSymbols cannot be derived for generic enums",
+        ));
+    }
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let namespace_ident = format_ident!("{}Namespace", ident);
+    let namespace_name = parse_name_attr(&input.attrs, "namespace")?
+        .unwrap_or_else(|| syn::LitStr::new(&ident.to_string(), ident.span()));
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, syn::Fields::Unit) {
+                return Err(syn::Error::new(
+                    variant.fields.span(),
+                    "StaticSymbols variants cannot have fields",
+                ));
+            }
+
+            let name = parse_name_attr(&variant.attrs, "name")?.unwrap_or_else(|| {
+                syn::LitStr::new(&variant.ident.to_string(), variant.ident.span())
+            });
+
+            Ok(Variant {
+                ident: variant.ident.clone(),
+                const_ident: format_ident!("{}", screaming_snake_case(&variant.ident.to_string())),
+                name,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let symbol_names = variants.iter().map(|variant| &variant.name);
+    let by_name_arms = variants.iter().enumerate().map(|(id, variant)| {
+        let name = &variant.name;
+        let id = id as u32;
+        quote! { #name => ::core::option::Option::Some(#id), }
+    });
+    let namespace_doc = format!("Static namespace derived from [{}].", ident);
+
+    let symbol_consts = variants.iter().enumerate().map(|(id, variant)| {
+        let Variant {
+            ident: variant_ident,
+            const_ident,
+            ..
+        } = variant;
+        let id = id as u32;
+        let doc = format!("The symbol for [{}::{}].", ident, variant_ident);
+
+        quote! {
+            #[doc = #doc]
+            pub const #const_ident: ::dyn_symbol::Symbol =
+                ::dyn_symbol::Symbol::Static(&#namespace_ident, #id);
+        }
+    });
+
+    let to_id_arms = variants.iter().enumerate().map(|(id, variant)| {
+        let variant_ident = &variant.ident;
+        let id = id as u32;
+        quote! { #ident::#variant_ident => #id, }
+    });
+
+    let from_id_arms = variants.iter().enumerate().map(|(id, variant)| {
+        let variant_ident = &variant.ident;
+        let id = id as u32;
+        quote! { ::core::option::Option::Some((_, #id)) => ::core::result::Result::Ok(#ident::#variant_ident), }
+    });
+
+    Ok(quote! {
+        #[doc = #namespace_doc]
+        #vis struct #namespace_ident;
+
+        impl #namespace_ident {
+            const SYMBOL_NAMES: &'static [&'static str] = &[#(#symbol_names),*];
+        }
+
+        impl ::dyn_symbol::namespace::Static for #namespace_ident {
+            fn namespace_name(&self) -> &str {
+                #namespace_name
+            }
+
+            fn symbol_count(&self) -> u32 {
+                Self::SYMBOL_NAMES.len() as u32
+            }
+
+            fn symbol_name(&self, id: u32) -> &str {
+                Self::SYMBOL_NAMES[id as usize]
+            }
+
+            fn unique_names(&self) -> bool {
+                true
+            }
+
+            fn symbol_by_name(&self, name: &str) -> ::core::option::Option<u32> {
+                match name {
+                    #(#by_name_arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        impl #ident {
+            #(#symbol_consts)*
+        }
+
+        impl ::core::convert::From<#ident> for ::dyn_symbol::Symbol {
+            fn from(value: #ident) -> Self {
+                let id = match value {
+                    #(#to_id_arms)*
+                };
+                ::dyn_symbol::Symbol::Static(&#namespace_ident, id)
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<&'a ::dyn_symbol::Symbol> for #ident {
+            type Error = &'a ::dyn_symbol::Symbol;
+
+            fn try_from(symbol: &'a ::dyn_symbol::Symbol) -> ::core::result::Result<Self, Self::Error> {
+                match symbol.downcast_static::<#namespace_ident>() {
+                    #(#from_id_arms)*
+                    _ => ::core::result::Result::Err(symbol),
+                }
+            }
+        }
+    })
+}
+
+///
+/// Parse `#[static_symbols(key = "value")]`.
+///
+fn parse_name_attr(attrs: &[syn::Attribute], key: &str) -> syn::Result<Option<syn::LitStr>> {
+    let mut output = None;
+
+    for attr in attrs {
+        if !attr.path().is_ident("static_symbols") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                output = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error(format!("expected `{}`", key)))
+            }
+        })?;
+    }
+
+    Ok(output)
+}
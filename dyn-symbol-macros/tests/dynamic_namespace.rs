@@ -0,0 +1,39 @@
+use dyn_symbol::Symbol;
+use dyn_symbol_macros::DynamicNamespace;
+
+#[derive(DynamicNamespace, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[namespace(name = "data")]
+struct Data(String);
+
+#[derive(DynamicNamespace, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[namespace(name = "prefixed", symbol_name_field = "local")]
+struct Prefixed {
+    prefix: u8,
+    local: String,
+}
+
+#[test]
+fn test_single_field() {
+    let foo = Symbol::from(Data("foo".into()));
+
+    assert_eq!(foo, Symbol::from(Data("foo".into())));
+    assert_ne!(foo, Symbol::from(Data("bar".into())));
+    assert_eq!(format!("{:?}", foo.clone()), "data::foo");
+}
+
+#[test]
+fn test_symbol_name_field() {
+    let a = Symbol::from(Prefixed {
+        prefix: 0,
+        local: "foo".into(),
+    });
+    let b = Symbol::from(Prefixed {
+        prefix: 1,
+        local: "foo".into(),
+    });
+
+    assert_eq!(a.name(), "foo");
+    assert_ne!(a, b);
+    assert!(a < b);
+    assert_eq!(b.downcast_dyn::<Prefixed>().unwrap().prefix, 1);
+}
@@ -0,0 +1,75 @@
+use dyn_symbol::Symbol;
+use dyn_symbol_macros::static_namespace;
+
+static_namespace! {
+    /// Documented namespace
+    pub html {
+        /// The `class` attribute
+        CLASS = "class",
+        ID = "id",
+        STYLE = "style"
+    }
+
+    other = "other-ns" {
+        CLASS = "class",
+    }
+
+    empty {}
+}
+
+#[test]
+fn test_sequential_ids() {
+    assert_eq!(
+        html::CLASS.downcast_static::<html::Namespace>().unwrap().1,
+        0
+    );
+    assert_eq!(html::ID.downcast_static::<html::Namespace>().unwrap().1, 1);
+    assert_eq!(
+        html::STYLE.downcast_static::<html::Namespace>().unwrap().1,
+        2
+    );
+}
+
+#[test]
+fn test_names() {
+    assert_eq!(html::STYLE.name(), "style");
+    assert_eq!(format!("{:?}", html::ID), "html::id");
+    assert_eq!(format!("{:?}", other::CLASS), "other-ns::class");
+}
+
+#[test]
+fn test_distinct_namespaces() {
+    assert_eq!(html::CLASS, html::CLASS.clone());
+    assert_ne!(html::CLASS, other::CLASS);
+    assert!(other::CLASS.downcast_static::<html::Namespace>().is_none());
+}
+
+#[test]
+fn test_symbol_by_name() {
+    assert_eq!(
+        Symbol::resolve_static(&html::Namespace, "id"),
+        Some(html::ID)
+    );
+    assert_eq!(Symbol::resolve_static(&html::Namespace, "href"), None);
+    assert_eq!(Symbol::resolve_static(&empty::Namespace, "id"), None);
+}
+
+#[test]
+fn test_unique_names() {
+    let mut validator = dyn_symbol::validation::Validator::new();
+    validator.register(&html::Namespace);
+
+    assert!(dyn_symbol::namespace::Static::unique_names(
+        &html::Namespace
+    ));
+    assert_eq!(validator.validate(), Ok(()));
+}
+
+#[test]
+fn test_iter_static() {
+    assert_eq!(
+        Symbol::iter_static(&html::Namespace).collect::<Vec<_>>(),
+        vec![html::CLASS, html::ID, html::STYLE]
+    );
+    assert_eq!(Symbol::iter_static(&empty::Namespace).count(), 0);
+}
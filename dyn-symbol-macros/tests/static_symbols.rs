@@ -0,0 +1,59 @@
+use dyn_symbol::Symbol;
+use dyn_symbol_macros::StaticSymbols;
+use std::convert::TryFrom;
+
+#[derive(StaticSymbols, Clone, Copy, PartialEq, Eq, Debug)]
+#[static_symbols(namespace = "html")]
+pub enum Attr {
+    #[static_symbols(name = "class")]
+    Class,
+    #[static_symbols(name = "id")]
+    Id,
+    #[static_symbols(name = "tabindex")]
+    TabIndex,
+}
+
+#[derive(StaticSymbols, Clone, Copy, PartialEq, Eq, Debug)]
+enum Event {
+    Click = 10,
+    KeyDown = 5,
+}
+
+#[test]
+fn test_consts() {
+    assert_eq!(format!("{:?}", Attr::CLASS), "html::class");
+    assert_eq!(format!("{:?}", Attr::TAB_INDEX), "html::tabindex");
+    assert_eq!(format!("{:?}", Event::KEY_DOWN), "Event::KeyDown");
+    assert_ne!(Attr::CLASS, Attr::ID);
+}
+
+#[test]
+fn test_roundtrip() {
+    for attr in [Attr::Class, Attr::Id, Attr::TabIndex].iter().copied() {
+        assert_eq!(Attr::try_from(&Symbol::from(attr)), Ok(attr));
+    }
+
+    // ids are sequential, regardless of explicit discriminants:
+    assert_eq!(
+        Event::KEY_DOWN
+            .downcast_static::<EventNamespace>()
+            .unwrap()
+            .1,
+        1
+    );
+    assert_eq!(Event::try_from(&Event::CLICK), Ok(Event::Click));
+}
+
+#[test]
+fn test_foreign_symbol() {
+    assert_eq!(Attr::try_from(&Event::CLICK), Err(&Event::CLICK));
+}
+
+#[test]
+fn test_symbol_by_name() {
+    assert_eq!(
+        Symbol::resolve_static(&AttrNamespace, "tabindex"),
+        Some(Attr::TAB_INDEX)
+    );
+    assert_eq!(Symbol::resolve_static(&AttrNamespace, "TabIndex"), None);
+}
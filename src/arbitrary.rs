@@ -0,0 +1,154 @@
+//!
+//! [proptest](mod@proptest) strategies for generating symbols in property tests.
+//!
+//! [Symbol] implements [Arbitrary], generating symbols from the built-in test namespaces of this module:
+//! the static [ARBITRARY_STATIC], the dynamic [ArbitraryDynamic] and the inline [ARBITRARY_INLINE].
+//! Symbols of one's own namespaces are generated with [static_symbol], [dynamic_symbol] and [inline_symbol].
+//!
+//! ```
+//! use dyn_symbol::Symbol;
+//! use proptest::prelude::*;
+//! use std::collections::BTreeSet;
+//!
+//! proptest! {
+//!     fn sets_are_sorted(symbols in proptest::collection::vec(any::<Symbol>(), 0..10)) {
+//!         let set: BTreeSet<Symbol> = symbols.into_iter().collect();
+//!         let sorted: Vec<_> = set.iter().cloned().collect();
+//!
+//!         prop_assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+//!     }
+//! }
+//! # fn main() { sets_are_sorted(); }
+//! ```
+//!
+
+use crate::inline::InlineNamespace;
+use crate::{namespace, Symbol};
+
+use proptest::prelude::*;
+
+///
+/// A static namespace of a few symbols, used by [`any::<Symbol>()`](any).
+///
+#[derive(Debug)]
+pub struct ArbitraryStatic;
+
+const STATIC_NAMES: [&str; 8] = [
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel",
+];
+
+impl namespace::Static for ArbitraryStatic {
+    fn namespace_name(&self) -> &str {
+        "arbitrary_static"
+    }
+
+    fn symbol_count(&self) -> u32 {
+        STATIC_NAMES.len() as u32
+    }
+
+    fn symbol_name(&self, id: u32) -> &str {
+        STATIC_NAMES[id as usize]
+    }
+
+    fn symbol_by_name(&self, name: &str) -> Option<u32> {
+        STATIC_NAMES
+            .iter()
+            .position(|n| *n == name)
+            .map(|id| id as u32)
+    }
+}
+
+///
+/// The [ArbitraryStatic] namespace.
+///
+pub static ARBITRARY_STATIC: ArbitraryStatic = ArbitraryStatic;
+
+///
+/// A dynamic namespace of any names, used by [`any::<Symbol>()`](any).
+///
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ArbitraryDynamic(pub String);
+
+impl namespace::DynValue for ArbitraryDynamic {
+    fn namespace_name(&self) -> &str {
+        "arbitrary_dynamic"
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.0
+    }
+}
+
+///
+/// An inline namespace, used by [`any::<Symbol>()`](any).
+///
+pub static ARBITRARY_INLINE: InlineNamespace = InlineNamespace::new("arbitrary_inline");
+
+/// Short names, so that generated symbols are often equal, and names longer than the inline capacity.
+const NAME_PATTERN: &str = "[a-d]{1,3}|[a-z]{1,24}";
+
+///
+/// Generate symbols of the static namespace `ns`, which must have at least one symbol.
+///
+pub fn static_symbol<NS: namespace::Static>(ns: &'static NS) -> impl Strategy<Value = Symbol> {
+    assert!(ns.symbol_count() > 0, "namespace has no symbols");
+    (0..ns.symbol_count()).prop_map(move |id| Symbol::Static(ns, id))
+}
+
+///
+/// Generate dynamic symbols by passing names generated by `names` to `constructor`.
+///
+pub fn dynamic_symbol<S, F>(names: S, constructor: F) -> impl Strategy<Value = Symbol>
+where
+    S: Strategy<Value = String>,
+    F: Fn(String) -> Symbol,
+{
+    names.prop_map(constructor)
+}
+
+///
+/// Generate symbols of the inline namespace `ns`, with names both shorter and longer than the inline capacity.
+///
+pub fn inline_symbol(ns: &'static InlineNamespace) -> impl Strategy<Value = Symbol> {
+    NAME_PATTERN.prop_map(move |name| ns.symbol(&name))
+}
+
+impl Arbitrary for Symbol {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Symbol>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            static_symbol(&ARBITRARY_STATIC),
+            dynamic_symbol(NAME_PATTERN, |name| {
+                Symbol::from(namespace::DynWrap(ArbitraryDynamic(name)))
+            }),
+            inline_symbol(&ARBITRARY_INLINE),
+        ]
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::BuildHasher;
+
+    proptest! {
+        #[test]
+        fn test_eq_hash_consistency(a in any::<Symbol>(), b in any::<Symbol>()) {
+            let random_state = std::collections::hash_map::RandomState::new();
+
+            prop_assert_eq!(a == b, a.cmp(&b) == std::cmp::Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            if a == b {
+                prop_assert_eq!(random_state.hash_one(&a), random_state.hash_one(&b));
+            }
+        }
+
+        #[test]
+        fn test_namespaces(symbol in static_symbol(&ARBITRARY_STATIC)) {
+            prop_assert!(symbol.downcast_static::<ArbitraryStatic>().is_some());
+        }
+    }
+}
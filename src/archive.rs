@@ -0,0 +1,381 @@
+//!
+//! [rkyv] zero-copy archiving of symbols, for memory-mapped symbol tables.
+//!
+//! A [Symbol] archives as an [ArchivedSymbol], which is resolved back into a [Symbol] on access:
+//!
+//! * Static symbols archive as the [namespace_uid](crate::namespace::Static::namespace_uid) of their namespace and their `id`.
+//!   Namespaces need a uid, since type ids are not stable between builds, and are looked up in the [registry]
+//!   by uid, so they must be registered before archived symbols are accessed.
+//! * [Wide](crate::wide) symbols archive the same way, with their `u64` id, and their namespaces are
+//!   [registered](crate::registry::register_wide) as wide namespaces.
+//! * Dynamic symbols archive as their namespace name and the byte encoding provided by the namespace's
+//!   `archive_bytes` method, and are decoded by a [registered decoder](register_decoder) for that namespace name.
+//!   Inline symbols archive as their namespace name and the bytes of their name.
+//! * [Indexed](crate::indexed) symbols are not archivable.
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! struct Html;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn namespace_uid(&self) -> Option<u128> {
+//!         Some(0x3f1c_72a0_8e4d_4b6a_9c1d_0e5f_2a7b_8c9d)
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["id", "class"][id as usize]
+//!     }
+//! }
+//!
+//! registry::register(&Html);
+//!
+//! let table = vec![Symbol::Static(&Html, 1), Symbol::Static(&Html, 0)];
+//! let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&table).unwrap();
+//!
+//! // No deserialization of the whole table needed:
+//! let archived = rkyv::access::<rkyv::Archived<Vec<Symbol>>, rkyv::rancor::Error>(&bytes).unwrap();
+//! assert_eq!(archived[0].to_symbol(), Some(Symbol::Static(&Html, 1)));
+//! ```
+//!
+
+use crate::{registry, Symbol};
+
+use rkyv::rancor::{Fallible, Source};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+type DecoderFn = dyn Fn(&[u8]) -> Option<Symbol> + Send + Sync;
+
+fn decoders() -> &'static RwLock<HashMap<String, Arc<DecoderFn>>> {
+    static DECODERS: OnceLock<RwLock<HashMap<String, Arc<DecoderFn>>>> = OnceLock::new();
+    DECODERS.get_or_init(Default::default)
+}
+
+///
+/// Register a decoder for archived dynamic symbols in the namespace named `namespace_name`.
+///
+/// The decoder receives the bytes produced by the namespace's `archive_bytes`, and returns `None`
+/// if they're not recognized. Registering a decoder for a namespace name that already has one
+/// replaces the old decoder.
+///
+pub fn register_decoder<F>(namespace_name: &str, decode: F)
+where
+    F: Fn(&[u8]) -> Option<Symbol> + Send + Sync + 'static,
+{
+    decoders()
+        .write()
+        .unwrap()
+        .insert(namespace_name.into(), Arc::new(decode));
+}
+
+#[derive(Archive, Serialize)]
+#[rkyv(archived = ArchivedRepr)]
+enum Repr {
+    Static { namespace_uid: u128, id: u32 },
+    Wide { namespace_uid: u128, id: u64 },
+    Dynamic { namespace: String, bytes: Vec<u8> },
+}
+
+///
+/// An archived [Symbol].
+///
+#[derive(Portable, rkyv::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(transparent)]
+pub struct ArchivedSymbol(ArchivedRepr);
+
+///
+/// The resolver of an archived [Symbol].
+///
+/// Keeps the representation built while serializing, so resolving doesn't build it again.
+///
+pub struct SymbolResolver {
+    repr: Repr,
+    resolver: ReprResolver,
+}
+
+impl ArchivedSymbol {
+    ///
+    /// Resolve the archived symbol, using the [registry] for static and wide symbols and the
+    /// [registered decoders](register_decoder) for dynamic symbols.
+    ///
+    /// Returns `None` if the namespace is not registered, or doesn't recognize the symbol.
+    ///
+    pub fn to_symbol(&self) -> Option<Symbol> {
+        match &self.0 {
+            ArchivedRepr::Static { namespace_uid, id } => {
+                let ns = registry::static_by_uid(namespace_uid.to_native())?;
+                let id = id.to_native();
+
+                if id < ns.symbol_count() {
+                    Some(Symbol::Static(ns, id))
+                } else {
+                    None
+                }
+            }
+            ArchivedRepr::Wide { namespace_uid, id } => {
+                let ns = registry::wide_by_uid(namespace_uid.to_native())?;
+                Some(ns.symbol(id.to_native()))
+            }
+            ArchivedRepr::Dynamic { namespace, bytes } => {
+                let decode = decoders()
+                    .read()
+                    .unwrap()
+                    .get(namespace.as_str())
+                    .cloned()?;
+                decode(bytes.as_slice())
+            }
+        }
+    }
+
+    ///
+    /// The name of the archived symbol's namespace, for dynamic symbols.
+    ///
+    pub fn dynamic_namespace_name(&self) -> Option<&str> {
+        match &self.0 {
+            ArchivedRepr::Static { .. } | ArchivedRepr::Wide { .. } => None,
+            ArchivedRepr::Dynamic { namespace, .. } => Some(namespace.as_str()),
+        }
+    }
+}
+
+impl Symbol {
+    fn archive_repr(&self) -> Result<Repr, ArchiveError> {
+        match self {
+            Self::Static(ns, id) => Ok(Repr::Static {
+                namespace_uid: ns
+                    .namespace_uid()
+                    .ok_or_else(|| ArchiveError::NoNamespaceUid(self.namespace().into()))?,
+                id: *id,
+            }),
+            Self::StaticWide(ns, id) => Ok(Repr::Wide {
+                namespace_uid: ns
+                    .namespace()
+                    .namespace_uid()
+                    .ok_or_else(|| ArchiveError::NoNamespaceUid(self.namespace().into()))?,
+                id: *id,
+            }),
+            // Resolving needs a `'static` base symbol, which can't be created on access.
+            Self::StaticIndexed(_, _) => Err(ArchiveError::NotArchivable(format!("{:?}", self))),
+            Self::Dynamic(instance) => Ok(Repr::Dynamic {
+                namespace: instance.namespace_name().into(),
+                bytes: instance
+                    .archive_bytes()
+                    .ok_or_else(|| ArchiveError::NotArchivable(format!("{:?}", self)))?
+                    .into_owned(),
+            }),
+            Self::Inline(ns, name) => Ok(Repr::Dynamic {
+                namespace: ns.name().into(),
+                bytes: name.as_str().as_bytes().to_vec(),
+            }),
+        }
+    }
+}
+
+impl Archive for Symbol {
+    type Archived = ArchivedSymbol;
+    type Resolver = SymbolResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // Safety: ArchivedSymbol is a transparent wrapper around ArchivedRepr.
+        let out = unsafe { out.cast_unchecked::<ArchivedRepr>() };
+        resolver.repr.resolve(resolver.resolver, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Symbol
+where
+    S::Error: Source,
+    Repr: Serialize<S>,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let repr = self.archive_repr().map_err(S::Error::new)?;
+        let resolver = repr.serialize(serializer)?;
+        Ok(SymbolResolver { repr, resolver })
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Symbol, D> for ArchivedSymbol
+where
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Symbol, D::Error> {
+        self.to_symbol()
+            .ok_or_else(|| D::Error::new(ArchiveError::Unresolved))
+    }
+}
+
+///
+/// Error returned when a symbol cannot be archived or resolved.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArchiveError {
+    /// The static or wide namespace has no [namespace_uid](crate::namespace::Static::namespace_uid).
+    NoNamespaceUid(String),
+    /// The symbol is indexed, or its dynamic namespace doesn't implement `archive_bytes`.
+    NotArchivable(String),
+    /// The namespace of an archived symbol is not registered, or doesn't recognize the symbol.
+    Unresolved,
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NoNamespaceUid(namespace_name) => {
+                write!(f, "namespace `{}` has no uid", namespace_name)
+            }
+            Self::NotArchivable(symbol) => write!(f, "symbol `{}` is not archivable", symbol),
+            Self::Unresolved => write!(f, "could not resolve archived symbol"),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed::IndexedBase;
+    use crate::inline::InlineNamespace;
+    use crate::namespace;
+    use crate::tests::_static::STATIC_NS_CLASS_A;
+    use crate::tests::{dynamic, STATIC_A_0};
+    use crate::wide::WideNamespace;
+
+    struct Uid;
+
+    impl namespace::Static for Uid {
+        fn namespace_name(&self) -> &str {
+            "uid"
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            Some(0x42)
+        }
+
+        fn symbol_count(&self) -> u32 {
+            2
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["a", "b"][id as usize]
+        }
+    }
+
+    struct Cells;
+
+    impl namespace::StaticWide for Cells {
+        fn namespace_name(&self) -> &str {
+            "archive_cells"
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            Some(0x43)
+        }
+
+        fn symbol_name(&self, _: u64) -> &str {
+            "cell"
+        }
+    }
+
+    static CELLS: WideNamespace = WideNamespace::new(&Cells);
+    static INLINE: InlineNamespace = InlineNamespace::new("archive_inline");
+    static A_0: IndexedBase = IndexedBase::new(&STATIC_NS_CLASS_A, 0);
+
+    fn roundtrip(symbols: &[Symbol]) -> Vec<Option<Symbol>> {
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&symbols.to_vec()).unwrap();
+        rkyv::access::<rkyv::Archived<Vec<Symbol>>, rkyv::rancor::Error>(&bytes)
+            .unwrap()
+            .iter()
+            .map(ArchivedSymbol::to_symbol)
+            .collect()
+    }
+
+    #[test]
+    fn test_archive_roundtrip() {
+        registry::register(&Uid);
+        registry::register_wide(&CELLS);
+        register_decoder("archive_inline", |bytes| {
+            Some(INLINE.symbol(std::str::from_utf8(bytes).ok()?))
+        });
+
+        let long = "a name that is too long to be stored inline in the symbol";
+        let symbols = [
+            Symbol::Static(&Uid, 1),
+            CELLS.symbol(u64::MAX),
+            INLINE.symbol("short"),
+            INLINE.symbol(long),
+        ];
+        assert_eq!(
+            roundtrip(&symbols),
+            symbols.iter().cloned().map(Some).collect::<Vec<_>>()
+        );
+
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&symbols[2]).unwrap();
+        let archived = rkyv::access::<ArchivedSymbol, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(archived.dynamic_namespace_name(), Some("archive_inline"));
+        assert_eq!(
+            rkyv::deserialize::<Symbol, rkyv::rancor::Error>(archived).unwrap(),
+            symbols[2]
+        );
+    }
+
+    #[test]
+    fn test_archive_errors() {
+        assert!(rkyv::to_bytes::<rkyv::rancor::Error>(&STATIC_A_0).is_err());
+        assert!(rkyv::to_bytes::<rkyv::rancor::Error>(&A_0.index(1)).is_err());
+        assert!(rkyv::to_bytes::<rkyv::rancor::Error>(&dynamic::sym0("foo")).is_err());
+
+        assert_eq!(
+            STATIC_A_0.archive_repr().err(),
+            Some(ArchiveError::NoNamespaceUid("A".into()))
+        );
+
+        static UNDECODED: InlineNamespace = InlineNamespace::new("archive_undecoded");
+        assert_eq!(roundtrip(&[UNDECODED.symbol("foo")]), [None]);
+    }
+
+    #[test]
+    fn test_archive_encodes_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static ENCODED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Counted(String);
+
+        impl namespace::DynValue for Counted {
+            fn namespace_name(&self) -> &str {
+                "archive_counted"
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.0
+            }
+
+            fn archive_bytes(&self) -> Option<std::borrow::Cow<'_, [u8]>> {
+                ENCODED.fetch_add(1, Ordering::Relaxed);
+                Some(self.0.as_bytes().into())
+            }
+        }
+
+        register_decoder("archive_counted", |bytes| {
+            let name = std::str::from_utf8(bytes).ok()?;
+            Some(Symbol::from(namespace::DynWrap(Counted(name.into()))))
+        });
+
+        let symbol = Symbol::from(namespace::DynWrap(Counted("foo".into())));
+        assert_eq!(roundtrip(std::slice::from_ref(&symbol)), [Some(symbol)]);
+        assert_eq!(ENCODED.load(Ordering::Relaxed), 1);
+    }
+}
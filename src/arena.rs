@@ -0,0 +1,174 @@
+//!
+//! Bump-allocated dynamic symbols, for parsing workloads that create and discard many symbols.
+//!
+//! A [SymbolArena] allocates string-based dynamic symbols and hands them out as [SymbolRef]s tied to the
+//! arena's lifetime. There is no per-symbol allocation or deallocation: everything is freed wholesale
+//! when the arena is dropped or [reset](SymbolArena::reset). Symbols that need to outlive the arena are
+//! converted with [SymbolRef::to_symbol].
+//!
+//! ```
+//! use dyn_symbol::arena::SymbolArena;
+//!
+//! let arena = SymbolArena::new();
+//!
+//! let a = arena.symbol("data", "data-id");
+//! let b = arena.symbol("data", "data-id");
+//!
+//! assert_eq!(a, b);
+//! assert_eq!(format!("{:?}", a), "data::data-id");
+//!
+//! let owned = a.to_symbol();
+//! drop(arena);
+//! assert_eq!(owned.name(), "data-id");
+//! ```
+//!
+
+use crate::namespace;
+use crate::symbol_ref::SymbolRef;
+
+use alloc::boxed::Box;
+
+///
+/// An arena for string-based dynamic symbols.
+///
+#[derive(Default)]
+pub struct SymbolArena {
+    bump: bumpalo::Bump,
+}
+
+impl SymbolArena {
+    ///
+    /// Create an empty arena.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Allocate the symbol `name` in the namespace `namespace_name`.
+    ///
+    pub fn symbol(&self, namespace_name: &'static str, name: &str) -> SymbolRef<'_> {
+        let name: &str = self.bump.alloc_str(name);
+        let instance: &ArenaName = self.bump.alloc(ArenaName {
+            namespace_name,
+            name: NameStorage::Arena(name),
+        });
+
+        SymbolRef::Dynamic(instance)
+    }
+
+    ///
+    /// The number of bytes currently allocated by the arena.
+    ///
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+
+    ///
+    /// Free all symbols allocated in the arena, keeping its memory for reuse.
+    ///
+    pub fn reset(&mut self) {
+        self.bump.reset()
+    }
+}
+
+///
+/// The dynamic namespace of arena symbols.
+///
+/// Symbols cloned out of the arena by [dyn_clone](namespace::Dynamic::dyn_clone) are instances of the same type,
+/// owning their name, so they compare equal to the arena symbols they were cloned from.
+///
+struct ArenaName {
+    namespace_name: &'static str,
+    name: NameStorage,
+}
+
+enum NameStorage {
+    /// Points into the arena. Only ever dereferenced through an `&ArenaName` borrowed from that arena,
+    /// which is tied to the arena's lifetime by [SymbolArena::symbol].
+    Arena(*const str),
+    Owned(Box<str>),
+}
+
+// SAFETY: the name is immutable, so sharing the pointer between threads is no different from sharing a `&str`.
+unsafe impl Send for NameStorage {}
+unsafe impl Sync for NameStorage {}
+
+impl ArenaName {
+    fn name(&self) -> &str {
+        match &self.name {
+            // SAFETY: see `NameStorage::Arena`.
+            NameStorage::Arena(name) => unsafe { &**name },
+            NameStorage::Owned(name) => name,
+        }
+    }
+}
+
+impl namespace::Dynamic for ArenaName {
+    fn namespace_name(&self) -> &str {
+        self.namespace_name
+    }
+
+    fn symbol_name(&self) -> &str {
+        self.name()
+    }
+
+    fn hash_by_name(&self) -> bool {
+        true
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(ArenaName {
+            namespace_name: self.namespace_name,
+            name: NameStorage::Owned(self.name().into()),
+        })
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        self.namespace_name == rhs.namespace_name && self.name() == rhs.name()
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> core::cmp::Ordering {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        (self.namespace_name, self.name()).cmp(&(rhs.namespace_name, rhs.name()))
+    }
+
+    fn dyn_hash(&self, state: &mut dyn core::hash::Hasher) {
+        state.write(self.namespace_name.as_bytes());
+        state.write_u8(0xff);
+        state.write(self.name().as_bytes());
+        state.write_u8(0xff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_arena_symbols() {
+        let arena = SymbolArena::new();
+        let foo = arena.symbol("ns", "foo");
+
+        assert_eq!(foo, arena.symbol("ns", "foo"));
+        assert_ne!(foo, arena.symbol("ns", "bar"));
+        assert_ne!(foo, arena.symbol("other", "foo"));
+        assert_eq!(foo.name(), "foo");
+        assert_eq!(foo.namespace(), "ns");
+        assert!(arena.allocated_bytes() > 0);
+    }
+
+    #[test]
+    fn test_owned_lookup() {
+        let mut map = HashMap::new();
+
+        let mut arena = SymbolArena::new();
+        map.insert(arena.symbol("ns", "foo").to_symbol(), 42);
+        arena.reset();
+
+        assert_eq!(map.get(arena.symbol("ns", "foo").as_key()), Some(&42));
+        assert_eq!(map.get(arena.symbol("ns", "bar").as_key()), None);
+    }
+}
@@ -0,0 +1,162 @@
+//!
+//! [string_cache] interop, for using existing interned atoms as symbols.
+//!
+//! An [AtomSymbol] is a dynamic symbol holding an [Atom]. Converting an atom into a [Symbol] does not copy
+//! the string, and equality and hashing use the atom's packed representation and precomputed hash.
+//!
+//! ```
+//! use dyn_symbol::atom::AtomSymbol;
+//! use dyn_symbol::Symbol;
+//! use string_cache::DefaultAtom;
+//!
+//! let div = Symbol::from(DefaultAtom::from("div"));
+//!
+//! assert_eq!(div, Symbol::from(DefaultAtom::from("div")));
+//! assert_eq!(format!("{:?}", div), "atom::div");
+//! assert_eq!(div.downcast_dyn::<AtomSymbol>().unwrap().atom(), &DefaultAtom::from("div"));
+//! ```
+//!
+
+use crate::{namespace, Symbol};
+
+use std::sync::Arc;
+use string_cache::{Atom, EmptyStaticAtomSet, StaticAtomSet};
+
+///
+/// The name of the namespace of [AtomSymbol]s.
+///
+pub const NAMESPACE_NAME: &str = "atom";
+
+///
+/// A dynamic symbol that is a [string_cache] atom.
+///
+/// Atoms from different static atom sets `S` are different namespaces.
+///
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct AtomSymbol<S: StaticAtomSet = EmptyStaticAtomSet>(Atom<S>);
+
+impl<S: StaticAtomSet> AtomSymbol<S> {
+    ///
+    /// Wrap an atom.
+    ///
+    pub fn new(atom: Atom<S>) -> Self {
+        Self(atom)
+    }
+
+    ///
+    /// The atom.
+    ///
+    pub fn atom(&self) -> &Atom<S> {
+        &self.0
+    }
+
+    ///
+    /// Unwrap the atom.
+    ///
+    pub fn into_atom(self) -> Atom<S> {
+        self.0
+    }
+}
+
+impl<S: StaticAtomSet> Clone for AtomSymbol<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: StaticAtomSet> std::hash::Hash for AtomSymbol<S> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<S: StaticAtomSet> std::fmt::Debug for AtomSymbol<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<S: StaticAtomSet + Send + Sync + 'static> namespace::Dynamic for AtomSymbol<S> {
+    fn namespace_name(&self) -> &str {
+        NAMESPACE_NAME
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.0
+    }
+
+    fn cached_hash(&self) -> Option<u64> {
+        Some(self.0.get_hash() as u64)
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialize_symbol(&self) -> Option<std::borrow::Cow<'_, str>> {
+        Some(self.symbol_name().into())
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        // Atoms compare by their packed representation, without looking at the string.
+        self.0 == rhs.downcast_ref::<Self>().unwrap().0
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> std::cmp::Ordering {
+        self.0.cmp(&rhs.downcast_ref::<Self>().unwrap().0)
+    }
+
+    fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+        state.write_u32(self.0.get_hash())
+    }
+}
+
+impl<S: StaticAtomSet + Send + Sync + 'static> From<AtomSymbol<S>> for Symbol {
+    fn from(symbol: AtomSymbol<S>) -> Self {
+        Symbol::Dynamic(Arc::new(symbol))
+    }
+}
+
+impl<S: StaticAtomSet + Send + Sync + 'static> From<Atom<S>> for Symbol {
+    fn from(atom: Atom<S>) -> Self {
+        AtomSymbol(atom).into()
+    }
+}
+
+impl<S: StaticAtomSet + Send + Sync + 'static> From<&Atom<S>> for Symbol {
+    fn from(atom: &Atom<S>) -> Self {
+        atom.clone().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use string_cache::DefaultAtom;
+
+    #[test]
+    fn test_atom_symbols() {
+        let long = "a string that is too long to be stored inline in an atom";
+
+        let mut map = HashMap::new();
+        map.insert(Symbol::from(DefaultAtom::from("foo")), 0);
+        map.insert(Symbol::from(DefaultAtom::from(long)), 1);
+
+        assert_eq!(map.get(&Symbol::from(&DefaultAtom::from("foo"))), Some(&0));
+        assert_eq!(map.get(&Symbol::from(DefaultAtom::from(long))), Some(&1));
+        assert_eq!(map.get(&Symbol::from(DefaultAtom::from("bar"))), None);
+
+        let (a, b) = (
+            Symbol::from(DefaultAtom::from("a")),
+            Symbol::from(DefaultAtom::from("b")),
+        );
+        assert!(a < b);
+        assert_eq!(b.name(), "b");
+        assert_eq!(
+            b.into_dynamic::<AtomSymbol>().unwrap().into_atom(),
+            DefaultAtom::from("b")
+        );
+    }
+}
@@ -0,0 +1,641 @@
+//!
+//! Code generation of static namespaces, intended for use from `build.rs`.
+//!
+//! Symbol definitions are read from CSV or JSON, and turned into Rust source code containing
+//! the namespace struct, its [Static](crate::namespace::Static) implementation and one `const` [Symbol](crate::Symbol)
+//! per symbol, and optionally a name lookup table. The output is meant to be written to `OUT_DIR` and `include!`d:
+//!
+//! ```no_run
+//! // build.rs
+//! use dyn_symbol::codegen::StaticNamespace;
+//!
+//! let csv = "const,name,description\n\
+//!            CLASS,class,The class attribute\n\
+//!            ID,id,The id attribute\n";
+//!
+//! StaticNamespace::new("HtmlAttr", "html")
+//!     .with_symbols_from_csv(csv)
+//!     .unwrap()
+//!     .with_lookup_table()
+//!     .write_to_out_dir("html_attr.rs")
+//!     .unwrap();
+//! ```
+//!
+//! ```ignore
+//! // lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/html_attr.rs"));
+//! ```
+//!
+//! The metadata of each symbol is available through [Symbol::metadata](crate::Symbol::metadata) as
+//! a `&'static [(&'static str, &'static str)]` of key/value pairs.
+//!
+//! ## CSV format
+//! The first row is a header. The `name` column is required, and contains the symbol name.
+//! The optional `const` column contains the name of the generated constant. If it's missing or empty,
+//! the constant name is derived from the symbol name. All other columns are stored as metadata.
+//! Fields can be quoted, and quoted fields can contain commas, line breaks and doubled `""` quotes.
+//!
+//! ## JSON format
+//! A JSON array of objects, each with a required `"name"` and optional `"const"` string.
+//! All other string-valued properties are stored as metadata.
+//!
+
+use std::fmt::Write;
+
+///
+/// Error produced while reading symbol definitions or writing generated code.
+///
+#[derive(Debug)]
+pub enum Error {
+    /// I/O failure while writing the output.
+    Io(std::io::Error),
+    /// The `OUT_DIR` environment variable is not set (not running from `build.rs`).
+    NoOutDir,
+    /// Malformed CSV input, with the 1-based line number.
+    Csv { line: usize, message: String },
+    /// Malformed JSON input.
+    Json(String),
+    /// The same symbol name was defined more than once.
+    DuplicateName(String),
+    /// Several symbols have the same constant name, e.g. `data-id` and `data_id` both derive `DATA_ID`.
+    DuplicateConstName(String),
+    /// A name does not form a valid Rust identifier.
+    InvalidIdent(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "io error: {}", err),
+            Self::NoOutDir => write!(f, "OUT_DIR is not set"),
+            Self::Csv { line, message } => write!(f, "csv error on line {}: {}", line, message),
+            Self::Json(message) => write!(f, "json error: {}", message),
+            Self::DuplicateName(name) => write!(f, "duplicate symbol name `{}`", name),
+            Self::DuplicateConstName(name) => write!(f, "duplicate constant name `{}`", name),
+            Self::InvalidIdent(ident) => write!(f, "`{}` is not a valid Rust identifier", ident),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+///
+/// Definition of one symbol in a generated namespace.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolDef {
+    /// The name of the generated constant.
+    pub const_name: String,
+    /// The symbol name.
+    pub name: String,
+    /// Metadata key/value pairs, emitted into the `SYMBOL_METADATA` table.
+    pub metadata: Vec<(String, String)>,
+}
+
+impl SymbolDef {
+    ///
+    /// Define a symbol, deriving the constant name from the symbol name.
+    ///
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            const_name: const_name_from_symbol_name(&name),
+            name,
+            metadata: vec![],
+        }
+    }
+}
+
+///
+/// Builder for a generated static namespace.
+///
+#[derive(Clone, Debug)]
+pub struct StaticNamespace {
+    type_name: String,
+    namespace_name: String,
+    symbols: Vec<SymbolDef>,
+    lookup_table: bool,
+}
+
+impl StaticNamespace {
+    ///
+    /// Create a new namespace. `type_name` is the name of the generated struct,
+    /// `namespace_name` is the name returned from [namespace_name](crate::namespace::Static::namespace_name).
+    ///
+    pub fn new(type_name: impl Into<String>, namespace_name: impl Into<String>) -> Self {
+        Self {
+            type_name: type_name.into(),
+            namespace_name: namespace_name.into(),
+            symbols: vec![],
+            lookup_table: false,
+        }
+    }
+
+    ///
+    /// Add one symbol definition. Symbols are assigned sequential ids in the order they are added.
+    ///
+    pub fn with_symbol(mut self, symbol: SymbolDef) -> Self {
+        self.symbols.push(symbol);
+        self
+    }
+
+    ///
+    /// Add symbol definitions from CSV. See the [module documentation](self) for the format.
+    ///
+    pub fn with_symbols_from_csv(mut self, csv: &str) -> Result<Self, Error> {
+        let mut rows = parse_csv(csv)?.into_iter();
+
+        let (header_line, header) = match rows.next() {
+            Some(row) => row,
+            None => return Ok(self),
+        };
+
+        let name_column = header
+            .iter()
+            .position(|column| column == "name")
+            .ok_or_else(|| Error::Csv {
+                line: header_line,
+                message: "missing `name` column".into(),
+            })?;
+        let const_column = header.iter().position(|column| column == "const");
+
+        for (line, fields) in rows {
+            if fields.len() != header.len() {
+                return Err(Error::Csv {
+                    line,
+                    message: format!("expected {} fields, got {}", header.len(), fields.len()),
+                });
+            }
+
+            let mut symbol = SymbolDef::new(fields[name_column].clone());
+            for (column, value) in fields.into_iter().enumerate() {
+                if column == name_column {
+                    continue;
+                } else if Some(column) == const_column {
+                    if !value.is_empty() {
+                        symbol.const_name = value;
+                    }
+                } else {
+                    symbol.metadata.push((header[column].clone(), value));
+                }
+            }
+
+            self.symbols.push(symbol);
+        }
+
+        Ok(self)
+    }
+
+    ///
+    /// Add symbol definitions from JSON. See the [module documentation](self) for the format.
+    ///
+    pub fn with_symbols_from_json(mut self, json: &str) -> Result<Self, Error> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| Error::Json(err.to_string()))?;
+
+        let array = value
+            .as_array()
+            .ok_or_else(|| Error::Json("expected an array of symbols".into()))?;
+
+        for (index, entry) in array.iter().enumerate() {
+            let object = entry
+                .as_object()
+                .ok_or_else(|| Error::Json(format!("symbol {} is not an object", index)))?;
+
+            let name = object
+                .get("name")
+                .and_then(|name| name.as_str())
+                .ok_or_else(|| Error::Json(format!("symbol {} has no string `name`", index)))?;
+
+            let mut symbol = SymbolDef::new(name);
+            for (key, value) in object {
+                let value = match value.as_str() {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match key.as_str() {
+                    "name" => {}
+                    "const" => symbol.const_name = value.into(),
+                    _ => symbol.metadata.push((key.clone(), value.into())),
+                }
+            }
+
+            self.symbols.push(symbol);
+        }
+
+        Ok(self)
+    }
+
+    ///
+    /// Also generate a sorted `(name, id)` table, used for implementing
+    /// [symbol_by_name](crate::namespace::Static::symbol_by_name).
+    ///
+    pub fn with_lookup_table(mut self) -> Self {
+        self.lookup_table = true;
+        self
+    }
+
+    ///
+    /// Generate the Rust source code.
+    ///
+    pub fn generate(&self) -> Result<String, Error> {
+        validate_ident(&self.type_name)?;
+
+        let mut seen_names = std::collections::HashSet::new();
+        let mut seen_const_names = std::collections::HashSet::new();
+        for symbol in &self.symbols {
+            validate_ident(&symbol.const_name)?;
+            if !seen_names.insert(symbol.name.as_str()) {
+                return Err(Error::DuplicateName(symbol.name.clone()));
+            }
+            if !seen_const_names.insert(symbol.const_name.as_str()) {
+                return Err(Error::DuplicateConstName(symbol.const_name.clone()));
+            }
+        }
+
+        let ty = &self.type_name;
+        let mut out = String::new();
+
+        // Writing to a String is infallible, so unwraps below are fine.
+        writeln!(
+            out,
+            "/// Static namespace `{}`.",
+            self.namespace_name.escape_debug()
+        )
+        .unwrap();
+        writeln!(out, "pub struct {};", ty).unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "impl {} {{", ty).unwrap();
+        writeln!(out, "    /// Symbol names, indexed by id.").unwrap();
+        writeln!(
+            out,
+            "    pub const SYMBOL_NAMES: &'static [&'static str] = &["
+        )
+        .unwrap();
+        for symbol in &self.symbols {
+            writeln!(out, "        {:?},", symbol.name).unwrap();
+        }
+        writeln!(out, "    ];").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(
+            out,
+            "    /// Symbol metadata key/value pairs, indexed by id."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    pub const SYMBOL_METADATA: &'static [&'static [(&'static str, &'static str)]] = &["
+        )
+        .unwrap();
+        for symbol in &self.symbols {
+            write!(out, "        &[").unwrap();
+            for (key, value) in &symbol.metadata {
+                write!(out, "({:?}, {:?}), ", key, value).unwrap();
+            }
+            writeln!(out, "],").unwrap();
+        }
+        writeln!(out, "    ];").unwrap();
+
+        if self.lookup_table {
+            let mut by_name: Vec<(&str, usize)> = self
+                .symbols
+                .iter()
+                .enumerate()
+                .map(|(id, symbol)| (symbol.name.as_str(), id))
+                .collect();
+            by_name.sort();
+
+            writeln!(out).unwrap();
+            writeln!(out, "    /// `(name, id)` pairs, sorted by name.").unwrap();
+            writeln!(
+                out,
+                "    pub const SYMBOLS_BY_NAME: &'static [(&'static str, u32)] = &["
+            )
+            .unwrap();
+            for (name, id) in by_name {
+                writeln!(out, "        ({:?}, {}),", name, id).unwrap();
+            }
+            writeln!(out, "    ];").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "impl ::dyn_symbol::namespace::Static for {} {{", ty).unwrap();
+        writeln!(out, "    fn namespace_name(&self) -> &str {{").unwrap();
+        writeln!(out, "        {:?}", self.namespace_name).unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    fn symbol_count(&self) -> u32 {{").unwrap();
+        writeln!(out, "        {}", self.symbols.len()).unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    fn symbol_name(&self, id: u32) -> &str {{").unwrap();
+        writeln!(out, "        Self::SYMBOL_NAMES[id as usize]").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "    fn metadata(&self, id: u32, type_id: ::core::any::TypeId) -> ::core::option::Option<&dyn ::core::any::Any> {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        if type_id == ::core::any::TypeId::of::<&'static [(&'static str, &'static str)]>() {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "            ::core::option::Option::Some(&Self::SYMBOL_METADATA[id as usize])"
+        )
+        .unwrap();
+        writeln!(out, "        }} else {{").unwrap();
+        writeln!(out, "            ::core::option::Option::None").unwrap();
+        writeln!(out, "        }}").unwrap();
+        writeln!(out, "    }}").unwrap();
+        if self.lookup_table {
+            writeln!(out).unwrap();
+            writeln!(
+                out,
+                "    fn symbol_by_name(&self, name: &str) -> ::core::option::Option<u32> {{"
+            )
+            .unwrap();
+            writeln!(out, "        Self::SYMBOLS_BY_NAME").unwrap();
+            writeln!(
+                out,
+                "            .binary_search_by(|(n, _)| ::core::cmp::Ord::cmp(*n, name))"
+            )
+            .unwrap();
+            writeln!(out, "            .ok()").unwrap();
+            writeln!(
+                out,
+                "            .map(|index| Self::SYMBOLS_BY_NAME[index].1)"
+            )
+            .unwrap();
+            writeln!(out, "    }}").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        for (id, symbol) in self.symbols.iter().enumerate() {
+            writeln!(out).unwrap();
+            writeln!(out, "/// The `{}` symbol.", symbol.name.escape_debug()).unwrap();
+            writeln!(
+                out,
+                "pub const {}: ::dyn_symbol::Symbol = ::dyn_symbol::Symbol::Static(&{}, {});",
+                symbol.const_name, ty, id
+            )
+            .unwrap();
+        }
+
+        Ok(out)
+    }
+
+    ///
+    /// Generate the code and write it to `file_name` inside `OUT_DIR`. Returns the full path of the written file.
+    ///
+    pub fn write_to_out_dir(&self, file_name: &str) -> Result<std::path::PathBuf, Error> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or(Error::NoOutDir)?;
+        let path = std::path::Path::new(&out_dir).join(file_name);
+        std::fs::write(&path, self.generate()?)?;
+        Ok(path)
+    }
+}
+
+fn const_name_from_symbol_name(name: &str) -> String {
+    let mut const_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if const_name.starts_with(|c: char| c.is_ascii_digit()) {
+        const_name.insert(0, '_');
+    }
+
+    const_name
+}
+
+/// Strict and reserved keywords, which can't be used as identifiers.
+const KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn validate_ident(ident: &str) -> Result<(), Error> {
+    let mut chars = ident.chars();
+    let valid = match chars.next() {
+        Some(first) => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && ident != "_"
+                && !KEYWORDS.contains(&ident)
+        }
+        None => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidIdent(ident.into()))
+    }
+}
+
+///
+/// Parse CSV into rows of fields, each with its 1-based starting line number. Blank lines are skipped.
+///
+fn parse_csv(csv: &str) -> Result<Vec<(usize, Vec<String>)>, Error> {
+    let mut rows = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = csv.chars().peekable();
+    let mut quoted = false;
+    let mut has_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+
+    loop {
+        let c = chars.next();
+        match (quoted, c) {
+            (true, None) => {
+                return Err(Error::Csv {
+                    line: row_line,
+                    message: "unterminated quoted field".into(),
+                })
+            }
+            (true, Some('"')) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, Some('"')) => quoted = false,
+            (_, Some('\r')) if chars.peek() == Some(&'\n') => {}
+            (true, Some(c)) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+            (false, Some('"')) if field.is_empty() => {
+                quoted = true;
+                has_quotes = true;
+            }
+            (false, Some(',')) => fields.push(std::mem::take(&mut field)),
+            (false, Some('\n')) | (false, None) => {
+                if has_quotes || !fields.is_empty() || !field.trim().is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                    rows.push((row_line, std::mem::take(&mut fields)));
+                }
+                field.clear();
+                has_quotes = false;
+
+                if c.is_none() {
+                    return Ok(rows);
+                }
+                line += 1;
+                row_line = line;
+            }
+            (false, Some(c)) => field.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv() {
+        let namespace = StaticNamespace::new("Html", "html")
+            .with_symbols_from_csv(
+                "name,const,description\n\
+                 class,,\"The \"\"class\"\" attribute\"\n\
+                 data-id,DATA_ID_ATTR,\n",
+            )
+            .unwrap();
+
+        assert_eq!(
+            namespace.symbols,
+            vec![
+                SymbolDef {
+                    const_name: "CLASS".into(),
+                    name: "class".into(),
+                    metadata: vec![("description".into(), "The \"class\" attribute".into())],
+                },
+                SymbolDef {
+                    const_name: "DATA_ID_ATTR".into(),
+                    name: "data-id".into(),
+                    metadata: vec![("description".into(), "".into())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_multiline() {
+        let namespace = StaticNamespace::new("Html", "html")
+            .with_symbols_from_csv(
+                "name,description\r\n\
+                 class,\"The class,\r\nattribute\"\r\n\
+                 \r\n\
+                 id,\r\n",
+            )
+            .unwrap();
+
+        assert_eq!(
+            namespace.symbols[0].metadata,
+            [("description".into(), "The class,\nattribute".into())]
+        );
+        assert_eq!(namespace.symbols[1].name, "id");
+
+        let unterminated =
+            StaticNamespace::new("Html", "html").with_symbols_from_csv("name\nclass\n\"id\n\n");
+        assert!(matches!(
+            unterminated,
+            Err(Error::Csv { line: 3, message }) if message == "unterminated quoted field"
+        ));
+
+        let misaligned =
+            StaticNamespace::new("Html", "html").with_symbols_from_csv("name\n\"a\nb\"\nc,d\n");
+        assert!(matches!(misaligned, Err(Error::Csv { line: 4, .. })));
+    }
+
+    #[test]
+    fn test_json() {
+        let namespace = StaticNamespace::new("Html", "html")
+            .with_symbols_from_json(r#"[{"name": "tab-index", "doc": "Tab order"}]"#)
+            .unwrap();
+
+        assert_eq!(
+            namespace.symbols,
+            vec![SymbolDef {
+                const_name: "TAB_INDEX".into(),
+                name: "tab-index".into(),
+                metadata: vec![("doc".into(), "Tab order".into())],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let code = StaticNamespace::new("Html", "html")
+            .with_symbol(SymbolDef::new("id"))
+            .with_symbol(SymbolDef::new("class"))
+            .with_lookup_table()
+            .generate()
+            .unwrap();
+
+        assert!(code.contains("pub struct Html;"));
+        assert!(code.contains("impl ::dyn_symbol::namespace::Static for Html {"));
+        assert!(code.contains(
+            "pub const CLASS: ::dyn_symbol::Symbol = ::dyn_symbol::Symbol::Static(&Html, 1);"
+        ));
+        assert!(code.contains("(\"class\", 1),\n        (\"id\", 0),"));
+        // Paths are fully qualified, so that user definitions of e.g. `Option` don't break the code:
+        assert!(code.contains("-> ::core::option::Option<u32> {"));
+        assert!(!code.contains(" Some(") && !code.contains(" None"));
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let duplicate = StaticNamespace::new("Html", "html")
+            .with_symbol(SymbolDef::new("id"))
+            .with_symbol(SymbolDef::new("id"));
+        assert!(matches!(duplicate.generate(), Err(Error::DuplicateName(_))));
+
+        let duplicate_const = StaticNamespace::new("Html", "html")
+            .with_symbol(SymbolDef::new("data-id"))
+            .with_symbol(SymbolDef::new("data_id"));
+        assert!(matches!(
+            duplicate_const.generate(),
+            Err(Error::DuplicateConstName(name)) if name == "DATA_ID"
+        ));
+
+        let missing_name =
+            StaticNamespace::new("Html", "html").with_symbols_from_csv("\n\nconst\nCLASS\n");
+        assert!(matches!(missing_name, Err(Error::Csv { line: 3, .. })));
+
+        let invalid = StaticNamespace::new("Html-Attrs", "html");
+        assert!(matches!(invalid.generate(), Err(Error::InvalidIdent(_))));
+
+        let keyword = StaticNamespace::new("Html", "html").with_symbol(SymbolDef {
+            const_name: "self".into(),
+            ..SymbolDef::new("self")
+        });
+        assert!(matches!(keyword.generate(), Err(Error::InvalidIdent(name)) if name == "self"));
+        assert!(StaticNamespace::new("Self", "html").generate().is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//!
+//! Compact integer codes for static symbols, for columnar storage and IPC.
+//!
+//! Static namespaces registered with [register] get a 16-bit namespace code. A symbol's code combines the
+//! namespace code with the symbol's `id`, so symbol-heavy data can be flattened into dense `u64` arrays:
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! struct Html;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["id", "class"][id as usize]
+//!     }
+//! }
+//!
+//! codes::register(&Html).unwrap();
+//!
+//! let class = Symbol::Static(&Html, 1);
+//! let code = class.to_code().unwrap();
+//!
+//! assert_eq!(Symbol::from_code(code), Some(class));
+//! ```
+//!
+//! Codes are assigned in registration order, and only mean something within one process. When codes cross
+//! process boundaries, each side must register the same namespaces in the same order, or pick fixed codes
+//! using [register_as].
+//!
+
+use crate::{namespace, NamespaceId, Symbol};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{OnceLock, RwLock};
+
+#[derive(Default)]
+struct Codes {
+    by_namespace: HashMap<NamespaceId, u16>,
+    namespaces: HashMap<u16, &'static dyn namespace::Static>,
+    next_code: u32,
+}
+
+fn codes() -> &'static RwLock<Codes> {
+    static CODES: OnceLock<RwLock<Codes>> = OnceLock::new();
+    CODES.get_or_init(Default::default)
+}
+
+///
+/// Register a static namespace, returning its namespace code.
+///
+/// Registering an already registered namespace returns its existing code.
+///
+pub fn register(ns: &'static dyn namespace::Static) -> Result<u16, CodeError> {
+    let mut codes = codes().write().unwrap();
+    if let Some(code) = codes.by_namespace.get(&NamespaceId::of_static(ns)) {
+        return Ok(*code);
+    }
+
+    let code = loop {
+        let code = u16::try_from(codes.next_code).map_err(|_| CodeError::Exhausted)?;
+        codes.next_code += 1;
+        if !codes.namespaces.contains_key(&code) {
+            break code;
+        }
+    };
+
+    codes.insert(ns, code);
+    Ok(code)
+}
+
+///
+/// Register a static namespace with a fixed namespace `code`.
+///
+pub fn register_as(ns: &'static dyn namespace::Static, code: u16) -> Result<(), CodeError> {
+    let mut codes = codes().write().unwrap();
+    match codes.by_namespace.get(&NamespaceId::of_static(ns)) {
+        Some(existing) if *existing == code => return Ok(()),
+        Some(existing) => return Err(CodeError::AlreadyRegistered(*existing)),
+        None => {}
+    }
+    if codes.namespaces.contains_key(&code) {
+        return Err(CodeError::CodeTaken(code));
+    }
+
+    codes.insert(ns, code);
+    Ok(())
+}
+
+///
+/// The namespace code of a registered static namespace.
+///
+pub fn namespace_code(ns: &dyn namespace::Static) -> Option<u16> {
+    codes()
+        .read()
+        .unwrap()
+        .by_namespace
+        .get(&NamespaceId::of_static(ns))
+        .copied()
+}
+
+impl Codes {
+    fn insert(&mut self, ns: &'static dyn namespace::Static, code: u16) {
+        self.by_namespace.insert(NamespaceId::of_static(ns), code);
+        self.namespaces.insert(code, ns);
+    }
+}
+
+impl Symbol {
+    ///
+    /// The compact code of this symbol: its namespace code in bits 32 to 47, and its `id` in the lower 32 bits.
+    ///
+    /// Only static symbols from [registered](register) namespaces, and dynamic symbols with a
+    /// [static_equivalent](namespace::Dynamic::static_equivalent) in one, have codes.
+    ///
+    pub fn to_code(&self) -> Option<u64> {
+        let (ns, id) = self.static_form()?;
+        let namespace_code = namespace_code(ns)?;
+
+        Some((namespace_code as u64) << 32 | id as u64)
+    }
+
+    ///
+    /// Decode a symbol from its [code](Self::to_code).
+    ///
+    pub fn from_code(code: u64) -> Option<Symbol> {
+        if code >> 48 != 0 {
+            return None;
+        }
+
+        let ns = *codes()
+            .read()
+            .unwrap()
+            .namespaces
+            .get(&((code >> 32) as u16))?;
+        let id = code as u32;
+
+        if id < ns.symbol_count() {
+            Some(Symbol::Static(ns, id))
+        } else {
+            None
+        }
+    }
+}
+
+///
+/// Error returned when a namespace code cannot be assigned.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodeError {
+    /// All 65536 namespace codes are taken.
+    Exhausted,
+    /// The code is already assigned to another namespace.
+    CodeTaken(u16),
+    /// The namespace is already registered with this other code.
+    AlreadyRegistered(u16),
+}
+
+impl std::fmt::Display for CodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Exhausted => write!(f, "all namespace codes are taken"),
+            Self::CodeTaken(code) => write!(f, "namespace code {} is already taken", code),
+            Self::AlreadyRegistered(code) => {
+                write!(f, "namespace is already registered with code {}", code)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::_static::{STATIC_NS_CLASS_A, STATIC_NS_CLASS_B};
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1, STATIC_B_0};
+
+    #[test]
+    fn test_codes() {
+        let code = register(&STATIC_NS_CLASS_A).unwrap();
+        assert_eq!(register(&STATIC_NS_CLASS_A), Ok(code));
+        assert_eq!(namespace_code(&STATIC_NS_CLASS_A), Some(code));
+
+        let a1 = STATIC_A_1.to_code().unwrap();
+        assert_eq!(a1, (code as u64) << 32 | 1);
+        assert_eq!(Symbol::from_code(a1), Some(STATIC_A_1));
+        assert_eq!(
+            Symbol::from_code(STATIC_A_0.to_code().unwrap()),
+            Some(STATIC_A_0)
+        );
+        assert_eq!(dynamic::dynamic_a("1").to_code(), Some(a1));
+
+        // Out of range:
+        assert_eq!(Symbol::from_code(a1 + 1), None);
+        assert_eq!(Symbol::from_code(1 << 48 | a1), None);
+
+        assert_eq!(dynamic::sym0("foo").to_code(), None);
+    }
+
+    #[test]
+    fn test_register_as() {
+        assert_eq!(register_as(&STATIC_NS_CLASS_B, 0xb00b), Ok(()));
+        assert_eq!(register_as(&STATIC_NS_CLASS_B, 0xb00b), Ok(()));
+        assert_eq!(
+            register_as(&STATIC_NS_CLASS_B, 0xb00c),
+            Err(CodeError::AlreadyRegistered(0xb00b))
+        );
+        assert_eq!(STATIC_B_0.to_code(), Some(0xb00b << 32));
+
+        struct Other;
+
+        impl namespace::Static for Other {
+            fn namespace_name(&self) -> &str {
+                "other"
+            }
+
+            fn symbol_count(&self) -> u32 {
+                0
+            }
+
+            fn symbol_name(&self, _: u32) -> &str {
+                unreachable!()
+            }
+        }
+
+        assert_eq!(
+            register_as(&Other, 0xb00b),
+            Err(CodeError::CodeTaken(0xb00b))
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use super::{find_slot, NamespaceSlot};
+use crate::{namespace, NamespaceId, Symbol};
+
+use std::collections::HashMap;
+
+///
+/// A map keyed by [Symbol].
+///
+/// Values for static symbols are stored in one dense vector per namespace, indexed by the symbol `id`.
+/// Only dynamic and inline symbols are hashed. This makes lookups of static symbols considerably cheaper than
+/// in a `HashMap<Symbol, V>`, but namespaces with sparse, large ids will waste memory.
+///
+/// ```
+/// use dyn_symbol::collections::SymbolMap;
+/// # use dyn_symbol::*;
+/// # struct MyStaticNS;
+/// # impl namespace::Static for MyStaticNS {
+/// #     fn namespace_name(&self) -> &str { "my" }
+/// #     fn symbol_count(&self) -> u32 { 2 }
+/// #     fn symbol_name(&self, id: u32) -> &str { ["foo", "bar"][id as usize] }
+/// # }
+/// # const FOO: Symbol = Symbol::Static(&MyStaticNS, 0);
+/// # const BAR: Symbol = Symbol::Static(&MyStaticNS, 1);
+///
+/// let mut map = SymbolMap::new();
+/// map.insert(FOO, "foo value");
+///
+/// assert_eq!(map.get(&FOO), Some(&"foo value"));
+/// assert_eq!(map.get(&BAR), None);
+/// ```
+///
+pub struct SymbolMap<V> {
+    statics: Vec<StaticSlots<V>>,
+    dynamics: HashMap<Symbol, V>,
+    static_len: usize,
+}
+
+struct StaticSlots<V> {
+    ns: &'static dyn namespace::Static,
+    ns_id: NamespaceId,
+    values: Vec<Option<V>>,
+}
+
+impl<V> NamespaceSlot for StaticSlots<V> {
+    fn namespace_id(&self) -> NamespaceId {
+        self.ns_id
+    }
+}
+
+impl<V> SymbolMap<V> {
+    ///
+    /// Create an empty map.
+    ///
+    pub fn new() -> Self {
+        Self {
+            statics: vec![],
+            dynamics: HashMap::new(),
+            static_len: 0,
+        }
+    }
+
+    ///
+    /// The number of entries in the map.
+    ///
+    pub fn len(&self) -> usize {
+        self.static_len + self.dynamics.len()
+    }
+
+    ///
+    /// Whether the map is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Insert a value, returning the previous value for the same key, if any.
+    ///
+    pub fn insert(&mut self, key: Symbol, value: V) -> Option<V> {
+        match key.static_form() {
+            Some((ns, id)) => {
+                let slots = self.static_slots_mut(ns);
+                let index = id as usize;
+                if index >= slots.values.len() {
+                    slots.values.resize_with(index + 1, || None);
+                }
+
+                let previous = slots.values[index].replace(value);
+                if previous.is_none() {
+                    self.static_len += 1;
+                }
+                previous
+            }
+            None => self.dynamics.insert(key, value),
+        }
+    }
+
+    ///
+    /// Get a reference to the value associated with `key`.
+    ///
+    pub fn get(&self, key: &Symbol) -> Option<&V> {
+        match key.static_form() {
+            Some((ns, id)) => self
+                .static_slots(ns)?
+                .values
+                .get(id as usize)
+                .and_then(Option::as_ref),
+            None => self.dynamics.get(key),
+        }
+    }
+
+    ///
+    /// Get a mutable reference to the value associated with `key`.
+    ///
+    pub fn get_mut(&mut self, key: &Symbol) -> Option<&mut V> {
+        match key.static_form() {
+            Some((ns, id)) => {
+                let index = find_slot(&self.statics, ns)?;
+                self.statics[index]
+                    .values
+                    .get_mut(id as usize)
+                    .and_then(Option::as_mut)
+            }
+            None => self.dynamics.get_mut(key),
+        }
+    }
+
+    ///
+    /// Whether the map contains a value for `key`.
+    ///
+    pub fn contains_key(&self, key: &Symbol) -> bool {
+        self.get(key).is_some()
+    }
+
+    ///
+    /// Remove and return the value associated with `key`.
+    ///
+    pub fn remove(&mut self, key: &Symbol) -> Option<V> {
+        match key.static_form() {
+            Some((ns, id)) => {
+                let index = find_slot(&self.statics, ns)?;
+                let removed = self.statics[index].values.get_mut(id as usize)?.take();
+                if removed.is_some() {
+                    self.static_len -= 1;
+                }
+                removed
+            }
+            None => self.dynamics.remove(key),
+        }
+    }
+
+    ///
+    /// Remove all entries.
+    ///
+    pub fn clear(&mut self) {
+        self.statics.clear();
+        self.dynamics.clear();
+        self.static_len = 0;
+    }
+
+    ///
+    /// Iterate over all entries. Static entries come first, grouped by namespace in `id` order,
+    /// followed by dynamic entries in arbitrary order.
+    ///
+    /// Keys are returned by value: static keys are reconstructed, dynamic keys are cloned.
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &V)> {
+        let statics = self.statics.iter().flat_map(|slots| {
+            let ns = slots.ns;
+            slots
+                .values
+                .iter()
+                .enumerate()
+                .filter_map(move |(id, value)| {
+                    value
+                        .as_ref()
+                        .map(|value| (Symbol::Static(ns, id as u32), value))
+                })
+        });
+        let dynamics = self
+            .dynamics
+            .iter()
+            .map(|(symbol, value)| (symbol.clone(), value));
+
+        statics.chain(dynamics)
+    }
+
+    ///
+    /// Iterate over all values, in the same order as [iter](Self::iter).
+    ///
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.statics
+            .iter()
+            .flat_map(|slots| slots.values.iter().filter_map(Option::as_ref))
+            .chain(self.dynamics.values())
+    }
+
+    fn static_slots(&self, ns: &'static dyn namespace::Static) -> Option<&StaticSlots<V>> {
+        find_slot(&self.statics, ns).map(|index| &self.statics[index])
+    }
+
+    fn static_slots_mut(&mut self, ns: &'static dyn namespace::Static) -> &mut StaticSlots<V> {
+        match find_slot(&self.statics, ns) {
+            Some(index) => &mut self.statics[index],
+            None => {
+                self.statics.push(StaticSlots {
+                    ns,
+                    ns_id: NamespaceId::of_static(ns),
+                    values: Vec::with_capacity(ns.symbol_count() as usize),
+                });
+                self.statics.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+impl<V> Default for SymbolMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone> Clone for SymbolMap<V> {
+    fn clone(&self) -> Self {
+        Self {
+            statics: self
+                .statics
+                .iter()
+                .map(|slots| StaticSlots {
+                    ns: slots.ns,
+                    ns_id: slots.ns_id,
+                    values: slots.values.clone(),
+                })
+                .collect(),
+            dynamics: self.dynamics.clone(),
+            static_len: self.static_len,
+        }
+    }
+}
+
+impl<V: std::fmt::Debug> std::fmt::Debug for SymbolMap<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V> Extend<(Symbol, V)> for SymbolMap<V> {
+    fn extend<I: IntoIterator<Item = (Symbol, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<V> std::iter::FromIterator<(Symbol, V)> for SymbolMap<V> {
+    fn from_iter<I: IntoIterator<Item = (Symbol, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1, STATIC_B_0};
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = SymbolMap::new();
+
+        assert_eq!(map.insert(STATIC_A_1, 1), None);
+        assert_eq!(map.insert(STATIC_B_0, 2), None);
+        assert_eq!(map.insert(dynamic::sym0("foo"), 3), None);
+        assert_eq!(map.insert(STATIC_A_1, 4), Some(1));
+        assert_eq!(map.len(), 3);
+
+        assert_eq!(map.get(&STATIC_A_0), None);
+        assert_eq!(map.get(&STATIC_A_1), Some(&4));
+        assert_eq!(map.get(&STATIC_B_0), Some(&2));
+        assert_eq!(map.get(&dynamic::sym0("foo")), Some(&3));
+        assert_eq!(map.get(&dynamic::sym1("foo")), None);
+
+        *map.get_mut(&STATIC_B_0).unwrap() += 10;
+        assert_eq!(map.get(&STATIC_B_0), Some(&12));
+
+        assert_eq!(map.remove(&STATIC_A_1), Some(4));
+        assert_eq!(map.remove(&STATIC_A_1), None);
+        assert_eq!(map.remove(&dynamic::sym0("foo")), Some(3));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_static_equivalent_keys() {
+        let mut map = SymbolMap::new();
+        map.insert(STATIC_A_1, 1);
+
+        assert_eq!(map.insert(dynamic::dynamic_a("1"), 2), Some(1));
+        assert_eq!(map.get(&STATIC_A_1), Some(&2));
+        assert_eq!(map.remove(&dynamic::dynamic_a("1")), Some(2));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let map: SymbolMap<u32> = vec![(dynamic::sym0("foo"), 0), (STATIC_A_1, 1), (STATIC_A_0, 2)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![
+                (STATIC_A_0, &2),
+                (STATIC_A_1, &1),
+                (dynamic::sym0("foo"), &0)
+            ]
+        );
+        assert_eq!(map.values().sum::<u32>(), 3);
+    }
+}
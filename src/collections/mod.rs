@@ -0,0 +1,34 @@
+//!
+//! Collections specialized for [Symbol](crate::Symbol) keys.
+//!
+//! Static symbols are just a namespace plus a small `u32` id, so they can be stored densely per namespace,
+//! without hashing. Dynamic symbols fall back to a regular hash-based collection.
+//! When all keys come from one known static namespace, [StaticVecMap] avoids even the namespace lookup.
+//!
+
+mod map;
+mod set;
+mod vec_map;
+
+pub use map::SymbolMap;
+pub use set::SymbolSet;
+pub use vec_map::StaticVecMap;
+
+use crate::{namespace, NamespaceId};
+
+///
+/// Per-namespace storage of static symbols in [SymbolMap] and [SymbolSet].
+///
+trait NamespaceSlot {
+    /// Cached when the slot is created, so that finding a slot takes no virtual calls per slot.
+    fn namespace_id(&self) -> NamespaceId;
+}
+
+///
+/// The position of the slot of `ns`. Namespaces are few, so slots are scanned linearly, comparing the cached
+/// [NamespaceId]s with the one of `ns`, which takes a single virtual call.
+///
+fn find_slot<S: NamespaceSlot>(slots: &[S], ns: &'static dyn namespace::Static) -> Option<usize> {
+    let ns_id = NamespaceId::of_static(ns);
+    slots.iter().position(|slot| slot.namespace_id() == ns_id)
+}
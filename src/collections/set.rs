@@ -0,0 +1,251 @@
+use super::{find_slot, NamespaceSlot};
+use crate::{namespace, NamespaceId, Symbol};
+
+use std::collections::HashSet;
+
+const BITS: usize = u64::BITS as usize;
+
+///
+/// A set of [Symbol]s.
+///
+/// Membership of static symbols is stored as one bit per symbol `id`, in a bitmap per namespace.
+/// Dynamic and inline symbols spill into a hash set, which does not allocate until the first dynamic symbol is inserted.
+///
+/// ```
+/// use dyn_symbol::collections::SymbolSet;
+/// # use dyn_symbol::*;
+/// # struct MyStaticNS;
+/// # impl namespace::Static for MyStaticNS {
+/// #     fn namespace_name(&self) -> &str { "my" }
+/// #     fn symbol_count(&self) -> u32 { 2 }
+/// #     fn symbol_name(&self, id: u32) -> &str { ["foo", "bar"][id as usize] }
+/// # }
+/// # const FOO: Symbol = Symbol::Static(&MyStaticNS, 0);
+/// # const BAR: Symbol = Symbol::Static(&MyStaticNS, 1);
+///
+/// let mut set = SymbolSet::new();
+/// assert!(set.insert(FOO));
+/// assert!(!set.insert(FOO));
+///
+/// assert!(set.contains(&FOO));
+/// assert!(!set.contains(&BAR));
+/// ```
+///
+#[derive(Clone, Default)]
+pub struct SymbolSet {
+    statics: Vec<StaticBits>,
+    dynamics: HashSet<Symbol>,
+    static_len: usize,
+}
+
+#[derive(Clone)]
+struct StaticBits {
+    ns: &'static dyn namespace::Static,
+    ns_id: NamespaceId,
+    words: Vec<u64>,
+}
+
+impl NamespaceSlot for StaticBits {
+    fn namespace_id(&self) -> NamespaceId {
+        self.ns_id
+    }
+}
+
+impl SymbolSet {
+    ///
+    /// Create an empty set.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// The number of symbols in the set.
+    ///
+    pub fn len(&self) -> usize {
+        self.static_len + self.dynamics.len()
+    }
+
+    ///
+    /// Whether the set is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Add a symbol to the set. Returns whether it was newly inserted.
+    ///
+    pub fn insert(&mut self, symbol: Symbol) -> bool {
+        match symbol.static_form() {
+            Some((ns, id)) => {
+                let bits = self.static_bits_mut(ns);
+                let (word, mask) = word_and_mask(id);
+                if word >= bits.words.len() {
+                    bits.words.resize(word + 1, 0);
+                }
+
+                let inserted = bits.words[word] & mask == 0;
+                bits.words[word] |= mask;
+                if inserted {
+                    self.static_len += 1;
+                }
+                inserted
+            }
+            None => self.dynamics.insert(symbol),
+        }
+    }
+
+    ///
+    /// Whether the set contains `symbol`.
+    ///
+    pub fn contains(&self, symbol: &Symbol) -> bool {
+        match symbol.static_form() {
+            Some((ns, id)) => {
+                let (word, mask) = word_and_mask(id);
+                find_slot(&self.statics, ns)
+                    .and_then(|index| self.statics[index].words.get(word))
+                    .map(|word| word & mask != 0)
+                    .unwrap_or(false)
+            }
+            None => self.dynamics.contains(symbol),
+        }
+    }
+
+    ///
+    /// Remove a symbol from the set. Returns whether it was present.
+    ///
+    pub fn remove(&mut self, symbol: &Symbol) -> bool {
+        match symbol.static_form() {
+            Some((ns, id)) => {
+                let (word, mask) = word_and_mask(id);
+                let word = match find_slot(&self.statics, ns)
+                    .and_then(|index| self.statics[index].words.get_mut(word))
+                {
+                    Some(word) => word,
+                    None => return false,
+                };
+
+                let removed = *word & mask != 0;
+                *word &= !mask;
+                if removed {
+                    self.static_len -= 1;
+                }
+                removed
+            }
+            None => self.dynamics.remove(symbol),
+        }
+    }
+
+    ///
+    /// Remove all symbols.
+    ///
+    pub fn clear(&mut self) {
+        self.statics.clear();
+        self.dynamics.clear();
+        self.static_len = 0;
+    }
+
+    ///
+    /// Iterate over the symbols in the set. Static symbols come first, grouped by namespace in `id` order,
+    /// followed by dynamic symbols in arbitrary order. Dynamic symbols are cloned.
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = Symbol> + '_ {
+        let statics = self.statics.iter().flat_map(|bits| {
+            let ns = bits.ns;
+            bits.words
+                .iter()
+                .enumerate()
+                .flat_map(move |(word_index, word)| {
+                    let word = *word;
+                    (0..BITS)
+                        .filter(move |bit| word & (1 << bit) != 0)
+                        .map(move |bit| Symbol::Static(ns, (word_index * BITS + bit) as u32))
+                })
+        });
+
+        statics.chain(self.dynamics.iter().cloned())
+    }
+
+    fn static_bits_mut(&mut self, ns: &'static dyn namespace::Static) -> &mut StaticBits {
+        match find_slot(&self.statics, ns) {
+            Some(index) => &mut self.statics[index],
+            None => {
+                self.statics.push(StaticBits {
+                    ns,
+                    ns_id: NamespaceId::of_static(ns),
+                    words: Vec::with_capacity((ns.symbol_count() as usize).div_ceil(BITS)),
+                });
+                self.statics.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+fn word_and_mask(id: u32) -> (usize, u64) {
+    let id = id as usize;
+    (id / BITS, 1 << (id % BITS))
+}
+
+impl std::fmt::Debug for SymbolSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<Symbol> for SymbolSet {
+    fn extend<I: IntoIterator<Item = Symbol>>(&mut self, iter: I) {
+        for symbol in iter {
+            self.insert(symbol);
+        }
+    }
+}
+
+impl std::iter::FromIterator<Symbol> for SymbolSet {
+    fn from_iter<I: IntoIterator<Item = Symbol>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1, STATIC_B_0};
+
+    #[test]
+    fn test_insert_contains_remove() {
+        let mut set = SymbolSet::new();
+
+        assert!(set.insert(STATIC_A_1));
+        assert!(set.insert(STATIC_B_0));
+        assert!(set.insert(dynamic::sym0("foo")));
+        assert!(!set.insert(STATIC_A_1));
+        assert_eq!(set.len(), 3);
+
+        assert!(!set.contains(&STATIC_A_0));
+        assert!(set.contains(&STATIC_A_1));
+        assert!(set.contains(&STATIC_B_0));
+        assert!(set.contains(&dynamic::sym0("foo")));
+        assert!(!set.contains(&dynamic::sym1("foo")));
+
+        assert!(set.remove(&STATIC_A_1));
+        assert!(!set.remove(&STATIC_A_1));
+        assert!(!set.remove(&STATIC_A_0));
+        assert!(set.remove(&dynamic::sym0("foo")));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_iter() {
+        let set: SymbolSet = vec![dynamic::sym0("foo"), STATIC_A_1, STATIC_A_0]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![STATIC_A_0, STATIC_A_1, dynamic::sym0("foo")]
+        );
+    }
+}
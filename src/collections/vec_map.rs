@@ -0,0 +1,232 @@
+use crate::{namespace, Symbol};
+
+///
+/// A map keyed by the symbols of one static namespace `NS`, indexed directly by symbol `id`.
+///
+/// Lookups are plain array indexing, without any hashing. Symbols from other namespaces are rejected.
+/// Like in a [SymbolMap](super::SymbolMap), dynamic symbols with a [static_equivalent](namespace::Dynamic::static_equivalent)
+/// in `NS`, and symbols of namespaces sharing the [namespace_uid](namespace::Static::namespace_uid) of `NS`, are keys of `NS`.
+///
+/// ```
+/// use dyn_symbol::collections::StaticVecMap;
+/// # use dyn_symbol::*;
+/// # struct MyStaticNS;
+/// # impl namespace::Static for MyStaticNS {
+/// #     fn namespace_name(&self) -> &str { "my" }
+/// #     fn symbol_count(&self) -> u32 { 2 }
+/// #     fn symbol_name(&self, id: u32) -> &str { ["foo", "bar"][id as usize] }
+/// # }
+/// # struct OtherNS;
+/// # impl namespace::Static for OtherNS {
+/// #     fn namespace_name(&self) -> &str { "other" }
+/// #     fn symbol_count(&self) -> u32 { 1 }
+/// #     fn symbol_name(&self, _: u32) -> &str { "foo" }
+/// # }
+/// # const FOO: Symbol = Symbol::Static(&MyStaticNS, 0);
+/// # const OTHER_FOO: Symbol = Symbol::Static(&OtherNS, 0);
+///
+/// let mut map = StaticVecMap::new(&MyStaticNS);
+///
+/// assert_eq!(map.insert(&FOO, "handler"), Ok(None));
+/// assert_eq!(map.insert(&OTHER_FOO, "rejected"), Err("rejected"));
+///
+/// assert_eq!(map.get(&FOO), Some(&"handler"));
+/// assert_eq!(map.get_by_id(0), Some(&"handler"));
+/// ```
+///
+pub struct StaticVecMap<NS: 'static, V> {
+    ns: &'static NS,
+    values: Vec<Option<V>>,
+}
+
+impl<NS: namespace::Static, V> StaticVecMap<NS, V> {
+    ///
+    /// Create an empty map for the namespace `ns`, with room for all of its symbols.
+    ///
+    pub fn new(ns: &'static NS) -> Self {
+        let mut values = Vec::new();
+        values.resize_with(ns.symbol_count() as usize, || None);
+        Self { ns, values }
+    }
+
+    ///
+    /// The namespace of this map.
+    ///
+    pub fn namespace(&self) -> &'static NS {
+        self.ns
+    }
+
+    ///
+    /// The number of entries in the map.
+    ///
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+
+    ///
+    /// Whether the map is empty.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+
+    ///
+    /// Insert a value for `key`, returning the previous value.
+    /// If `key` is not a symbol of `NS`, the value is given back as an error.
+    ///
+    pub fn insert(&mut self, key: &Symbol, value: V) -> Result<Option<V>, V> {
+        match key.static_id_in(self.ns) {
+            Some(id) => Ok(self.insert_by_id(id, value)),
+            None => Err(value),
+        }
+    }
+
+    ///
+    /// Insert a value for the symbol with the given `id`, returning the previous value.
+    ///
+    pub fn insert_by_id(&mut self, id: u32, value: V) -> Option<V> {
+        let index = id as usize;
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+        self.values[index].replace(value)
+    }
+
+    ///
+    /// Get the value for `key`. Returns `None` for symbols of other namespaces.
+    ///
+    pub fn get(&self, key: &Symbol) -> Option<&V> {
+        let id = key.static_id_in(self.ns)?;
+        self.get_by_id(id)
+    }
+
+    ///
+    /// Get the value for the symbol with the given `id`.
+    ///
+    pub fn get_by_id(&self, id: u32) -> Option<&V> {
+        self.values.get(id as usize).and_then(Option::as_ref)
+    }
+
+    ///
+    /// Get a mutable reference to the value for `key`. Returns `None` for symbols of other namespaces.
+    ///
+    pub fn get_mut(&mut self, key: &Symbol) -> Option<&mut V> {
+        let id = key.static_id_in(self.ns)?;
+        self.get_by_id_mut(id)
+    }
+
+    ///
+    /// Get a mutable reference to the value for the symbol with the given `id`.
+    ///
+    pub fn get_by_id_mut(&mut self, id: u32) -> Option<&mut V> {
+        self.values.get_mut(id as usize).and_then(Option::as_mut)
+    }
+
+    ///
+    /// Remove and return the value for `key`.
+    ///
+    pub fn remove(&mut self, key: &Symbol) -> Option<V> {
+        let id = key.static_id_in(self.ns)?;
+        self.remove_by_id(id)
+    }
+
+    ///
+    /// Remove and return the value for the symbol with the given `id`.
+    ///
+    pub fn remove_by_id(&mut self, id: u32) -> Option<V> {
+        self.values.get_mut(id as usize).and_then(Option::take)
+    }
+
+    ///
+    /// Iterate over all entries in `id` order.
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &V)> {
+        let ns = self.ns;
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(move |(id, value)| {
+                value
+                    .as_ref()
+                    .map(|value| (Symbol::Static(ns, id as u32), value))
+            })
+    }
+}
+
+impl<NS, V: Clone> Clone for StaticVecMap<NS, V> {
+    fn clone(&self) -> Self {
+        Self {
+            ns: self.ns,
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<NS: namespace::Static, V: std::fmt::Debug> std::fmt::Debug for StaticVecMap<NS, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{_static, dynamic, STATIC_A_0, STATIC_A_1, STATIC_B_0};
+
+    #[test]
+    fn test_checked_insert() {
+        let mut map = StaticVecMap::new(&_static::STATIC_NS_CLASS_A);
+
+        assert_eq!(map.insert(&STATIC_A_1, 1), Ok(None));
+        assert_eq!(map.insert(&STATIC_A_1, 2), Ok(Some(1)));
+        assert_eq!(map.insert(&STATIC_B_0, 3), Err(3));
+        assert_eq!(map.insert(&dynamic::sym0("foo"), 4), Err(4));
+
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&STATIC_A_0), None);
+        assert_eq!(map.get(&STATIC_A_1), Some(&2));
+        assert_eq!(map.get(&STATIC_B_0), None);
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(STATIC_A_1, &2)]);
+
+        assert_eq!(map.remove(&STATIC_A_1), Some(2));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_equivalent_keys() {
+        let mut map = StaticVecMap::new(&_static::STATIC_NS_CLASS_A);
+
+        assert_eq!(map.insert(&dynamic::dynamic_a("1"), 1), Ok(None));
+        assert_eq!(map.get(&STATIC_A_1), Some(&1));
+        assert_eq!(map.get_mut(&dynamic::dynamic_a("1")), Some(&mut 1));
+        assert_eq!(map.remove(&dynamic::dynamic_a("1")), Some(1));
+    }
+
+    #[test]
+    fn test_shared_uid() {
+        struct Uid<const N: u8>;
+
+        impl<const N: u8> namespace::Static for Uid<N> {
+            fn namespace_name(&self) -> &str {
+                "vec_map_uid"
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(0xec)
+            }
+
+            fn symbol_count(&self) -> u32 {
+                1
+            }
+
+            fn symbol_name(&self, _: u32) -> &str {
+                "foo"
+            }
+        }
+
+        let mut map = StaticVecMap::new(&Uid::<1>);
+
+        assert_eq!(map.insert(&Symbol::Static(&Uid::<2>, 0), 1), Ok(None));
+        assert_eq!(map.get(&Symbol::Static(&Uid::<1>, 0)), Some(&1));
+    }
+}
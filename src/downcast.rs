@@ -0,0 +1,156 @@
+//!
+//! Downcasts that explain why they failed.
+//!
+//! [Symbol::try_downcast_static] and [Symbol::try_downcast_dyn] are like [Symbol::downcast_static] and
+//! [Symbol::downcast_dyn], but fail with a [DowncastError] telling which namespace was expected and which symbol was found:
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! struct Html;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         1
+//!     }
+//!
+//!     fn symbol_name(&self, _: u32) -> &str {
+//!         "class"
+//!     }
+//! }
+//!
+//! struct Svg;
+//!
+//! let class = Symbol::Static(&Html, 0);
+//! assert!(class.try_downcast_static::<Html>().is_ok());
+//!
+//! let error = class.try_downcast_static::<Svg>().err().unwrap();
+//! assert_eq!(error.namespace(), "html");
+//! assert!(error.expected_type_name().ends_with("Svg"));
+//! assert!(error.to_string().ends_with("got `html::class`"));
+//! ```
+//!
+
+use crate::Symbol;
+
+use alloc::string::String;
+
+///
+/// Error returned when a symbol cannot be downcast to a namespace type.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DowncastError {
+    expected: &'static str,
+    expected_static: bool,
+    namespace: String,
+    name: String,
+}
+
+impl DowncastError {
+    fn new<T: 'static>(expected_static: bool, symbol: &Symbol) -> Self {
+        Self {
+            expected: core::any::type_name::<T>(),
+            expected_static,
+            namespace: symbol.namespace().into(),
+            name: symbol.name().into(),
+        }
+    }
+
+    ///
+    /// The type name of the expected namespace, as given by [type_name](core::any::type_name).
+    ///
+    pub fn expected_type_name(&self) -> &'static str {
+        self.expected
+    }
+
+    ///
+    /// The namespace name of the symbol that could not be downcast.
+    ///
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    ///
+    /// The name of the symbol that could not be downcast.
+    ///
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl core::fmt::Display for DowncastError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "expected a symbol in the {} namespace `{}`, got `{}::{}`",
+            if self.expected_static {
+                "static"
+            } else {
+                "dynamic"
+            },
+            self.expected,
+            self.namespace,
+            self.name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DowncastError {}
+
+impl Symbol {
+    ///
+    /// Like [Symbol::downcast_static], but returns a [DowncastError] on failure.
+    ///
+    pub fn try_downcast_static<T: 'static>(&self) -> Result<(&T, u32), DowncastError> {
+        self.downcast_static::<T>()
+            .ok_or_else(|| DowncastError::new::<T>(true, self))
+    }
+
+    ///
+    /// Like [Symbol::downcast_dyn], but returns a [DowncastError] on failure.
+    ///
+    pub fn try_downcast_dyn<T: 'static>(&self) -> Result<&T, DowncastError> {
+        self.downcast_dyn::<T>()
+            .ok_or_else(|| DowncastError::new::<T>(false, self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::_static::ClassN;
+    use crate::tests::dynamic::TestDynamic;
+    use crate::tests::{dynamic, STATIC_A_1};
+
+    #[test]
+    fn test_try_downcast() {
+        assert_eq!(STATIC_A_1.try_downcast_static::<ClassN<1>>().unwrap().1, 1);
+
+        let error = STATIC_A_1.try_downcast_static::<ClassN<2>>().err().unwrap();
+        assert_eq!(
+            error.expected_type_name(),
+            core::any::type_name::<ClassN<2>>()
+        );
+        assert_eq!((error.namespace(), error.name()), ("A", "1"));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "expected a symbol in the static namespace `{}`, got `A::1`",
+                core::any::type_name::<ClassN<2>>()
+            )
+        );
+
+        let foo = dynamic::sym0("foo");
+        assert_eq!(foo.try_downcast_dyn::<TestDynamic<0>>().unwrap().0, "foo");
+        assert!(foo.try_downcast_static::<ClassN<1>>().is_err());
+
+        let error: Box<dyn std::error::Error> =
+            Box::new(foo.try_downcast_dyn::<TestDynamic<1>>().err().unwrap());
+        assert!(error.to_string().ends_with("got `dyn0::foo`"));
+        assert!(STATIC_A_1.try_downcast_dyn::<TestDynamic<0>>().is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//!
+//! C FFI for holding and round-tripping symbols through C code.
+//!
+//! C code sees symbols as pointers to an opaque [SymbolHandle]. Rust code creates handles with
+//! [SymbolHandle::into_raw] and takes them back with [SymbolHandle::from_raw]. Every handle must eventually
+//! be released with [dyn_symbol_drop], either by C or by Rust.
+//!
+//! ```
+//! use dyn_symbol::ffi::*;
+//! use dyn_symbol::*;
+//!
+//! # struct MyStaticNS;
+//! # impl namespace::Static for MyStaticNS {
+//! #     fn namespace_name(&self) -> &str { "my" }
+//! #     fn symbol_count(&self) -> u32 { 1 }
+//! #     fn symbol_name(&self, _: u32) -> &str { "foo" }
+//! # }
+//! let handle = SymbolHandle::into_raw(Symbol::Static(&MyStaticNS, 0));
+//!
+//! unsafe {
+//!     let clone = dyn_symbol_clone(handle);
+//!     assert!(dyn_symbol_eq(handle, clone));
+//!     dyn_symbol_drop(clone);
+//!
+//!     assert_eq!(SymbolHandle::from_raw(handle), Symbol::Static(&MyStaticNS, 0));
+//! }
+//! ```
+//!
+//! The C declarations are:
+//!
+//! ```c
+//! typedef struct SymbolHandle SymbolHandle;
+//!
+//! SymbolHandle *dyn_symbol_clone(const SymbolHandle *symbol);
+//! void dyn_symbol_drop(SymbolHandle *symbol);
+//! bool dyn_symbol_eq(const SymbolHandle *a, const SymbolHandle *b);
+//! int32_t dyn_symbol_cmp(const SymbolHandle *a, const SymbolHandle *b);
+//! uint64_t dyn_symbol_hash(const SymbolHandle *symbol);
+//! const uint8_t *dyn_symbol_name(const SymbolHandle *symbol, size_t *len);
+//! const uint8_t *dyn_symbol_namespace(const SymbolHandle *symbol, size_t *len);
+//! ```
+//!
+
+use crate::Symbol;
+
+use std::hash::{Hash, Hasher};
+
+///
+/// An opaque symbol handle. Only ever used behind a pointer.
+///
+#[repr(C)]
+pub struct SymbolHandle {
+    _private: [u8; 0],
+}
+
+impl SymbolHandle {
+    ///
+    /// Move a symbol into a new handle, to be passed to C.
+    ///
+    pub fn into_raw(symbol: Symbol) -> *mut SymbolHandle {
+        Box::into_raw(Box::new(symbol)) as *mut SymbolHandle
+    }
+
+    ///
+    /// Take back the symbol of a handle, releasing the handle.
+    ///
+    /// # Safety
+    /// `handle` must have been created by [SymbolHandle::into_raw] or [dyn_symbol_clone], and not yet been released.
+    ///
+    pub unsafe fn from_raw(handle: *mut SymbolHandle) -> Symbol {
+        *Box::from_raw(handle as *mut Symbol)
+    }
+
+    ///
+    /// Borrow the symbol of a handle.
+    ///
+    /// # Safety
+    /// `handle` must be a live handle, which outlives the returned reference.
+    ///
+    pub unsafe fn as_symbol<'a>(handle: *const SymbolHandle) -> &'a Symbol {
+        &*(handle as *const Symbol)
+    }
+}
+
+///
+/// Create a new handle to the same symbol.
+///
+/// # Safety
+/// `symbol` must be a live handle.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_clone(symbol: *const SymbolHandle) -> *mut SymbolHandle {
+    SymbolHandle::into_raw(SymbolHandle::as_symbol(symbol).clone())
+}
+
+///
+/// Release a handle. Null pointers are ignored.
+///
+/// # Safety
+/// `symbol` must be null or a live handle, which must not be used afterwards.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_drop(symbol: *mut SymbolHandle) {
+    if !symbol.is_null() {
+        drop(SymbolHandle::from_raw(symbol));
+    }
+}
+
+///
+/// Whether two symbols are equal.
+///
+/// # Safety
+/// `a` and `b` must be live handles.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_eq(a: *const SymbolHandle, b: *const SymbolHandle) -> bool {
+    SymbolHandle::as_symbol(a) == SymbolHandle::as_symbol(b)
+}
+
+///
+/// Compare two symbols, returning a negative number, zero or a positive number when `a` is less than,
+/// equal to or greater than `b`.
+///
+/// # Safety
+/// `a` and `b` must be live handles.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_cmp(a: *const SymbolHandle, b: *const SymbolHandle) -> i32 {
+    SymbolHandle::as_symbol(a).cmp(SymbolHandle::as_symbol(b)) as i32
+}
+
+///
+/// Hash a symbol. The hash is consistent with [dyn_symbol_eq] within one process.
+///
+/// # Safety
+/// `symbol` must be a live handle.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_hash(symbol: *const SymbolHandle) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    SymbolHandle::as_symbol(symbol).hash(&mut hasher);
+    hasher.finish()
+}
+
+///
+/// The symbol's name as UTF-8 bytes, which are not NUL-terminated. The length is written to `len`.
+///
+/// # Safety
+/// `symbol` must be a live handle, and `len` must be valid for writes.
+/// The returned pointer is valid as long as the handle.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_name(
+    symbol: *const SymbolHandle,
+    len: *mut usize,
+) -> *const u8 {
+    let name = SymbolHandle::as_symbol(symbol).name();
+    *len = name.len();
+    name.as_ptr()
+}
+
+///
+/// The name of the symbol's namespace as UTF-8 bytes, which are not NUL-terminated. The length is written to `len`.
+///
+/// # Safety
+/// `symbol` must be a live handle, and `len` must be valid for writes.
+/// The returned pointer is valid as long as the handle.
+///
+#[no_mangle]
+pub unsafe extern "C" fn dyn_symbol_namespace(
+    symbol: *const SymbolHandle,
+    len: *mut usize,
+) -> *const u8 {
+    let namespace = SymbolHandle::as_symbol(symbol).namespace();
+    *len = namespace.len();
+    namespace.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1};
+
+    fn str_from_raw<'a>(ptr: *const u8, len: usize) -> &'a str {
+        unsafe { std::str::from_utf8(std::slice::from_raw_parts(ptr, len)).unwrap() }
+    }
+
+    #[test]
+    fn test_handles() {
+        let a0 = SymbolHandle::into_raw(STATIC_A_0);
+        let a1 = SymbolHandle::into_raw(STATIC_A_1);
+        let foo = SymbolHandle::into_raw(dynamic::sym0("foo"));
+
+        unsafe {
+            let foo_clone = dyn_symbol_clone(foo);
+
+            assert!(dyn_symbol_eq(foo, foo_clone));
+            assert!(!dyn_symbol_eq(a0, a1));
+            assert_eq!(dyn_symbol_cmp(a0, a1), -1);
+            assert_eq!(dyn_symbol_cmp(a1, a0), 1);
+            assert_eq!(dyn_symbol_cmp(foo, foo_clone), 0);
+            assert_eq!(dyn_symbol_hash(foo), dyn_symbol_hash(foo_clone));
+
+            let mut len = 0;
+            assert_eq!(
+                str_from_raw(dyn_symbol_name(foo_clone, &mut len), len),
+                "foo"
+            );
+            assert_eq!(str_from_raw(dyn_symbol_namespace(a1, &mut len), len), "A");
+
+            dyn_symbol_drop(foo_clone);
+            dyn_symbol_drop(std::ptr::null_mut());
+
+            assert_eq!(SymbolHandle::from_raw(foo), dynamic::sym0("foo"));
+            dyn_symbol_drop(a0);
+            dyn_symbol_drop(a1);
+        }
+    }
+}
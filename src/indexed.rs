@@ -0,0 +1,179 @@
+//!
+//! Parameterized static symbols, like `aria-level[3]` or `column[17]`, without memory allocation.
+//!
+//! An [IndexedBase] is a static symbol that can be combined with a runtime index, creating
+//! [Symbol::StaticIndexed] symbols. The index participates in equality, ordering, hashing and formatting.
+//! Indexed symbols are ordered right after their base symbol, by index.
+//!
+//! ```
+//! use dyn_symbol::indexed::IndexedBase;
+//! use dyn_symbol::*;
+//!
+//! struct Table;
+//!
+//! impl namespace::Static for Table {
+//!     fn namespace_name(&self) -> &str {
+//!         "table"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["row", "column"][id as usize]
+//!     }
+//! }
+//!
+//! static COLUMN: IndexedBase = IndexedBase::new(&Table, 1);
+//!
+//! let column = COLUMN.index(17);
+//!
+//! assert_eq!(column, COLUMN.index(17));
+//! assert_ne!(column, COLUMN.index(18));
+//! assert_ne!(column, Symbol::Static(&Table, 1));
+//! assert_eq!(column.name(), "column");
+//! assert_eq!(column.index(), Some(17));
+//! assert_eq!(format!("{:?}", column), "table::column[17]");
+//! ```
+//!
+//! The [Symbol] stays at its usual size, because the base symbol lives in a `static` and the
+//! symbol only needs to point to it. Indexed symbols are compared by namespace, base `id` and index,
+//! so two `IndexedBase`s for the same base symbol produce equal symbols.
+//!
+//! Serializing an indexed symbol appends `[index]` to the base symbol's
+//! serialized representation (`serialize_symbol`), which the namespace's
+//! deserializer has to recognize.
+//!
+
+use crate::{namespace, Symbol};
+
+///
+/// A static symbol that is the base of [indexed](Symbol::StaticIndexed) symbols.
+///
+pub struct IndexedBase {
+    ns: &'static dyn namespace::Static,
+    id: u32,
+}
+
+impl IndexedBase {
+    ///
+    /// The base symbol with the given `id` in `ns`.
+    ///
+    pub const fn new(ns: &'static dyn namespace::Static, id: u32) -> Self {
+        Self { ns, id }
+    }
+
+    ///
+    /// The base symbol's namespace.
+    ///
+    pub fn namespace(&self) -> &'static dyn namespace::Static {
+        self.ns
+    }
+
+    ///
+    /// The base symbol's `id` within its namespace.
+    ///
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    ///
+    /// The base symbol itself, without an index.
+    ///
+    pub fn base(&self) -> Symbol {
+        Symbol::Static(self.ns, self.id)
+    }
+
+    ///
+    /// Create the symbol with the given `index`.
+    ///
+    pub fn index(&'static self, index: u32) -> Symbol {
+        Symbol::StaticIndexed(self, index)
+    }
+}
+
+impl core::fmt::Debug for IndexedBase {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.base(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::_static::STATIC_NS_CLASS_A;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1};
+    use std::collections::BTreeSet;
+
+    static A_0: IndexedBase = IndexedBase::new(&STATIC_NS_CLASS_A, 0);
+    static A_0_AGAIN: IndexedBase = IndexedBase::new(&STATIC_NS_CLASS_A, 0);
+
+    #[test]
+    fn test_indexed_symbols() {
+        let random_state = std::collections::hash_map::RandomState::new();
+        let hash = |symbol: &Symbol| std::hash::BuildHasher::hash_one(&random_state, symbol);
+
+        assert_eq!(A_0.index(3), A_0_AGAIN.index(3));
+        assert_eq!(hash(&A_0.index(3)), hash(&A_0_AGAIN.index(3)));
+        assert_ne!(A_0.index(3), A_0.index(4));
+        assert_ne!(A_0.index(0), STATIC_A_0);
+        assert_eq!(A_0.base(), STATIC_A_0);
+        assert_eq!(A_0.index(3).namespace(), "A");
+        assert_eq!(STATIC_A_0.index(), None);
+        assert_eq!(A_0.index(3).to_string(), "A::0[3]");
+        assert_eq!(
+            std::mem::size_of::<Symbol>(),
+            std::mem::size_of::<Option<Symbol>>()
+        );
+    }
+
+    #[test]
+    fn test_indexed_order() {
+        let set: BTreeSet<Symbol> = vec![
+            dynamic::sym0("foo"),
+            STATIC_A_1,
+            A_0.index(2),
+            STATIC_A_0,
+            A_0.index(1),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            [
+                STATIC_A_0,
+                A_0.index(1),
+                A_0.index(2),
+                STATIC_A_1,
+                dynamic::sym0("foo")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_indexed_compare_with_str() {
+        assert!(A_0.index(3) == "A::0[3]");
+        assert!(A_0.index(3) != "A::0");
+        assert!(A_0.index(3) != "A::0[4]");
+        assert!(A_0.index(3) != "A::0[03]");
+        assert!(A_0.index(3) != "A::0[+3]");
+        assert!(A_0.index(0) == "A::0[0]");
+        assert!(STATIC_A_0 != "A::0[0]");
+        assert!(STATIC_A_0 == "A::0");
+    }
+
+    #[test]
+    fn test_indexed_stable_hash() {
+        fn stable_hash(symbol: &Symbol) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            symbol.stable_hash(&mut hasher);
+            std::hash::Hasher::finish(&hasher)
+        }
+
+        assert_eq!(stable_hash(&A_0.index(3)), stable_hash(&A_0_AGAIN.index(3)));
+        assert_ne!(stable_hash(&A_0.index(3)), stable_hash(&A_0.index(4)));
+        assert_ne!(stable_hash(&A_0.index(0)), stable_hash(&STATIC_A_0));
+    }
+}
@@ -0,0 +1,185 @@
+//!
+//! Structured introspection of symbols, for debugging tools and error messages.
+//!
+//! [Symbol::describe] returns a [SymbolInfo], so tools don't need to parse `Debug` output:
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! struct Html;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         1
+//!     }
+//!
+//!     fn symbol_name(&self, _: u32) -> &str {
+//!         "class"
+//!     }
+//!
+//!     fn describe_metadata(&self, _: u32, entry: &mut dyn FnMut(&str, &dyn std::fmt::Display)) {
+//!         entry("scope", &"global");
+//!     }
+//! }
+//!
+//! let info = Symbol::Static(&Html, 0).describe();
+//!
+//! assert_eq!(info.namespace, "html");
+//! assert_eq!(info.name, "class");
+//! assert!(info.is_static);
+//! assert_eq!(info.id, Some(0));
+//! assert_eq!(info.metadata_value("scope"), Some("global"));
+//! ```
+//!
+
+use crate::Symbol;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+///
+/// A structured description of a [Symbol], returned by [Symbol::describe].
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolInfo<'a> {
+    /// The name of the symbol's namespace.
+    pub namespace: &'a str,
+    /// The name of the symbol.
+    pub name: &'a str,
+    /// Whether the symbol is a static symbol, indexed, wide or not.
+    pub is_static: bool,
+    /// The `id` of a static symbol. Wide ids don't fit, so it's `None` for [wide](crate::wide) symbols.
+    pub id: Option<u32>,
+    /// The index of an [indexed](crate::indexed) static symbol.
+    pub index: Option<u32>,
+    /// Key/value pairs supplied by the namespace's `describe_metadata`, in the order they were supplied.
+    pub metadata: Vec<(String, String)>,
+}
+
+impl SymbolInfo<'_> {
+    ///
+    /// The first metadata value with the given `key`.
+    ///
+    pub fn metadata_value(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Symbol {
+    ///
+    /// Describe this symbol as a [SymbolInfo].
+    ///
+    pub fn describe(&self) -> SymbolInfo<'_> {
+        let mut metadata = Vec::new();
+        let mut entry = |key: &str, value: &dyn core::fmt::Display| {
+            metadata.push((key.into(), value.to_string()))
+        };
+
+        let (is_static, id) = match self {
+            Self::Static(ns, id) => {
+                ns.describe_metadata(*id, &mut entry);
+                (true, Some(*id))
+            }
+            Self::StaticIndexed(base, _) => {
+                base.namespace().describe_metadata(base.id(), &mut entry);
+                (true, Some(base.id()))
+            }
+            Self::Dynamic(instance) => {
+                instance.describe_metadata(&mut entry);
+                (false, None)
+            }
+            // Wide ids don't fit in `id`.
+            Self::StaticWide(ns, id) => {
+                ns.namespace().describe_metadata(*id, &mut entry);
+                (true, None)
+            }
+            Self::Inline(_, _) => (false, None),
+        };
+
+        SymbolInfo {
+            namespace: self.namespace(),
+            name: self.name(),
+            is_static,
+            id,
+            index: self.index(),
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed::IndexedBase;
+    use crate::inline::InlineNamespace;
+    use crate::tests::_static::STATIC_NS_CLASS_A;
+    use crate::tests::{dynamic, STATIC_A_1};
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            STATIC_A_1.describe(),
+            SymbolInfo {
+                namespace: "A",
+                name: "1",
+                is_static: true,
+                id: Some(1),
+                index: None,
+                metadata: vec![],
+            }
+        );
+
+        static A_0: IndexedBase = IndexedBase::new(&STATIC_NS_CLASS_A, 0);
+        let indexed = A_0.index(7);
+        let info = indexed.describe();
+        assert_eq!((info.id, info.index), (Some(0), Some(7)));
+
+        let foo = dynamic::sym0("foo");
+        let info = foo.describe();
+        assert_eq!((info.namespace, info.name), ("dyn0", "foo"));
+        assert!(!info.is_static);
+        assert_eq!(info.id, None);
+
+        static INLINE: InlineNamespace = InlineNamespace::new("inline");
+        let bar = INLINE.symbol("bar");
+        assert_eq!(bar.describe().namespace, "inline");
+        assert!(!bar.describe().is_static);
+    }
+
+    #[test]
+    fn test_describe_metadata() {
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Tagged(String);
+
+        impl crate::namespace::DynValue for Tagged {
+            fn namespace_name(&self) -> &str {
+                "tagged"
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.0
+            }
+
+            fn describe_metadata(&self, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+                entry("len", &self.0.len());
+                entry("source", &"test");
+            }
+        }
+
+        let symbol = Symbol::from(crate::namespace::DynWrap(Tagged("foo".into())));
+        let info = symbol.describe();
+        assert_eq!(
+            info.metadata,
+            [("len".into(), "3".into()), ("source".into(), "test".into())]
+        );
+        assert_eq!(info.metadata_value("source"), Some("test"));
+        assert_eq!(info.metadata_value("missing"), None);
+    }
+}
@@ -0,0 +1,219 @@
+//!
+//! String-based dynamic symbols that store short names inline, without memory allocation.
+//!
+//! An [InlineNamespace] creates [Symbol::Inline] symbols for names of up to [INLINE_CAPACITY] bytes,
+//! which fit inside the [Symbol] itself. Longer names fall back to a reference counted [Symbol::Dynamic].
+//! The choice of representation only depends on the length of the name, so symbols created from
+//! equal names always have equal representations.
+//!
+//! ```
+//! use dyn_symbol::inline::InlineNamespace;
+//! use dyn_symbol::Symbol;
+//!
+//! static DATA: InlineNamespace = InlineNamespace::new("data");
+//!
+//! let short = DATA.symbol("data-id");
+//! let long = DATA.symbol("data-some-very-long-attribute");
+//!
+//! assert!(matches!(short, Symbol::Inline(..)));
+//! assert!(matches!(long, Symbol::Dynamic(_)));
+//!
+//! assert_eq!(short, DATA.symbol("data-id"));
+//! assert_eq!(format!("{:?}", short), "data::data-id");
+//! assert_eq!(long.name(), "data-some-very-long-attribute");
+//! ```
+//!
+
+use crate::{namespace, Symbol};
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+///
+/// The maximum length in bytes of names stored inline in a [Symbol].
+///
+pub const INLINE_CAPACITY: usize = 14;
+
+///
+/// A namespace of string symbols that are stored inline when short enough.
+///
+/// Namespace identity is determined by the namespace name: two `InlineNamespace`s
+/// with the same name produce equal symbols for equal names.
+///
+#[derive(Debug)]
+pub struct InlineNamespace {
+    name: &'static str,
+}
+
+impl InlineNamespace {
+    ///
+    /// Create a namespace with the given name.
+    ///
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    ///
+    /// The namespace's name.
+    ///
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    ///
+    /// Create a symbol in this namespace. Names of up to [INLINE_CAPACITY] bytes don't allocate.
+    ///
+    pub fn symbol(&'static self, name: &str) -> Symbol {
+        match InlineName::new(name) {
+            Some(inline_name) => Symbol::Inline(self, inline_name),
+            None => Symbol::Dynamic(Arc::new(LongName {
+                ns: self,
+                name: name.into(),
+            })),
+        }
+    }
+
+    pub(crate) fn same_namespace(&self, other: &InlineNamespace) -> bool {
+        core::ptr::eq(self, other) || self.name == other.name
+    }
+}
+
+///
+/// A name of up to [INLINE_CAPACITY] bytes, stored inline.
+///
+/// Values can only be created through [InlineNamespace::symbol], and always contain valid UTF-8.
+///
+#[derive(Clone, Copy)]
+pub struct InlineName {
+    len: u8,
+    bytes: [u8; INLINE_CAPACITY],
+}
+
+impl InlineName {
+    fn new(name: &str) -> Option<Self> {
+        if name.len() > INLINE_CAPACITY {
+            return None;
+        }
+
+        let mut bytes = [0; INLINE_CAPACITY];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+
+        Some(Self {
+            len: name.len() as u8,
+            bytes,
+        })
+    }
+
+    ///
+    /// The name as a string slice.
+    ///
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the bytes were copied from a `&str` in `new`, and are never mutated.
+        unsafe { core::str::from_utf8_unchecked(&self.bytes[..self.len as usize]) }
+    }
+}
+
+///
+/// Whether `instance` is an inline namespace symbol whose name was too long to be stored inline.
+///
+pub(crate) fn is_long_name(instance: &dyn namespace::Dynamic) -> bool {
+    instance.as_any().is::<LongName>()
+}
+
+///
+/// The namespace of a long name symbol, see [is_long_name].
+///
+pub(crate) fn long_name_namespace(
+    instance: &dyn namespace::Dynamic,
+) -> Option<&'static InlineNamespace> {
+    instance
+        .as_any()
+        .downcast_ref::<LongName>()
+        .map(|long_name| long_name.ns)
+}
+
+///
+/// Dynamic fallback for names that are too long to be stored inline.
+///
+struct LongName {
+    ns: &'static InlineNamespace,
+    name: Box<str>,
+}
+
+impl namespace::Dynamic for LongName {
+    fn namespace_name(&self) -> &str {
+        self.ns.name
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.name
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialize_symbol(&self) -> Option<alloc::borrow::Cow<'_, str>> {
+        Some(self.name.as_ref().into())
+    }
+
+    #[cfg(feature = "rkyv")]
+    fn archive_bytes(&self) -> Option<alloc::borrow::Cow<'_, [u8]>> {
+        Some(self.symbol_name().as_bytes().into())
+    }
+
+    fn hash_by_name(&self) -> bool {
+        true
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(LongName {
+            ns: self.ns,
+            name: self.name.clone(),
+        })
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        self.ns.same_namespace(rhs.ns) && self.name == rhs.name
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> core::cmp::Ordering {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        (self.ns.name, &self.name).cmp(&(rhs.ns.name, &rhs.name))
+    }
+
+    fn dyn_hash(&self, state: &mut dyn core::hash::Hasher) {
+        state.write(self.ns.name.as_bytes());
+        state.write_u8(0xff);
+        state.write(self.name.as_bytes());
+        state.write_u8(0xff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static NS_A: InlineNamespace = InlineNamespace::new("a");
+    static NS_A_AGAIN: InlineNamespace = InlineNamespace::new("a");
+    static NS_B: InlineNamespace = InlineNamespace::new("b");
+
+    #[test]
+    fn test_inline_capacity() {
+        assert!(matches!(NS_A.symbol(""), Symbol::Inline(..)));
+        assert!(matches!(NS_A.symbol("14 bytes long!"), Symbol::Inline(..)));
+        assert!(matches!(NS_A.symbol("15 bytes long!!"), Symbol::Dynamic(_)));
+        assert!(matches!(NS_A.symbol("ææææææææ"), Symbol::Dynamic(_)));
+        assert_eq!(NS_A.symbol("æææææææ").name(), "æææææææ");
+    }
+
+    #[test]
+    fn test_namespace_identity() {
+        assert_eq!(NS_A.symbol("foo"), NS_A_AGAIN.symbol("foo"));
+        assert_eq!(
+            NS_A.symbol("a rather long symbol name"),
+            NS_A_AGAIN.symbol("a rather long symbol name")
+        );
+        assert_ne!(NS_A.symbol("foo"), NS_B.symbol("foo"));
+        assert_ne!(NS_A.symbol("foo"), NS_A.symbol("bar"));
+        assert_eq!(NS_B.symbol("foo").namespace(), "b");
+    }
+}
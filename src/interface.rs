@@ -0,0 +1,301 @@
+//!
+//! Discovering trait object interfaces of symbols.
+//!
+//! Downcasting a symbol requires knowing its namespace type. Instead, a namespace can provide trait objects for the
+//! interfaces it implements through `query_interface`, and [Symbol::as_trait] asks for one by its `dyn Trait` type,
+//! so the interface can be used without depending on the crate that defines the namespace:
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! trait SchemaProvider {
+//!     fn schema(&self, id: u32) -> &str;
+//! }
+//!
+//! struct Html;
+//!
+//! impl SchemaProvider for Html {
+//!     fn schema(&self, _: u32) -> &str {
+//!         "string"
+//!     }
+//! }
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         1
+//!     }
+//!
+//!     fn symbol_name(&self, _: u32) -> &str {
+//!         "class"
+//!     }
+//!
+//!     dyn_symbol::query_interfaces!(static dyn SchemaProvider);
+//! }
+//!
+//! let class = Symbol::Static(&Html, 0);
+//! let provider = class.as_trait::<dyn SchemaProvider>().unwrap();
+//!
+//! assert_eq!(provider.schema(0), "string");
+//! assert!(class.as_trait::<dyn std::fmt::Debug>().is_none());
+//! ```
+//!
+//! Namespaces can also implement `query_interface` by hand, e.g. to provide interfaces for only some symbols,
+//! or interfaces implemented by a field instead of the namespace itself.
+//!
+
+use crate::Symbol;
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+///
+/// A request for a trait object interface, passed to `query_interface`.
+///
+pub struct Query<'a> {
+    interface: TypeId,
+    /// Points to an `Option<&'a I>`, where `I` is the type identified by `interface`.
+    answer: *mut (),
+    /// `'a` must be invariant, so that a shorter lived reference can't be provided.
+    lifetime: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+impl<'a> Query<'a> {
+    ///
+    /// The [TypeId] of the requested interface, e.g. of `dyn Trait`.
+    ///
+    pub fn interface(&self) -> TypeId {
+        self.interface
+    }
+
+    ///
+    /// Provide `implementation` as the interface `I`, if `I` is the requested interface.
+    ///
+    pub fn provide<I: ?Sized + 'static>(&mut self, implementation: &'a I) -> &mut Self {
+        if self.interface == TypeId::of::<I>() {
+            // Safety: `answer` points to an `Option<&'a I>` of the same `I`, as the type ids are equal.
+            unsafe { *(self.answer as *mut Option<&'a I>) = Some(implementation) }
+        }
+        self
+    }
+}
+
+///
+/// Ask `query_interface` for the interface `I`.
+///
+fn query<'a, I: ?Sized + 'static>(query_interface: impl FnOnce(&mut Query<'a>)) -> Option<&'a I> {
+    let mut answer: Option<&'a I> = None;
+    query_interface(&mut Query {
+        interface: TypeId::of::<I>(),
+        answer: &mut answer as *mut Option<&'a I> as *mut (),
+        lifetime: PhantomData,
+    });
+    answer
+}
+
+impl Symbol {
+    ///
+    /// The interface `I`, usually a `dyn Trait`, if the symbol's namespace provides it.
+    ///
+    pub fn as_trait<I: ?Sized + 'static>(&self) -> Option<&I> {
+        match self {
+            Self::Static(ns, id) => query(|q| ns.query_interface(*id, q)),
+            Self::Dynamic(instance) => query(|q| instance.query_interface(q)),
+            Self::Inline(_, _) => None,
+            Self::StaticWide(ns, id) => query(|q| ns.namespace().query_interface(*id, q)),
+            Self::StaticIndexed(base, _) => {
+                query(|q| base.namespace().query_interface(base.id(), q))
+            }
+        }
+    }
+
+    ///
+    /// Whether the symbol's namespace provides the interface `I`.
+    ///
+    pub fn implements<I: ?Sized + 'static>(&self) -> bool {
+        self.as_trait::<I>().is_some()
+    }
+}
+
+///
+/// Implement `query_interface` in an `impl` of a namespace trait, providing `self` as each of the listed interfaces.
+///
+/// The `static` form is for [Static](crate::namespace::Static) namespaces, and the `wide` form is for
+/// [StaticWide](crate::namespace::StaticWide) namespaces. They provide the interfaces for all of their symbols:
+///
+/// ```ignore
+/// impl namespace::Static for Html {
+///     // ...
+///     dyn_symbol::query_interfaces!(static dyn SchemaProvider, dyn Documented);
+/// }
+///
+/// impl namespace::StaticWide for Columns {
+///     // ...
+///     dyn_symbol::query_interfaces!(wide dyn SchemaProvider);
+/// }
+///
+/// impl namespace::DynValue for Attr {
+///     // ...
+///     dyn_symbol::query_interfaces!(dyn SchemaProvider);
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! query_interfaces {
+    (static $($interface:ty),+ $(,)?) => {
+        fn query_interface<'a>(&'a self, _id: u32, query: &mut $crate::interface::Query<'a>) {
+            $(query.provide::<$interface>(self);)+
+        }
+    };
+    (wide $($interface:ty),+ $(,)?) => {
+        fn query_interface<'a>(&'a self, _id: u64, query: &mut $crate::interface::Query<'a>) {
+            $(query.provide::<$interface>(self);)+
+        }
+    };
+    ($($interface:ty),+ $(,)?) => {
+        fn query_interface<'a>(&'a self, query: &mut $crate::interface::Query<'a>) {
+            $(query.provide::<$interface>(self);)+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed::IndexedBase;
+    use crate::namespace;
+    use crate::tests::{dynamic, STATIC_A_0};
+    use crate::wide::WideNamespace;
+
+    trait Describe {
+        fn describe(&self) -> String;
+    }
+
+    trait Unrelated {}
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Color(String);
+
+    impl Describe for Color {
+        fn describe(&self) -> String {
+            format!("the color {}", self.0)
+        }
+    }
+
+    impl namespace::DynValue for Color {
+        fn namespace_name(&self) -> &str {
+            "color"
+        }
+
+        fn symbol_name(&self) -> &str {
+            &self.0
+        }
+
+        query_interfaces!(dyn Describe);
+    }
+
+    struct Sizes;
+
+    impl Describe for Sizes {
+        fn describe(&self) -> String {
+            "a size".to_string()
+        }
+    }
+
+    impl namespace::Static for Sizes {
+        fn namespace_name(&self) -> &str {
+            "size"
+        }
+
+        fn symbol_count(&self) -> u32 {
+            2
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["small", "large"][id as usize]
+        }
+
+        fn query_interface<'a>(&'a self, id: u32, query: &mut Query<'a>) {
+            if id == 1 {
+                query.provide::<dyn Describe>(self);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_interface() {
+        let red = Symbol::from(namespace::DynWrap(Color("red".into())));
+
+        assert_eq!(
+            red.as_trait::<dyn Describe>().unwrap().describe(),
+            "the color red"
+        );
+        assert!(!red.implements::<dyn Unrelated>());
+        assert!(!dynamic::sym0("red").implements::<dyn Describe>());
+    }
+
+    #[test]
+    fn test_static_interface() {
+        static LARGE: IndexedBase = IndexedBase::new(&Sizes, 1);
+
+        assert!(!Symbol::Static(&Sizes, 0).implements::<dyn Describe>());
+        assert_eq!(
+            Symbol::Static(&Sizes, 1)
+                .as_trait::<dyn Describe>()
+                .unwrap()
+                .describe(),
+            "a size"
+        );
+        assert!(LARGE.index(3).implements::<dyn Describe>());
+        assert!(!STATIC_A_0.implements::<dyn Describe>());
+    }
+
+    struct Cells;
+
+    impl Describe for Cells {
+        fn describe(&self) -> String {
+            "a cell".to_string()
+        }
+    }
+
+    impl namespace::StaticWide for Cells {
+        fn namespace_name(&self) -> &str {
+            "cell"
+        }
+
+        fn symbol_name(&self, _: u64) -> &str {
+            "cell"
+        }
+
+        query_interfaces!(wide dyn Describe);
+    }
+
+    #[test]
+    fn test_wide_interface() {
+        static CELLS: WideNamespace = WideNamespace::new(&Cells);
+
+        assert_eq!(
+            CELLS
+                .symbol(u64::MAX)
+                .as_trait::<dyn Describe>()
+                .unwrap()
+                .describe(),
+            "a cell"
+        );
+        assert!(!CELLS.symbol(0).implements::<dyn Unrelated>());
+    }
+
+    #[test]
+    fn test_query() {
+        let answer = query::<str>(|q| {
+            assert_eq!(q.interface(), TypeId::of::<str>());
+            q.provide::<dyn Describe>(&Sizes).provide::<str>("foo");
+        });
+
+        // Providing is not limited to trait objects.
+        assert_eq!(answer, Some("foo"));
+    }
+}
@@ -0,0 +1,205 @@
+//!
+//! Deduplicating interner for dynamic symbols.
+//!
+//! Interning the same name twice returns symbols sharing one canonical allocation. Equality checks between
+//! interned symbols from the same [Interner] succeed on pointer comparison, and hashing uses a precomputed
+//! [cached_hash](namespace::Dynamic::cached_hash), so neither has to look at the name itself.
+//!
+//! ```
+//! use dyn_symbol::interner;
+//!
+//! let a = interner::intern("data", "data-user-id");
+//! let b = interner::intern("data", "data-user-id");
+//!
+//! assert_eq!(a, b);
+//! assert_eq!(format!("{:?}", a), "data::data-user-id");
+//! assert_ne!(a, interner::intern("other", "data-user-id"));
+//! ```
+//!
+//! Interned symbols are kept alive by their interner. The [global] interner is never dropped.
+//!
+
+use crate::{namespace, Symbol};
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock, RwLock};
+
+type Table = HashMap<&'static str, HashMap<Box<str>, Arc<Interned>>>;
+
+///
+/// A concurrent symbol table, mapping `(namespace_name, name)` pairs to canonical dynamic symbols.
+///
+#[derive(Default)]
+pub struct Interner {
+    table: RwLock<Table>,
+}
+
+impl Interner {
+    ///
+    /// Create an empty interner.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Get the canonical symbol for `name` in the namespace `namespace_name`, creating it if necessary.
+    ///
+    pub fn intern(&self, namespace_name: &'static str, name: &str) -> Symbol {
+        let existing = self
+            .table
+            .read()
+            .unwrap()
+            .get(namespace_name)
+            .and_then(|names| names.get(name))
+            .cloned();
+
+        let interned = match existing {
+            Some(interned) => interned,
+            None => self
+                .table
+                .write()
+                .unwrap()
+                .entry(namespace_name)
+                .or_default()
+                .entry(name.into())
+                .or_insert_with(|| Arc::new(Interned::new(namespace_name, name.into())))
+                .clone(),
+        };
+
+        Symbol::Dynamic(interned)
+    }
+
+    ///
+    /// The number of interned symbols.
+    ///
+    pub fn len(&self) -> usize {
+        self.table.read().unwrap().values().map(HashMap::len).sum()
+    }
+
+    ///
+    /// Whether no symbols have been interned.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+///
+/// The global interner.
+///
+pub fn global() -> &'static Interner {
+    static GLOBAL: OnceLock<Interner> = OnceLock::new();
+    GLOBAL.get_or_init(Interner::new)
+}
+
+///
+/// Intern a symbol using the [global] interner.
+///
+pub fn intern(namespace_name: &'static str, name: &str) -> Symbol {
+    global().intern(namespace_name, name)
+}
+
+///
+/// The dynamic namespace of interned symbols.
+///
+/// Two interned symbols are equal when they have the same namespace name and name, even if they
+/// were interned by different [Interner]s.
+///
+#[derive(Clone, Debug)]
+pub struct Interned {
+    namespace_name: &'static str,
+    name: Box<str>,
+    hash: u64,
+}
+
+impl Interned {
+    fn new(namespace_name: &'static str, name: Box<str>) -> Self {
+        // DefaultHasher::new() is deterministic, so equal names always get equal hashes:
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (namespace_name, &name).hash(&mut hasher);
+
+        Self {
+            namespace_name,
+            name,
+            hash: hasher.finish(),
+        }
+    }
+}
+
+impl namespace::Dynamic for Interned {
+    fn namespace_name(&self) -> &str {
+        self.namespace_name
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.name
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        std::ptr::eq(self, rhs)
+            || (self.hash == rhs.hash
+                && self.namespace_name == rhs.namespace_name
+                && self.name == rhs.name)
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> std::cmp::Ordering {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        if std::ptr::eq(self, rhs) {
+            std::cmp::Ordering::Equal
+        } else {
+            (self.namespace_name, &self.name).cmp(&(rhs.namespace_name, &rhs.name))
+        }
+    }
+
+    fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+        state.write_u64(self.hash)
+    }
+
+    fn cached_hash(&self) -> Option<u64> {
+        Some(self.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(symbol: &Symbol) -> &Arc<dyn namespace::Dynamic> {
+        match symbol {
+            Symbol::Dynamic(instance) => instance,
+            _ => panic!("expected a dynamic symbol"),
+        }
+    }
+
+    #[test]
+    fn test_deduplication() {
+        let interner = Interner::new();
+        let a = interner.intern("ns", "foo");
+        let b = interner.intern("ns", "foo");
+        let c = interner.intern("ns", "bar");
+        let d = interner.intern("other", "foo");
+
+        assert!(Arc::ptr_eq(instance(&a), instance(&b)));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, d);
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn test_equal_across_interners() {
+        let a = Interner::new().intern("ns", "foo");
+        let b = Interner::new().intern("ns", "foo");
+
+        assert!(!Arc::ptr_eq(instance(&a), instance(&b)));
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+    }
+}
@@ -0,0 +1,102 @@
+//!
+//! Coarse classification of symbols across namespaces.
+//!
+//! Namespaces classify their symbols by implementing `symbol_kind`, and [Symbol::kind] returns the classification,
+//! so symbols can be grouped without downcasting to every known namespace type:
+//!
+//! ```
+//! use dyn_symbol::kind::SymbolKind;
+//! use dyn_symbol::*;
+//!
+//! struct Html;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["class", "onclick"][id as usize]
+//!     }
+//!
+//!     fn symbol_kind(&self, id: u32) -> Option<SymbolKind> {
+//!         Some([SymbolKind::Attribute, SymbolKind::Event][id as usize])
+//!     }
+//! }
+//!
+//! assert_eq!(Symbol::Static(&Html, 1).kind(), Some(SymbolKind::Event));
+//! ```
+//!
+
+use crate::Symbol;
+
+///
+/// The kind of a symbol, see [Symbol::kind].
+///
+/// Kinds not covered by the predefined variants use [Other](SymbolKind::Other) with a tag.
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum SymbolKind {
+    /// An attribute, like an HTML attribute.
+    Attribute,
+    /// An event, or the name of an event handler.
+    Event,
+    /// A property of an object.
+    Property,
+    /// Application defined data.
+    UserData,
+    /// Any other kind, identified by the tag.
+    Other(&'static str),
+}
+
+impl Symbol {
+    ///
+    /// The kind of this symbol, if its namespace classifies it.
+    /// Indexed symbols have the kind of their base symbol.
+    ///
+    pub fn kind(&self) -> Option<SymbolKind> {
+        match self {
+            Self::Static(ns, id) => ns.symbol_kind(*id),
+            Self::Dynamic(instance) => instance.symbol_kind(),
+            Self::Inline(_, _) => None,
+            Self::StaticIndexed(base, _) => base.namespace().symbol_kind(base.id()),
+            Self::StaticWide(ns, id) => ns.namespace().symbol_kind(*id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::namespace;
+    use crate::tests::{dynamic, STATIC_A_0};
+
+    #[test]
+    fn test_kind() {
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Setting(String);
+
+        impl namespace::DynValue for Setting {
+            fn namespace_name(&self) -> &str {
+                "setting"
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.0
+            }
+
+            fn symbol_kind(&self) -> Option<SymbolKind> {
+                Some(SymbolKind::Other("setting"))
+            }
+        }
+
+        let setting = Symbol::from(namespace::DynWrap(Setting("volume".into())));
+        assert_eq!(setting.kind(), Some(SymbolKind::Other("setting")));
+        assert_eq!(STATIC_A_0.kind(), None);
+        assert_eq!(dynamic::sym0("foo").kind(), None);
+    }
+}
@@ -19,14 +19,15 @@
 //! # Example use cases
 //! * Namespaced XML/HTML attributes (in HTML, some are static and some are dynamic. i.e. `data-` attributes)
 //! * Key/value stores for "anything"
-//! * Some way to abstract away string interners? (this is untested)
+//! * Some way to abstract away string interners (see the [interner] module)
 //!
 //! A [Symbol] is just one plain, non-generic type, that can represent all possible symbol values. It implements all traits to make it
 //! usable as a key in maps.
 //!
 //! # What this crate does not do
-//! * Serialization and deserialization of symbols. [Symbol] should not implement `serde` traits, ser/de should instead be handled by each namespace.
-//! * Provide any namespaces.
+//! * Serialization and deserialization of symbols by default. The optional `serde` feature implements `serde` traits for [Symbol],
+//!   but the actual ser/de is handled by each namespace, which has to opt in (see `serialization` module).
+//! * Provide any namespaces, except for the plain string namespace of the opt-in `str-namespace` feature (see `str_ns` module).
 //!
 //! # Static symbols
 //! Static symbols originate from a namespace where all possible values are statically known at compile time.
@@ -53,6 +54,10 @@
 //!         "my"
 //!     }
 //!
+//!     fn symbol_count(&self) -> u32 {
+//!         self.symbols.len() as u32
+//!     }
+//!
 //!     fn symbol_name(&self, id: u32) -> &str {
 //!         self.symbols[id as usize].0
 //!     }
@@ -82,10 +87,34 @@
 //! assert_eq!(get_symbol_description(&BAR).unwrap(), "the second symbol!");
 //! ```
 //!
-//! For static symbols, the implementations of [Eq]/[Ord]/[Hash](std::hash::Hash) et. al use only the namespace's [type_id](std::any::Any::type_id)
+//! For static symbols, the implementations of [Eq]/[Ord]/[Hash](core::hash::Hash) et. al use only the namespace's [type_id](core::any::Any::type_id)
 //! plus the symbol's numerical `id`.
 //!
+//! [Ord] orders symbols from different namespaces by their namespace names, so iteration order of e.g. a `BTreeMap`
+//! is reproducible across builds. Only namespaces of different types sharing the same name fall back to
+//! [TypeId](core::any::TypeId) order, which is not stable. Static symbols order before dynamic symbols,
+//! which order before inline symbols.
+//!
 //! Typically, the boilerplate code for a static namespace will be generated by macros or `build.rs`.
+//! With the `macros` feature enabled, `static_namespace!` generates the namespace struct, its
+//! [namespace::Static] implementation and the symbol constants:
+//!
+//! ```text
+//! static_namespace! {
+//!     pub my {
+//!         FOO = "foo",
+//!         BAR = "bar",
+//!     }
+//! }
+//!
+//! assert_eq!(format!("{:?}", my::FOO), "my::foo");
+//! ```
+//!
+//! Alternatively, `#[derive(StaticSymbols)]` turns a fieldless `enum` into a static namespace.
+//! For large, data-driven namespaces, the `codegen` feature provides a `build.rs` helper (see `codegen` module).
+//!
+//! For parameterized symbols like `column[17]`, a static symbol can be combined with a runtime index,
+//! see the [indexed] module.
 //!
 //! # Dynamic symbols
 //! Sometimes the values that a symbol can take are not known upfront. In this case we have to resort to memory allocation.
@@ -94,6 +123,7 @@
 //!
 //! ```
 //! use dyn_symbol::*;
+//! use std::sync::Arc;
 //!
 //! // This symbol is string-based:
 //! struct DynamicNS(String);
@@ -127,23 +157,30 @@
 //!     }
 //! }
 //!
-//! let foo0 = Symbol::Dynamic(Box::new(DynamicNS("foo".into())));
-//! let foo1 = Symbol::Dynamic(Box::new(DynamicNS("foo".into())));
-//! let bar = Symbol::Dynamic(Box::new(DynamicNS("bar".into())));
+//! let foo0 = Symbol::Dynamic(Arc::new(DynamicNS("foo".into())));
+//! let foo1 = Symbol::Dynamic(Arc::new(DynamicNS("foo".into())));
+//! let bar = Symbol::Dynamic(Arc::new(DynamicNS("bar".into())));
 //!
 //! assert_eq!(foo0, foo1);
 //! assert_eq!(foo0.clone(), foo1.clone());
 //! assert_ne!(foo0, bar);
 //! ```
 //!
+//! Dynamic symbol instances are reference counted, so cloning a dynamic [Symbol] is cheap and does not copy the instance.
+//! For short string-based names, the [inline] module avoids the allocation altogether by storing the name inside the [Symbol].
+//!
 //! It is entirely up to the Dynamic implementation to consider what kind of symbols are considered equal.
 //! The `Eq`/`Hash` symmetry need to hold, though.
 //!
+//! For value types that already implement [Clone], [Eq], [Ord] and [Hash](core::hash::Hash), most of this boilerplate can be
+//! avoided by implementing the smaller [namespace::DynValue] trait and wrapping values in [namespace::DynWrap].
+//! With the `macros` feature enabled, `#[derive(DynamicNamespace)]` generates the full [namespace::Dynamic] implementation instead.
+//!
 //! Dynamic symbols are supported as a companion to static symbols. If your application works mainly with dynamic symbols,
 //! you should consider using a different keying mechanism, because of the inherent overhead/indirection/boxing of dynamic symbols.
 //!
 //! # Type system
-//! This crate makes use of [Any](std::any::Any), and consideres namespaces sharing the same [TypeId](std::any::TypeId) to be the _same namespace_.
+//! This crate makes use of [Any](core::any::Any), and consideres namespaces sharing the same [TypeId](core::any::TypeId) to be the _same namespace_.
 //! This could make code reuse a bit cumbersome. If one crate exports multiple namespaces, this can be solved by using const generics:
 //!
 //! ```
@@ -159,9 +196,93 @@
 //!
 //! This will cause the two namespaces to have differing `type_id`s.
 //!
+//! Conversely, when two versions of a crate providing a namespace end up in the same program, their namespaces have
+//! differing `type_id`s too. Implementing [namespace_uid](namespace::Static::namespace_uid) makes such namespaces equal.
+//!
+//! # `no_std`
+//! The crate supports `no_std` targets with `alloc`, by disabling the default `std` feature.
+//! [Symbol], the namespace traits and the [inline], [symbol_ref] and [typed] modules are always available.
+//! The `codes`, `collections`, `interner`, `pool`, `registry`, `runtime_static` and `validation` modules, as well as the `serde`, `codegen`, `abi_stable`, `ffi`, `proptest`, `provenance`, `rkyv`, `string-cache` and `wasm` features, require `std`.
 //!
 
-use std::cmp::Ordering;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use symbol_ref::SymbolRef;
+
+#[cfg(feature = "std")]
+pub use validation::validate;
+
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "rkyv")]
+pub mod archive;
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(feature = "string-cache")]
+pub mod atom;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+#[cfg(feature = "std")]
+pub mod codes;
+
+#[cfg(feature = "std")]
+pub mod collections;
+pub mod downcast;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod indexed;
+pub mod info;
+pub mod inline;
+pub mod interface;
+#[cfg(feature = "std")]
+pub mod interner;
+pub mod kind;
+pub mod local;
+#[cfg(feature = "hashbrown")]
+pub mod lookup;
+pub mod normalize;
+pub mod path;
+pub mod pattern;
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "provenance")]
+pub mod provenance;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod runtime_static;
+#[cfg(feature = "str-namespace")]
+pub mod str_ns;
+pub mod symbol_ref;
+pub mod trace;
+pub mod typed;
+#[cfg(feature = "std")]
+pub mod validation;
+pub mod wide;
+
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+#[cfg(feature = "abi_stable")]
+pub mod stable_abi;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "macros")]
+pub use dyn_symbol_macros::{static_namespace, DynamicNamespace, StaticSymbols};
+
+#[doc(hidden)]
+pub mod __private {
+    //! Re-exports used by code generated by the `macros` feature, so that it also works in `no_std` crates.
+
+    pub use alloc::boxed::Box;
+    pub use alloc::sync::Arc;
+}
 
 ///
 /// A symbol, with support for mixed static/dynamic allocation.
@@ -176,25 +297,252 @@ pub enum Symbol {
     /// so a memory allocation is needed. This encoding allows dynamic namespaces to support
     /// the same semantics that static namespaces do. Instead of just using a [String], we
     /// can also encode what kind of string it is.
-    Dynamic(Box<dyn namespace::Dynamic>),
+    ///
+    /// The instance is reference counted, so cloning the symbol does not clone the instance.
+    Dynamic(Arc<dyn namespace::Dynamic>),
+
+    /// Construct a Symbol from a short string stored inline, without memory allocation.
+    /// The first parameter is the namespace, the second parameter is the name.
+    ///
+    /// Inline symbols are created using [InlineNamespace::symbol](inline::InlineNamespace::symbol).
+    Inline(&'static inline::InlineNamespace, inline::InlineName),
+
+    /// Construct a parameterized static symbol, like `column[17]`, without memory allocation.
+    /// The first parameter is the base symbol, the second parameter is the index.
+    ///
+    /// Indexed symbols are created using [IndexedBase::index](indexed::IndexedBase::index).
+    StaticIndexed(&'static indexed::IndexedBase, u32),
+
+    /// Construct a Symbol originating from a static namespace with `u64` ids, like packed composite ids.
+    /// The first parameter is the namespace, the second parameter is the symbol `id` within that namespace.
+    ///
+    /// Wide symbols are created using [WideNamespace::symbol](wide::WideNamespace::symbol).
+    StaticWide(&'static wide::WideNamespace, u64),
 }
 
 impl Symbol {
+    ///
+    /// The symbol's name within its namespace.
+    ///
     pub fn name(&self) -> &str {
         match self {
             Self::Static(ns, id) => ns.symbol_name(*id),
             Self::Dynamic(instance) => instance.symbol_name(),
+            Self::Inline(_, name) => name.as_str(),
+            Self::StaticIndexed(base, _) => base.namespace().symbol_name(base.id()),
+            Self::StaticWide(ns, id) => ns.namespace().symbol_name(*id),
+        }
+    }
+
+    ///
+    /// The name of the symbol's namespace.
+    ///
+    pub fn namespace(&self) -> &str {
+        match self {
+            Self::Static(ns, _) => ns.namespace_name(),
+            Self::Dynamic(instance) => instance.namespace_name(),
+            Self::Inline(ns, _) => ns.name(),
+            Self::StaticIndexed(base, _) => base.namespace().namespace_name(),
+            Self::StaticWide(ns, _) => ns.namespace().namespace_name(),
+        }
+    }
+
+    ///
+    /// The index of an [indexed](Self::StaticIndexed) static symbol.
+    ///
+    pub fn index(&self) -> Option<u32> {
+        match self {
+            Self::StaticIndexed(_, index) => Some(*index),
+            _ => None,
         }
     }
 
+    ///
+    /// Resolve a static symbol by its name within the namespace `ns`,
+    /// using [symbol_by_name](namespace::Static::symbol_by_name).
+    ///
+    pub fn resolve_static<NS: namespace::Static>(ns: &'static NS, name: &str) -> Option<Symbol> {
+        ns.symbol_by_name(name).map(|id| Self::Static(ns, id))
+    }
+
+    ///
+    /// Iterate over all the symbols of the static namespace `ns`, in `id` order.
+    ///
+    pub fn iter_static<NS: namespace::Static>(ns: &'static NS) -> impl Iterator<Item = Symbol> {
+        (0..ns.symbol_count()).map(move |id| Self::Static(ns, id))
+    }
+
+    ///
+    /// Create a dynamic symbol from a namespace instance.
+    ///
+    /// With the `provenance` feature, the caller's location is recorded, see `Symbol::provenance`.
+    ///
+    #[track_caller]
+    pub fn new_dynamic<T: namespace::Dynamic>(instance: T) -> Symbol {
+        let symbol = Self::Dynamic(Arc::new(instance));
+        #[cfg(feature = "provenance")]
+        provenance::record(&symbol, core::panic::Location::caller());
+        symbol
+    }
+
+    ///
+    /// Create a dynamic symbol from a [DynValue](namespace::DynValue), like [Symbol::new_dynamic].
+    ///
+    #[track_caller]
+    pub fn new_dyn_value<T: namespace::DynValue>(value: T) -> Symbol {
+        Self::new_dynamic(namespace::DynWrap(value))
+    }
+
     ///
     /// Get access to the associated namespace's `Any` representation.
     /// its `type_id` may be used as a reflection tool to get to know about the Symbol's origin.
     ///
-    pub fn as_any(&self) -> &dyn std::any::Any {
+    pub fn as_any(&self) -> &dyn core::any::Any {
         match self {
             Self::Static(ns, _) => ns.as_any(),
             Self::Dynamic(instance) => instance.as_any(),
+            Self::Inline(ns, _) => *ns,
+            Self::StaticIndexed(base, _) => base.namespace().as_any(),
+            Self::StaticWide(ns, _) => ns.namespace().as_any(),
+        }
+    }
+
+    ///
+    /// The [TypeId](core::any::TypeId) of the symbol's namespace type. See [SymbolRef::namespace_type_id].
+    ///
+    pub fn namespace_type_id(&self) -> core::any::TypeId {
+        self.as_symbol_ref().namespace_type_id()
+    }
+
+    ///
+    /// Whether the symbol's namespace is of type `NS`, without downcasting.
+    ///
+    pub fn is<NS: 'static>(&self) -> bool {
+        self.as_symbol_ref().is::<NS>()
+    }
+
+    ///
+    /// Whether the two symbols are from the same namespace. See [SymbolRef::same_namespace].
+    ///
+    pub fn same_namespace(&self, other: &Symbol) -> bool {
+        self.as_symbol_ref().same_namespace(&other.as_symbol_ref())
+    }
+
+    ///
+    /// Query the symbol's namespace for metadata of type `M`.
+    ///
+    /// ```
+    /// use dyn_symbol::*;
+    /// use std::any::{Any, TypeId};
+    ///
+    /// struct Deprecated(bool);
+    ///
+    /// struct MyStaticNS;
+    ///
+    /// impl namespace::Static for MyStaticNS {
+    ///     fn namespace_name(&self) -> &str {
+    ///         "my"
+    ///     }
+    ///
+    ///     fn symbol_count(&self) -> u32 {
+    ///         2
+    ///     }
+    ///
+    ///     fn symbol_name(&self, id: u32) -> &str {
+    ///         ["old", "new"][id as usize]
+    ///     }
+    ///
+    ///     fn metadata(&self, id: u32, type_id: TypeId) -> Option<&dyn Any> {
+    ///         static DEPRECATED: [Deprecated; 2] = [Deprecated(true), Deprecated(false)];
+    ///
+    ///         if type_id == TypeId::of::<Deprecated>() {
+    ///             Some(&DEPRECATED[id as usize])
+    ///         } else {
+    ///             None
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert!(Symbol::Static(&MyStaticNS, 0).metadata::<Deprecated>().unwrap().0);
+    /// assert!(!Symbol::Static(&MyStaticNS, 1).metadata::<Deprecated>().unwrap().0);
+    /// assert!(Symbol::Static(&MyStaticNS, 1).metadata::<String>().is_none());
+    /// ```
+    ///
+    pub fn metadata<M: 'static>(&self) -> Option<&M> {
+        let type_id = core::any::TypeId::of::<M>();
+        let metadata = match self {
+            Self::Static(ns, id) => ns.metadata(*id, type_id),
+            Self::Dynamic(instance) => instance.metadata(type_id),
+            Self::Inline(_, _) => None,
+            Self::StaticIndexed(base, _) => base.namespace().metadata(base.id(), type_id),
+            Self::StaticWide(ns, id) => ns.namespace().metadata(*id, type_id),
+        };
+        metadata?.downcast_ref::<M>()
+    }
+
+    ///
+    /// The parent of this symbol, if its namespace arranges symbols in a hierarchy
+    /// (see [Static::parent](namespace::Static::parent) and [Dynamic::parent](namespace::Dynamic::parent)).
+    /// Indexed symbols have the parent of their base symbol.
+    ///
+    pub fn parent(&self) -> Option<Symbol> {
+        match self {
+            Self::Static(ns, id) => ns.parent(*id).map(|parent| Self::Static(*ns, parent)),
+            Self::Dynamic(instance) => instance.parent(),
+            Self::StaticWide(ns, id) => ns.namespace().parent(*id).map(|parent| ns.symbol(parent)),
+            Self::StaticIndexed(base, _) => base
+                .namespace()
+                .parent(base.id())
+                .map(|parent| Self::Static(base.namespace(), parent)),
+            Self::Inline(_, _) => None,
+        }
+    }
+
+    ///
+    /// The parent of this symbol, followed by its parent and so on, up to the root of the hierarchy.
+    ///
+    /// Namespaces must not arrange their symbols in cycles of parents, or the iterator never ends.
+    ///
+    pub fn ancestors(&self) -> impl Iterator<Item = Symbol> {
+        core::iter::successors(self.parent(), Symbol::parent)
+    }
+
+    ///
+    /// Whether `ancestor` is one of this symbol's [ancestors](Self::ancestors). A symbol is not its own descendant.
+    ///
+    pub fn is_descendant_of(&self, ancestor: &Symbol) -> bool {
+        self.ancestors().any(|symbol| symbol == *ancestor)
+    }
+
+    ///
+    /// The segments of this symbol's path in its hierarchy, from the root down to this symbol.
+    ///
+    /// Namespaces can provide the segments (see [Static::path_segments](namespace::Static::path_segments)),
+    /// e.g. by splitting qualified names. Otherwise they are the names of the [ancestors](Self::ancestors),
+    /// root first, followed by the name of this symbol.
+    ///
+    pub fn path_segments(&self) -> alloc::vec::Vec<alloc::borrow::Cow<'_, str>> {
+        use alloc::borrow::Cow;
+
+        let segments = match self {
+            Self::Static(ns, id) => ns.path_segments(*id),
+            Self::Dynamic(instance) => instance.path_segments(),
+            Self::StaticWide(ns, id) => ns.namespace().path_segments(*id),
+            Self::StaticIndexed(base, _) => base.namespace().path_segments(base.id()),
+            Self::Inline(_, _) => None,
+        };
+
+        match segments {
+            Some(segments) => segments.into_iter().map(Cow::Borrowed).collect(),
+            None => {
+                let mut segments: alloc::vec::Vec<_> = self
+                    .ancestors()
+                    .map(|ancestor| Cow::Owned(ancestor.name().into()))
+                    .collect();
+                segments.reverse();
+                segments.push(Cow::Borrowed(self.name()));
+                segments
+            }
         }
     }
 
@@ -205,174 +553,1323 @@ impl Symbol {
     pub fn downcast_static<T: 'static>(&self) -> Option<(&T, u32)> {
         match self {
             Self::Static(ns, id) => ns.as_any().downcast_ref::<T>().map(|t| (t, *id)),
-            Self::Dynamic(_) => None,
+            Self::Dynamic(_)
+            | Self::Inline(_, _)
+            | Self::StaticIndexed(_, _)
+            | Self::StaticWide(_, _) => None,
         }
     }
 
     ///
     /// Try to downcast this Symbol's _dynamic namespace_ as a `&T`.
     ///
-    /// Always fails for static namespaces.
+    /// Always fails for static namespaces and inline symbols.
     ///
     pub fn downcast_dyn<T: 'static>(&self) -> Option<&T> {
         match self {
-            Self::Static(_, _) => None,
+            Self::Static(_, _)
+            | Self::Inline(_, _)
+            | Self::StaticIndexed(_, _)
+            | Self::StaticWide(_, _) => None,
             Self::Dynamic(instance) => instance.as_any().downcast_ref::<T>(),
         }
     }
+
+    ///
+    /// Try to downcast this Symbol's _dynamic namespace_ as a `&mut T`.
+    ///
+    /// Dynamic instances are shared between clones of a symbol, so if the instance is shared, this symbol first gets
+    /// its own copy using [dyn_clone](namespace::Dynamic::dyn_clone). Mutations must not change the symbol's equality
+    /// or hash, e.g. while it's used as a map key.
+    ///
+    /// Always fails for static namespaces and inline symbols.
+    ///
+    pub fn downcast_dyn_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        match self {
+            Self::Static(_, _)
+            | Self::Inline(_, _)
+            | Self::StaticIndexed(_, _)
+            | Self::StaticWide(_, _) => None,
+            Self::Dynamic(instance) => {
+                if !instance.as_any().is::<T>() {
+                    return None;
+                }
+                if Arc::get_mut(instance).is_none() {
+                    *instance = Arc::from(instance.dyn_clone());
+                }
+                Arc::get_mut(instance)?.as_any_mut().downcast_mut::<T>()
+            }
+        }
+    }
+
+    ///
+    /// Try to downcast this Symbol's _dynamic namespace_ into an owned `T`, consuming the symbol.
+    ///
+    /// If this symbol is the last owner of its instance, the instance is moved out without copying. Otherwise
+    /// the caller gets its own copy using [dyn_clone](namespace::Dynamic::dyn_clone), and the other clones keep
+    /// the shared instance.
+    ///
+    /// Fails with the unchanged symbol for other types, static namespaces and inline symbols.
+    ///
+    pub fn into_dynamic<T: namespace::Dynamic>(self) -> Result<T, Symbol> {
+        self.into_boxed_dynamic().map(|boxed| *boxed)
+    }
+
+    ///
+    /// Like [into_dynamic](Self::into_dynamic), but boxes the instance.
+    ///
+    /// A shared instance is copied straight into the returned box.
+    ///
+    pub fn into_boxed_dynamic<T: namespace::Dynamic>(self) -> Result<alloc::boxed::Box<T>, Symbol> {
+        match self {
+            Self::Static(_, _)
+            | Self::Inline(_, _)
+            | Self::StaticIndexed(_, _)
+            | Self::StaticWide(_, _) => Err(self),
+            Self::Dynamic(instance) => match instance.downcast_arc::<T>() {
+                Ok(concrete) => match Arc::try_unwrap(concrete) {
+                    Ok(owned) => Ok(alloc::boxed::Box::new(owned)),
+                    Err(shared) => match shared.dyn_clone().downcast::<T>() {
+                        Ok(copy) => Ok(copy),
+                        Err(_) => panic!("dyn_clone must return an instance of the same type"),
+                    },
+                },
+                Err(instance) => Err(Self::Dynamic(instance)),
+            },
+        }
+    }
+
+    ///
+    /// Borrow this symbol as a [SymbolRef].
+    ///
+    pub fn as_symbol_ref(&self) -> SymbolRef<'_> {
+        match self {
+            Self::Static(ns, id) => SymbolRef::Static(*ns, *id),
+            Self::Dynamic(instance) => SymbolRef::Dynamic(instance.as_ref()),
+            Self::Inline(ns, name) => SymbolRef::Inline(ns, name),
+            Self::StaticIndexed(base, index) => SymbolRef::StaticIndexed(base, *index),
+            Self::StaticWide(ns, id) => SymbolRef::StaticWide(ns, *id),
+        }
+    }
+
+    ///
+    /// Get the canonical form of this symbol: dynamic symbols that their namespace recognizes through
+    /// [try_to_static](namespace::Dynamic::try_to_static) are replaced by an allocation-free symbol,
+    /// other symbols are returned as they are.
+    ///
+    /// This lets parsers create permissive dynamic symbols, and converge on cheap static symbols for known names.
+    ///
+    pub fn canonicalize(&self) -> Symbol {
+        match self {
+            Self::Dynamic(instance) => instance.try_to_static().unwrap_or_else(|| self.clone()),
+            _ => self.clone(),
+        }
+    }
+
+    ///
+    /// Hash the symbol in a way that is stable across builds and program versions, unlike [Hash](core::hash::Hash),
+    /// which mixes in the namespace's [TypeId](core::any::TypeId).
+    ///
+    /// Only the namespace name, the symbol name and the [index](Symbol::index), if any, are hashed,
+    /// so the stable hash of a symbol does not change when ids are reassigned. For the result to be persistable,
+    /// the hasher must be stable too (the algorithm of `DefaultHasher` is not guaranteed to stay the same between Rust releases).
+    ///
+    pub fn stable_hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write(self.namespace().as_bytes());
+        state.write_u8(0xff);
+        state.write(self.name().as_bytes());
+        state.write_u8(0xff);
+        if let Some(index) = self.index() {
+            state.write(&index.to_le_bytes());
+        }
+    }
 }
 
 impl Clone for Symbol {
     fn clone(&self) -> Self {
         match self {
             Self::Static(static_symbol, id) => Self::Static(*static_symbol, *id),
-            Self::Dynamic(instance) => Self::Dynamic(instance.dyn_clone()),
+            Self::Dynamic(instance) => Self::Dynamic(instance.clone()),
+            Self::Inline(ns, name) => Self::Inline(ns, *name),
+            Self::StaticIndexed(base, index) => Self::StaticIndexed(base, *index),
+            Self::StaticWide(ns, id) => Self::StaticWide(ns, *id),
         }
     }
 }
 
-impl std::fmt::Debug for Symbol {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.as_symbol_ref(), f)
+    }
+}
+
+impl core::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
-            Self::Static(ns, id) => {
-                write!(f, "{}::{}", ns.namespace_name(), ns.symbol_name(*id))
+            Self::Static(ns, id) => ns.fmt_display(*id, f),
+            Self::Dynamic(instance) => instance.fmt_display(f),
+            Self::Inline(ns, name) => write!(f, "{}::{}", ns.name(), name.as_str()),
+            Self::StaticIndexed(base, index) => {
+                base.namespace().fmt_display(base.id(), f)?;
+                write!(f, "[{}]", index)
             }
-            Self::Dynamic(instance) => {
-                write!(
-                    f,
-                    "{}::{}",
-                    instance.namespace_name(),
-                    instance.symbol_name()
-                )
+            Self::StaticWide(ns, id) => ns.namespace().fmt_display(*id, f),
+        }
+    }
+}
+
+///
+/// The form of a [Symbol] used for equality, ordering and hashing:
+/// dynamic symbols with a [static_equivalent](namespace::Dynamic::static_equivalent) are compared as that static symbol.
+///
+#[derive(Clone, Copy)]
+pub(crate) enum Canonical<'a> {
+    /// A static symbol, and the index of an [indexed](indexed::IndexedBase) static symbol.
+    Static(&'static dyn namespace::Static, u32, Option<u32>),
+    Wide(&'static dyn namespace::StaticWide, u64),
+    Dynamic(&'a dyn namespace::Dynamic),
+    /// A [LocalSymbol](local::LocalSymbol) instance.
+    Local(&'a dyn namespace::LocalDynamic),
+    Inline(&'a inline::InlineNamespace, &'a str),
+}
+
+impl<'a> Canonical<'a> {
+    pub(crate) fn of(symbol: SymbolRef<'a>) -> Self {
+        match symbol {
+            SymbolRef::Static(ns, id) => Self::Static(ns, id, None),
+            SymbolRef::StaticIndexed(base, index) => {
+                Self::Static(base.namespace(), base.id(), Some(index))
             }
+            SymbolRef::StaticWide(ns, id) => Self::Wide(ns.namespace(), id),
+            SymbolRef::Dynamic(instance) => match instance.static_equivalent() {
+                Some((ns, id)) => Self::Static(ns, id, None),
+                None => Self::Dynamic(instance),
+            },
+            SymbolRef::Inline(ns, name) => Self::Inline(ns, name.as_str()),
+        }
+    }
+
+    fn hashes_by_name(&self) -> bool {
+        match *self {
+            Self::Static(ns, _, None) => ns.hash_by_name(),
+            // Indexed symbols have the same names as their base symbol.
+            Self::Static(_, _, Some(_)) => false,
+            Self::Wide(ns, _) => ns.hash_by_name(),
+            Self::Dynamic(instance) => instance.hash_by_name(),
+            Self::Local(_) => false,
+            Self::Inline(_, _) => true,
+        }
+    }
+
+    fn names(&self) -> (&'a str, &'a str) {
+        match *self {
+            Self::Static(ns, id, _) => (ns.namespace_name(), ns.symbol_name(id)),
+            Self::Wide(ns, id) => (ns.namespace_name(), ns.symbol_name(id)),
+            Self::Dynamic(instance) => (instance.namespace_name(), instance.symbol_name()),
+            Self::Local(instance) => (instance.namespace_name(), instance.symbol_name()),
+            Self::Inline(ns, name) => (ns.name(), name),
         }
     }
 }
 
-impl PartialEq for Symbol {
-    fn eq(&self, rhs: &Symbol) -> bool {
-        match (self, rhs) {
-            (Self::Static(this_ns, this_id), Self::Static(rhs_ns, rhs_id)) => {
-                *this_id == *rhs_id && this_ns.type_id() == rhs_ns.type_id()
+impl Symbol {
+    fn canonical(&self) -> Canonical<'_> {
+        Canonical::of(self.as_symbol_ref())
+    }
+
+    ///
+    /// The namespace and symbol names, if the symbol is [hashed by them](namespace::Static::hash_by_name).
+    ///
+    #[cfg(feature = "hashbrown")]
+    pub(crate) fn hashed_names(&self) -> Option<(&str, &str)> {
+        let canonical = self.canonical();
+        if canonical.hashes_by_name() {
+            Some(canonical.names())
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// The static symbol this symbol is, or is equivalent to.
+    ///
+    pub(crate) fn static_form(&self) -> Option<(&'static dyn namespace::Static, u32)> {
+        match self.canonical() {
+            Canonical::Static(ns, id, None) => Some((ns, id)),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The id of the [static form](Self::static_form) of this symbol, if it is in the same namespace as `ns`.
+    ///
+    pub(crate) fn static_id_in(&self, ns: &'static dyn namespace::Static) -> Option<u32> {
+        let (symbol_ns, id) = self.static_form()?;
+        if same_static(symbol_ns, ns)
+            || NamespaceId::of_static(symbol_ns) == NamespaceId::of_static(ns)
+        {
+            Some(id)
+        } else {
+            None
+        }
+    }
+}
+
+///
+/// Fast path for comparing static namespaces, without looking up their [NamespaceId]s.
+///
+/// The whole pointer is compared, because zero-sized namespaces of different types can share an address.
+/// Pointers with the same address and vtable are the same namespace.
+///
+fn same_static(a: &'static dyn namespace::Static, b: &'static dyn namespace::Static) -> bool {
+    core::ptr::eq(a, b)
+}
+
+///
+/// Like [same_static], for wide namespaces.
+///
+fn same_wide(a: &'static dyn namespace::StaticWide, b: &'static dyn namespace::StaticWide) -> bool {
+    core::ptr::eq(a, b)
+}
+
+impl PartialEq for Canonical<'_> {
+    fn eq(&self, rhs: &Self) -> bool {
+        match (*self, *rhs) {
+            (
+                Canonical::Static(this_ns, this_id, this_index),
+                Canonical::Static(rhs_ns, rhs_id, rhs_index),
+            ) => {
+                this_id == rhs_id
+                    && this_index == rhs_index
+                    && (same_static(this_ns, rhs_ns)
+                        || NamespaceId::of_static(this_ns) == NamespaceId::of_static(rhs_ns))
+            }
+            (Canonical::Wide(this_ns, this_id), Canonical::Wide(rhs_ns, rhs_id)) => {
+                this_id == rhs_id
+                    && (same_wide(this_ns, rhs_ns)
+                        || NamespaceId::of_wide(this_ns) == NamespaceId::of_wide(rhs_ns))
+            }
+            (Canonical::Dynamic(this), Canonical::Dynamic(rhs)) if core::ptr::eq(this, rhs) => true,
+            (Canonical::Dynamic(this), Canonical::Dynamic(rhs)) => {
+                match (NamespaceId::of_dynamic(this), NamespaceId::of_dynamic(rhs)) {
+                    (NamespaceId::Type(this_type_id), NamespaceId::Type(rhs_type_id)) => {
+                        this_type_id == rhs_type_id && this.dyn_eq(rhs)
+                    }
+                    (this_uid, rhs_uid) => {
+                        this_uid == rhs_uid && this.normalized_name() == rhs.normalized_name()
+                    }
+                }
+            }
+            (Canonical::Local(this), Canonical::Local(rhs)) => {
+                core::ptr::eq(this, rhs)
+                    || (core::any::Any::type_id(this.as_any())
+                        == core::any::Any::type_id(rhs.as_any())
+                        && this.dyn_eq(rhs))
             }
-            (Self::Dynamic(this), Self::Dynamic(rhs)) => {
-                this.type_id() == rhs.type_id() && this.dyn_eq(rhs.as_ref())
+            (Canonical::Inline(this_ns, this_name), Canonical::Inline(rhs_ns, rhs_name)) => {
+                this_name == rhs_name && this_ns.same_namespace(rhs_ns)
             }
             _ => false,
         }
     }
 }
 
-impl Eq for Symbol {}
-
-impl Ord for Symbol {
-    fn cmp(&self, rhs: &Symbol) -> Ordering {
-        match (self, rhs) {
-            (Self::Static(this_ns, this_id), Self::Static(rhs_ns, rhs_id)) => {
-                let this_type_id = this_ns.type_id();
-                let rhs_type_id = rhs_ns.type_id();
+impl Eq for Canonical<'_> {}
+
+impl Ord for Canonical<'_> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        match (*self, *rhs) {
+            (
+                Canonical::Static(this_ns, this_id, this_index),
+                Canonical::Static(rhs_ns, rhs_id, rhs_index),
+            ) if same_static(this_ns, rhs_ns) => (this_id, this_index).cmp(&(rhs_id, rhs_index)),
+            (
+                Canonical::Static(this_ns, this_id, this_index),
+                Canonical::Static(rhs_ns, rhs_id, rhs_index),
+            ) => {
+                let this_ns_id = NamespaceId::of_static(this_ns);
+                let rhs_ns_id = NamespaceId::of_static(rhs_ns);
+
+                if this_ns_id == rhs_ns_id {
+                    (this_id, this_index).cmp(&(rhs_id, rhs_index))
+                } else {
+                    this_ns
+                        .namespace_name()
+                        .cmp(rhs_ns.namespace_name())
+                        .then_with(|| this_ns_id.cmp(&rhs_ns_id))
+                }
+            }
+            (Canonical::Wide(this_ns, this_id), Canonical::Wide(rhs_ns, rhs_id))
+                if same_wide(this_ns, rhs_ns) =>
+            {
+                this_id.cmp(&rhs_id)
+            }
+            (Canonical::Wide(this_ns, this_id), Canonical::Wide(rhs_ns, rhs_id)) => {
+                let this_ns_id = NamespaceId::of_wide(this_ns);
+                let rhs_ns_id = NamespaceId::of_wide(rhs_ns);
 
-                if this_type_id == rhs_type_id {
+                if this_ns_id == rhs_ns_id {
                     this_id.cmp(&rhs_id)
                 } else {
-                    this_type_id.cmp(&rhs_type_id)
+                    this_ns
+                        .namespace_name()
+                        .cmp(rhs_ns.namespace_name())
+                        .then_with(|| this_ns_id.cmp(&rhs_ns_id))
+                }
+            }
+            (Canonical::Dynamic(this), Canonical::Dynamic(rhs)) if core::ptr::eq(this, rhs) => {
+                Ordering::Equal
+            }
+            (Canonical::Dynamic(this), Canonical::Dynamic(rhs)) => {
+                let this_ns_id = NamespaceId::of_dynamic(this);
+                let rhs_ns_id = NamespaceId::of_dynamic(rhs);
+
+                match (this_ns_id == rhs_ns_id, &this_ns_id) {
+                    (true, NamespaceId::Type(_)) => this.dyn_cmp(rhs),
+                    (true, NamespaceId::Uid(_)) => {
+                        this.normalized_name().cmp(&rhs.normalized_name())
+                    }
+                    (false, _) => this
+                        .namespace_name()
+                        .cmp(rhs.namespace_name())
+                        .then_with(|| this_ns_id.cmp(&rhs_ns_id)),
                 }
             }
-            (Self::Dynamic(this), Self::Dynamic(rhs)) => {
-                let this_type_id = this.type_id();
-                let rhs_type_id = rhs.type_id();
+            (Canonical::Local(this), Canonical::Local(rhs)) if core::ptr::eq(this, rhs) => {
+                Ordering::Equal
+            }
+            (Canonical::Local(this), Canonical::Local(rhs)) => {
+                let this_type_id = core::any::Any::type_id(this.as_any());
+                let rhs_type_id = core::any::Any::type_id(rhs.as_any());
 
                 if this_type_id == rhs_type_id {
-                    this.dyn_cmp(rhs.as_ref())
+                    this.dyn_cmp(rhs)
                 } else {
-                    this_type_id.cmp(&rhs_type_id)
+                    this.namespace_name()
+                        .cmp(rhs.namespace_name())
+                        .then_with(|| this_type_id.cmp(&rhs_type_id))
                 }
             }
-            (Self::Static(_, _), Self::Dynamic(_)) => Ordering::Less,
-            (Self::Dynamic(_), Self::Static(_, _)) => Ordering::Greater,
+            (Canonical::Inline(this_ns, this_name), Canonical::Inline(rhs_ns, rhs_name)) => {
+                (this_ns.name(), this_name).cmp(&(rhs_ns.name(), rhs_name))
+            }
+            (Canonical::Static(_, _, _), _) => Ordering::Less,
+            (_, Canonical::Static(_, _, _)) => Ordering::Greater,
+            (Canonical::Wide(_, _), _) => Ordering::Less,
+            (_, Canonical::Wide(_, _)) => Ordering::Greater,
+            (Canonical::Dynamic(_), _) => Ordering::Less,
+            (_, Canonical::Dynamic(_)) => Ordering::Greater,
+            (Canonical::Local(_), Canonical::Inline(_, _)) => Ordering::Less,
+            (Canonical::Inline(_, _), Canonical::Local(_)) => Ordering::Greater,
         }
     }
 }
 
-impl PartialOrd for Symbol {
-    fn partial_cmp(&self, other: &Symbol) -> Option<std::cmp::Ordering> {
+impl PartialOrd for Canonical<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl std::hash::Hash for Symbol {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        match self {
-            Self::Static(ns, id) => {
-                ns.type_id().hash(state);
-                state.write_u32(*id)
+impl core::hash::Hash for Canonical<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        if self.hashes_by_name() {
+            // Same as the hash of the `(namespace, name)` tuple, which name based lookups rely on.
+            return self.names().hash(state);
+        }
+
+        match *self {
+            Canonical::Static(ns, id, index) => {
+                NamespaceId::of_static(ns).hash(state);
+                state.write_u32(id);
+                if let Some(index) = index {
+                    state.write_u32(index)
+                }
+            }
+            Canonical::Wide(ns, id) => {
+                NamespaceId::of_wide(ns).hash(state);
+                state.write_u64(id)
+            }
+            Canonical::Dynamic(dynamic_sym) => {
+                let ns_id = NamespaceId::of_dynamic(dynamic_sym);
+                ns_id.hash(state);
+                match (ns_id, dynamic_sym.cached_hash()) {
+                    (NamespaceId::Uid(_), _) => {
+                        state.write(dynamic_sym.normalized_name().as_bytes());
+                        state.write_u8(0xff)
+                    }
+                    (NamespaceId::Type(_), Some(hash)) => state.write_u64(hash),
+                    (NamespaceId::Type(_), None) => dynamic_sym.dyn_hash(state),
+                }
             }
-            Self::Dynamic(dynamic_sym) => {
-                dynamic_sym.type_id().hash(state);
-                dynamic_sym.dyn_hash(state)
+            Canonical::Local(local_sym) => {
+                core::any::Any::type_id(local_sym.as_any()).hash(state);
+                local_sym.dyn_hash(state)
             }
+            Canonical::Inline(_, _) => unreachable!(),
         }
     }
 }
 
-pub mod namespace {
-    //!
-    //! Namespace traits that must be implemented by symbol providers.
-    //!
+impl PartialEq for Symbol {
+    fn eq(&self, rhs: &Symbol) -> bool {
+        self.canonical() == rhs.canonical()
+    }
+}
 
-    use downcast_rs::*;
+impl Eq for Symbol {}
 
-    ///
-    /// A static namespace. Symbols in a static namespace are identified with an `id` encoded as a `u32`.
-    ///
-    pub trait Static: Send + Sync + Downcast {
-        ///
-        /// The namespace's name, used for [Debug][std::fmt::Debug].
-        ///
-        fn namespace_name(&self) -> &str;
+///
+/// Compare with a qualified `namespace::symbol` name, followed by `[index]` for indexed symbols,
+/// as formatted by [Symbol::display].
+///
+impl PartialEq<str> for Symbol {
+    fn eq(&self, qualified_name: &str) -> bool {
+        let rest = match qualified_name
+            .strip_prefix(self.namespace())
+            .and_then(|rest| rest.strip_prefix("::"))
+            .and_then(|rest| rest.strip_prefix(self.name()))
+        {
+            Some(rest) => rest,
+            None => return false,
+        };
 
-        ///
-        /// A symbol's name, used for [Debug][std::fmt::Debug].
-        ///
-        fn symbol_name(&self, id: u32) -> &str;
+        match self.index() {
+            None => rest.is_empty(),
+            Some(index) => {
+                rest.strip_prefix('[')
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .filter(|digits| is_canonical_decimal(digits))
+                    .and_then(|digits| digits.parse::<u32>().ok())
+                    == Some(index)
+            }
+        }
     }
+}
 
-    ///
-    /// A dynamic namespace. A dynamic symbol instance is tied to `Self`.
-    ///
-    pub trait Dynamic: Send + Sync + Downcast {
-        ///
-        /// The namespace's name, used for [Debug][std::fmt::Debug].
-        ///
-        fn namespace_name(&self) -> &str;
+/// Whether `digits` is formatted the way `u32`'s [Display](core::fmt::Display) would, without sign or leading zeros.
+fn is_canonical_decimal(digits: &str) -> bool {
+    digits.bytes().all(|byte| byte.is_ascii_digit()) && (digits == "0" || !digits.starts_with('0'))
+}
 
-        ///
-        /// The symbol name, used for [Debug][std::fmt::Debug].
-        ///
-        fn symbol_name(&self) -> &str;
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, qualified_name: &&str) -> bool {
+        self == *qualified_name
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, symbol: &Symbol) -> bool {
+        symbol == self
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, rhs: &Symbol) -> Ordering {
+        self.canonical().cmp(&rhs.canonical())
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Symbol) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl core::hash::Hash for Symbol {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+///
+/// The identity of a namespace: its [namespace_uid](namespace::Static::namespace_uid) if it has one, otherwise its type.
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum NamespaceId {
+    Type(core::any::TypeId),
+    Uid(u128),
+}
+
+impl NamespaceId {
+    pub(crate) fn of_static(ns: &dyn namespace::Static) -> Self {
+        match ns.namespace_uid() {
+            Some(uid) => Self::Uid(uid),
+            None => Self::Type(ns.type_id()),
+        }
+    }
+
+    pub(crate) fn of_dynamic(instance: &dyn namespace::Dynamic) -> Self {
+        match instance.namespace_uid() {
+            Some(uid) => Self::Uid(uid),
+            None => Self::Type(instance.type_id()),
+        }
+    }
+
+    pub(crate) fn of_wide(ns: &dyn namespace::StaticWide) -> Self {
+        match ns.namespace_uid() {
+            Some(uid) => Self::Uid(uid),
+            None => Self::Type(ns.type_id()),
+        }
+    }
+}
+
+pub mod namespace {
+    //!
+    //! Namespace traits that must be implemented by symbol providers.
+    //!
+
+    use alloc::boxed::Box;
+    use downcast_rs::*;
+
+    ///
+    /// A static namespace. Symbols in a static namespace are identified with an `id` encoded as a `u32`.
+    ///
+    pub trait Static: Send + Sync + Downcast {
+        ///
+        /// The namespace's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn namespace_name(&self) -> &str;
+
+        ///
+        /// The number of symbols in this namespace. Valid symbol ids are `0..symbol_count()`.
+        ///
+        /// This method is required since 0.2.0. Implementations written for 0.1 must add it.
+        ///
+        fn symbol_count(&self) -> u32;
+
+        ///
+        /// A symbol's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn symbol_name(&self, id: u32) -> &str;
+
+        ///
+        /// A globally unique identifier for this namespace. By default, namespaces are identified by their
+        /// [TypeId](core::any::TypeId), so the "same" namespace from two versions of a crate is considered two
+        /// different namespaces. Namespaces returning `Some` are identified by the uid instead.
+        ///
+        /// The uid must be the same for all instances of the namespace type, and ids must mean the same symbols
+        /// in every namespace type sharing the uid.
+        ///
+        fn namespace_uid(&self) -> Option<u128> {
+            None
+        }
+
+        ///
+        /// Whether symbols are hashed by their namespace and symbol names only, instead of by namespace type and id.
+        ///
+        /// The hash is then the same as that of a `(namespace, name)` tuple of strings, so maps keyed by [Symbol](crate::Symbol)
+        /// can be probed by names without constructing a symbol (see the `lookup` module).
+        /// Hashing strings is slower than hashing ids, so this is an opt-in. Symbol names must be unique within the namespace.
+        ///
+        fn hash_by_name(&self) -> bool {
+            false
+        }
+
+        ///
+        /// Whether symbol names are unique within the namespace, which the [validation](crate::validation) module checks.
+        ///
+        /// Namespaces that [hash by name](Self::hash_by_name) must have unique names whether or not this returns `true`.
+        ///
+        fn unique_names(&self) -> bool {
+            false
+        }
+
+        ///
+        /// Format a symbol for [Display](core::fmt::Display). The default format is `namespace::symbol`.
+        ///
+        fn fmt_display(&self, id: u32, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name(id))
+        }
+
+        ///
+        /// Format a symbol for [Debug](core::fmt::Debug). The default format is `namespace::symbol`.
+        ///
+        /// Namespaces can include extra context, like `html::attr(class, global)`.
+        ///
+        fn fmt_debug(&self, id: u32, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name(id))
+        }
+
+        ///
+        /// Look up the `id` of a symbol by its name, used by [Symbol::resolve_static](crate::Symbol::resolve_static)
+        /// and for resolving names in the [registry](crate::registry). The default implementation doesn't recognize any names.
+        ///
+        /// Namespaces generated by the `macros` or `codegen` features implement this method using a `match`
+        /// or a sorted lookup table, respectively.
+        ///
+        fn symbol_by_name(&self, name: &str) -> Option<u32> {
+            let _ = name;
+            None
+        }
+
+        ///
+        /// Typed metadata associated with a symbol, used by [Symbol::metadata](crate::Symbol::metadata).
+        /// If the namespace has metadata of the type identified by `type_id`, it should return a reference to it.
+        /// The returned value must be of that exact type, or it will be discarded.
+        ///
+        fn metadata(&self, id: u32, type_id: core::any::TypeId) -> Option<&dyn core::any::Any> {
+            let _ = (id, type_id);
+            None
+        }
+
+        ///
+        /// Human readable metadata of a symbol, used by [Symbol::describe](crate::Symbol::describe).
+        /// The namespace calls `entry` once for each key/value pair it wants to show.
+        ///
+        fn describe_metadata(&self, id: u32, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+            let _ = (id, entry);
+        }
+
+        ///
+        /// The kind of a symbol, used by [Symbol::kind](crate::Symbol::kind) to group symbols across namespaces.
+        ///
+        fn symbol_kind(&self, id: u32) -> Option<crate::kind::SymbolKind> {
+            let _ = id;
+            None
+        }
+
+        ///
+        /// Provide the trait object interfaces a symbol implements, used by [Symbol::as_trait](crate::Symbol::as_trait).
+        /// See the [interface](crate::interface) module.
+        ///
+        fn query_interface<'a>(&'a self, id: u32, query: &mut crate::interface::Query<'a>) {
+            let _ = (id, query);
+        }
+
+        ///
+        /// The `id` of the parent of a symbol in a hierarchy of symbols, used by [Symbol::parent](crate::Symbol::parent).
+        /// The parent must be in the same namespace, and the hierarchy must not contain cycles.
+        ///
+        fn parent(&self, id: u32) -> Option<u32> {
+            let _ = id;
+            None
+        }
+
+        ///
+        /// The segments of a symbol's path in the hierarchy, from the root down to the symbol,
+        /// used by [Symbol::path_segments](crate::Symbol::path_segments). Returning `None` uses the names of
+        /// the symbol's ancestors.
+        ///
+        fn path_segments(&self, id: u32) -> Option<alloc::vec::Vec<&str>> {
+            let _ = id;
+            None
+        }
+
+        ///
+        /// The serialized representation of a symbol, used by the `serde` implementation of [Symbol](crate::Symbol).
+        /// Namespaces opt in to serialization by returning `Some`, and must also register a
+        /// deserializer for the same namespace name using [register_deserializer](crate::serialization::register_deserializer).
+        ///
+        #[cfg(feature = "serde")]
+        fn serialize_symbol(&self, id: u32) -> Option<alloc::borrow::Cow<'_, str>> {
+            let _ = id;
+            None
+        }
+    }
+
+    ///
+    /// A static namespace with `u64` symbol ids, used by [Symbol::StaticWide](crate::Symbol::StaticWide).
+    ///
+    /// The namespace is free to interpret the id, e.g. as a composite of a table id and a column id
+    /// (see [pack](crate::wide::pack)). The methods mean the same as those of [Static].
+    ///
+    pub trait StaticWide: Send + Sync + Downcast {
+        ///
+        /// The namespace's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn namespace_name(&self) -> &str;
+
+        ///
+        /// A symbol's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn symbol_name(&self, id: u64) -> &str;
+
+        ///
+        /// A globally unique identifier for this namespace. See [Static::namespace_uid].
+        ///
+        fn namespace_uid(&self) -> Option<u128> {
+            None
+        }
+
+        ///
+        /// Whether symbols are hashed by their namespace and symbol names only. See [Static::hash_by_name].
+        ///
+        fn hash_by_name(&self) -> bool {
+            false
+        }
+
+        ///
+        /// Format a symbol for [Display](core::fmt::Display). The default format is `namespace::symbol`.
+        ///
+        fn fmt_display(&self, id: u64, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name(id))
+        }
+
+        ///
+        /// Format a symbol for [Debug](core::fmt::Debug). The default format is `namespace::symbol`.
+        ///
+        fn fmt_debug(&self, id: u64, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name(id))
+        }
+
+        ///
+        /// Look up the `id` of a symbol by its name. See [Static::symbol_by_name].
+        ///
+        fn symbol_by_name(&self, name: &str) -> Option<u64> {
+            let _ = name;
+            None
+        }
+
+        ///
+        /// Typed metadata associated with a symbol. See [Static::metadata].
+        ///
+        fn metadata(&self, id: u64, type_id: core::any::TypeId) -> Option<&dyn core::any::Any> {
+            let _ = (id, type_id);
+            None
+        }
+
+        ///
+        /// Human readable metadata of a symbol. See [Static::describe_metadata].
+        ///
+        fn describe_metadata(&self, id: u64, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+            let _ = (id, entry);
+        }
+
+        ///
+        /// The kind of a symbol. See [Static::symbol_kind].
+        ///
+        fn symbol_kind(&self, id: u64) -> Option<crate::kind::SymbolKind> {
+            let _ = id;
+            None
+        }
+
+        ///
+        /// Provide the trait object interfaces a symbol implements. See [Static::query_interface].
+        ///
+        fn query_interface<'a>(&'a self, id: u64, query: &mut crate::interface::Query<'a>) {
+            let _ = (id, query);
+        }
+
+        ///
+        /// The `id` of the parent of a symbol. See [Static::parent].
+        ///
+        fn parent(&self, id: u64) -> Option<u64> {
+            let _ = id;
+            None
+        }
+
+        ///
+        /// The segments of a symbol's path in the hierarchy. See [Static::path_segments].
+        ///
+        fn path_segments(&self, id: u64) -> Option<alloc::vec::Vec<&str>> {
+            let _ = id;
+            None
+        }
+
+        ///
+        /// The serialized representation of a symbol. See [Static::serialize_symbol].
+        ///
+        #[cfg(feature = "serde")]
+        fn serialize_symbol(&self, id: u64) -> Option<alloc::borrow::Cow<'_, str>> {
+            let _ = id;
+            None
+        }
+    }
+
+    ///
+    /// A dynamic namespace. A dynamic symbol instance is tied to `Self`.
+    ///
+    pub trait Dynamic: Send + Sync + DowncastSync {
+        ///
+        /// The namespace's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn namespace_name(&self) -> &str;
+
+        ///
+        /// The symbol name, used for [Debug][core::fmt::Debug].
+        ///
+        fn symbol_name(&self) -> &str;
+
+        ///
+        /// The symbol name in normalized form, for namespaces where differently spelled names are the same symbol,
+        /// like ASCII case-insensitive names. Defaults to [symbol_name](Self::symbol_name).
+        ///
+        /// The [normalize](crate::normalize) module has `dyn_eq`, `dyn_cmp` and `dyn_hash` implementations based on it.
+        ///
+        fn normalized_name(&self) -> alloc::borrow::Cow<'_, str> {
+            self.symbol_name().into()
+        }
+
+        ///
+        /// A globally unique identifier for this namespace. See [Static::namespace_uid].
+        ///
+        /// Instances of different types cannot be passed to [dyn_eq](Self::dyn_eq) and related methods,
+        /// so symbols from namespaces with a uid are compared and hashed by their [normalized_name](Self::normalized_name) instead.
+        ///
+        fn namespace_uid(&self) -> Option<u128> {
+            None
+        }
+
+        ///
+        /// Whether symbols are hashed by their names only, bypassing [dyn_hash](Self::dyn_hash). See [Static::hash_by_name].
+        ///
+        /// Must return the same for all instances of the namespace, and symbols that are `eq` must have equal names.
+        ///
+        fn hash_by_name(&self) -> bool {
+            false
+        }
+
+        ///
+        /// Format this symbol for [Display](core::fmt::Display). The default format is `namespace::symbol`.
+        ///
+        fn fmt_display(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name())
+        }
+
+        ///
+        /// Format this symbol for [Debug](core::fmt::Debug). See [Static::fmt_debug].
+        ///
+        fn fmt_debug(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name())
+        }
+
+        ///
+        /// Typed metadata associated with this symbol. See [Static::metadata].
+        ///
+        fn metadata(&self, type_id: core::any::TypeId) -> Option<&dyn core::any::Any> {
+            let _ = type_id;
+            None
+        }
+
+        ///
+        /// Human readable metadata of this symbol. See [Static::describe_metadata].
+        ///
+        fn describe_metadata(&self, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+            let _ = entry;
+        }
+
+        ///
+        /// The kind of this symbol. See [Static::symbol_kind].
+        ///
+        fn symbol_kind(&self) -> Option<crate::kind::SymbolKind> {
+            None
+        }
+
+        ///
+        /// Provide the trait object interfaces this symbol implements. See [Static::query_interface].
+        ///
+        fn query_interface<'a>(&'a self, query: &mut crate::interface::Query<'a>) {
+            let _ = query;
+        }
+
+        ///
+        /// The parent of this symbol in a hierarchy of symbols, used by [Symbol::parent](crate::Symbol::parent).
+        /// The parent can be in any namespace, but the hierarchy must not contain cycles.
+        ///
+        fn parent(&self) -> Option<crate::Symbol> {
+            None
+        }
+
+        ///
+        /// The segments of this symbol's path in the hierarchy. See [Static::path_segments].
+        ///
+        fn path_segments(&self) -> Option<alloc::vec::Vec<&str>> {
+            None
+        }
+
+        ///
+        /// The serialized representation of this symbol, used by the `serde` implementation of [Symbol](crate::Symbol).
+        /// Namespaces opt in to serialization by returning `Some`, and must also register a
+        /// deserializer for the same namespace name using [register_deserializer](crate::serialization::register_deserializer).
+        ///
+        #[cfg(feature = "serde")]
+        fn serialize_symbol(&self) -> Option<alloc::borrow::Cow<'_, str>> {
+            None
+        }
+
+        ///
+        /// The byte encoding of this symbol, used by the `rkyv` implementation of [Symbol](crate::Symbol).
+        /// Namespaces opt in to archiving by returning `Some`, and must also register a
+        /// decoder for the same namespace name using [register_decoder](crate::archive::register_decoder).
+        ///
+        #[cfg(feature = "rkyv")]
+        fn archive_bytes(&self) -> Option<alloc::borrow::Cow<'_, [u8]>> {
+            None
+        }
+
+        ///
+        /// Clone this dynamic symbol into a new, uniquely owned instance. Must return a new symbol instance that is `eq` to `&self`.
+        ///
+        /// Note that cloning a [Symbol](crate::Symbol) does not call this method, it only increments a reference count.
+        /// It is called by [Symbol::downcast_dyn_mut](crate::Symbol::downcast_dyn_mut) and [Symbol::into_dynamic](crate::Symbol::into_dynamic)
+        /// to copy a shared instance, and those expect the copy to be of the same type as `self`.
+        ///
+        fn dyn_clone(&self) -> Box<dyn Dynamic>;
+
+        ///
+        /// Dynamic [eq](core::cmp::PartialEq::eq). `rhs` can be unconditionally downcasted to `Self`.
+        ///
+        fn dyn_eq(&self, rhs: &dyn Dynamic) -> bool;
+
+        ///
+        /// Dynamic [cmp](core::cmp::Ord::cmp). `rhs` can be unconditionally downcasted to `Self`.
+        ///
+        fn dyn_cmp(&self, rhs: &dyn Dynamic) -> core::cmp::Ordering;
+
+        ///
+        /// Dynamic [hash](core::hash::Hash::hash). `rhs` can be unconditionally downcasted to `Self`.
+        ///
+        fn dyn_hash(&self, state: &mut dyn core::hash::Hasher);
+
+        ///
+        /// A static symbol that this dynamic symbol is equal to. Equality, ordering and hashing of the
+        /// [Symbol](crate::Symbol) then behave as if it was that static symbol, so e.g. a dynamically parsed
+        /// `class` attribute can be the same map key as a well-known static `class` symbol.
+        ///
+        /// Symbols that are `dyn_eq` must return the same equivalent.
+        ///
+        fn static_equivalent(&self) -> Option<(&'static dyn Static, u32)> {
+            None
+        }
+
+        ///
+        /// An allocation-free symbol to replace this symbol with, used by [Symbol::canonicalize](crate::Symbol::canonicalize).
+        /// The default implementation returns the [static_equivalent](Self::static_equivalent).
+        ///
+        fn try_to_static(&self) -> Option<crate::Symbol> {
+            self.static_equivalent()
+                .map(|(ns, id)| crate::Symbol::Static(ns, id))
+        }
+
+        ///
+        /// A precomputed hash of this symbol. When this returns `Some`, [Hash](core::hash::Hash) for [Symbol](crate::Symbol)
+        /// feeds the value to the hasher instead of calling [dyn_hash](Self::dyn_hash).
+        ///
+        /// Symbols that are `dyn_eq` must return equal values, and a namespace should either always or never return `Some`.
+        ///
+        fn cached_hash(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    impl_downcast!(sync Dynamic);
+
+    ///
+    /// A plain value type that can be used as a dynamic symbol by wrapping it in [DynWrap].
+    ///
+    /// Equality, ordering and hashing of the symbol are those of the value type itself.
+    ///
+    pub trait DynValue: Clone + Eq + Ord + core::hash::Hash + Send + Sync + 'static {
+        ///
+        /// The namespace's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn namespace_name(&self) -> &str;
+
+        ///
+        /// The symbol name, used for [Debug][core::fmt::Debug].
+        ///
+        fn symbol_name(&self) -> &str;
+
+        ///
+        /// The value's name in normalized form. See [Dynamic::normalized_name].
+        ///
+        fn normalized_name(&self) -> alloc::borrow::Cow<'_, str> {
+            self.symbol_name().into()
+        }
+
+        ///
+        /// A globally unique identifier for this value's namespace. See [Dynamic::namespace_uid].
+        ///
+        fn namespace_uid(&self) -> Option<u128> {
+            None
+        }
+
+        ///
+        /// Whether symbols are hashed by their names only. See [Dynamic::hash_by_name].
+        ///
+        fn hash_by_name(&self) -> bool {
+            false
+        }
+
+        ///
+        /// Format this value for [Display](core::fmt::Display). See [Dynamic::fmt_display].
+        ///
+        fn fmt_display(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name())
+        }
+
+        ///
+        /// Format this value for [Debug](core::fmt::Debug). See [Dynamic::fmt_debug].
+        ///
+        fn fmt_debug(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name())
+        }
+
+        ///
+        /// Typed metadata associated with this value. See [Dynamic::metadata].
+        ///
+        fn metadata(&self, type_id: core::any::TypeId) -> Option<&dyn core::any::Any> {
+            let _ = type_id;
+            None
+        }
+
+        ///
+        /// Human readable metadata of this value. See [Dynamic::describe_metadata].
+        ///
+        fn describe_metadata(&self, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+            let _ = entry;
+        }
+
+        ///
+        /// The kind of this value. See [Dynamic::symbol_kind].
+        ///
+        fn symbol_kind(&self) -> Option<crate::kind::SymbolKind> {
+            None
+        }
+
+        ///
+        /// Provide the trait object interfaces this value implements. See [Dynamic::query_interface].
+        ///
+        fn query_interface<'a>(&'a self, query: &mut crate::interface::Query<'a>) {
+            let _ = query;
+        }
+
+        ///
+        /// The parent of this value in a hierarchy of symbols. See [Dynamic::parent].
+        ///
+        fn parent(&self) -> Option<crate::Symbol> {
+            None
+        }
+
+        ///
+        /// The segments of this value's path in the hierarchy. See [Dynamic::path_segments].
+        ///
+        fn path_segments(&self) -> Option<alloc::vec::Vec<&str>> {
+            None
+        }
+
+        ///
+        /// The serialized representation of this value. See [Dynamic::serialize_symbol].
+        ///
+        #[cfg(feature = "serde")]
+        fn serialize_symbol(&self) -> Option<alloc::borrow::Cow<'_, str>> {
+            None
+        }
+
+        ///
+        /// The byte encoding of this value. See [Dynamic::archive_bytes].
+        ///
+        #[cfg(feature = "rkyv")]
+        fn archive_bytes(&self) -> Option<alloc::borrow::Cow<'_, [u8]>> {
+            None
+        }
+
+        ///
+        /// A static symbol that this value is equal to. See [Dynamic::static_equivalent].
+        ///
+        fn static_equivalent(&self) -> Option<(&'static dyn Static, u32)> {
+            None
+        }
+
+        ///
+        /// An allocation-free symbol to replace this value with. See [Dynamic::try_to_static].
+        ///
+        fn try_to_static(&self) -> Option<crate::Symbol> {
+            self.static_equivalent()
+                .map(|(ns, id)| crate::Symbol::Static(ns, id))
+        }
+
+        ///
+        /// A precomputed hash of this value. See [Dynamic::cached_hash].
+        ///
+        fn cached_hash(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    ///
+    /// A wrapper implementing [Dynamic] for any [DynValue].
+    ///
+    /// ```
+    /// use dyn_symbol::*;
+    ///
+    /// #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    /// struct Data(String);
+    ///
+    /// impl namespace::DynValue for Data {
+    ///     fn namespace_name(&self) -> &str {
+    ///         "data"
+    ///     }
+    ///
+    ///     fn symbol_name(&self) -> &str {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let foo = Symbol::from(namespace::DynWrap(Data("foo".into())));
+    ///
+    /// assert_eq!(foo, foo.clone());
+    /// assert_eq!(format!("{:?}", foo), "data::foo");
+    /// assert_eq!(foo.downcast_dyn::<namespace::DynWrap<Data>>().unwrap().0 .0, "foo");
+    /// ```
+    ///
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+    pub struct DynWrap<T>(pub T);
+
+    impl<T: DynValue> Dynamic for DynWrap<T> {
+        fn namespace_name(&self) -> &str {
+            self.0.namespace_name()
+        }
+
+        fn symbol_name(&self) -> &str {
+            self.0.symbol_name()
+        }
+
+        fn normalized_name(&self) -> alloc::borrow::Cow<'_, str> {
+            self.0.normalized_name()
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            self.0.namespace_uid()
+        }
+
+        fn hash_by_name(&self) -> bool {
+            self.0.hash_by_name()
+        }
+
+        fn fmt_display(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            self.0.fmt_display(f)
+        }
+
+        fn fmt_debug(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            self.0.fmt_debug(f)
+        }
+
+        fn metadata(&self, type_id: core::any::TypeId) -> Option<&dyn core::any::Any> {
+            self.0.metadata(type_id)
+        }
+
+        fn describe_metadata(&self, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+            self.0.describe_metadata(entry)
+        }
+
+        fn symbol_kind(&self) -> Option<crate::kind::SymbolKind> {
+            self.0.symbol_kind()
+        }
+
+        fn query_interface<'a>(&'a self, query: &mut crate::interface::Query<'a>) {
+            self.0.query_interface(query)
+        }
+
+        fn parent(&self) -> Option<crate::Symbol> {
+            self.0.parent()
+        }
+
+        fn path_segments(&self) -> Option<alloc::vec::Vec<&str>> {
+            self.0.path_segments()
+        }
+
+        #[cfg(feature = "serde")]
+        fn serialize_symbol(&self) -> Option<alloc::borrow::Cow<'_, str>> {
+            self.0.serialize_symbol()
+        }
+
+        #[cfg(feature = "rkyv")]
+        fn archive_bytes(&self) -> Option<alloc::borrow::Cow<'_, [u8]>> {
+            self.0.archive_bytes()
+        }
+
+        fn dyn_clone(&self) -> Box<dyn Dynamic> {
+            Box::new(self.clone())
+        }
+
+        fn dyn_eq(&self, rhs: &dyn Dynamic) -> bool {
+            self.0 == rhs.downcast_ref::<Self>().unwrap().0
+        }
+
+        fn dyn_cmp(&self, rhs: &dyn Dynamic) -> core::cmp::Ordering {
+            self.0.cmp(&rhs.downcast_ref::<Self>().unwrap().0)
+        }
+
+        fn dyn_hash(&self, mut state: &mut dyn core::hash::Hasher) {
+            core::hash::Hash::hash(&self.0, &mut state)
+        }
+
+        fn static_equivalent(&self) -> Option<(&'static dyn Static, u32)> {
+            self.0.static_equivalent()
+        }
+
+        fn try_to_static(&self) -> Option<crate::Symbol> {
+            self.0.try_to_static()
+        }
+
+        fn cached_hash(&self) -> Option<u64> {
+            self.0.cached_hash()
+        }
+    }
+
+    ///
+    /// A dynamic namespace whose symbol instances don't need to be `Send` or `Sync`, used by [LocalSymbol](crate::local::LocalSymbol).
+    ///
+    /// The methods mean the same as those of [Dynamic], and `rhs` can be unconditionally downcasted to `Self`.
+    ///
+    pub trait LocalDynamic: Downcast {
+        ///
+        /// The namespace's name, used for [Debug][core::fmt::Debug].
+        ///
+        fn namespace_name(&self) -> &str;
 
         ///
-        /// Clone this dynamic symbol. Must return a new symbol instance that is `eq` to `&self`.
+        /// The symbol name, used for [Debug][core::fmt::Debug].
         ///
-        fn dyn_clone(&self) -> Box<dyn Dynamic>;
+        fn symbol_name(&self) -> &str;
 
         ///
-        /// Dynamic [eq](std::cmp::PartialEq::eq). `rhs` can be unconditionally downcasted to `Self`.
+        /// Format this symbol for [Display](core::fmt::Display). See [Dynamic::fmt_display].
         ///
-        fn dyn_eq(&self, rhs: &dyn Dynamic) -> bool;
+        fn fmt_display(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name())
+        }
+
+        ///
+        /// Format this symbol for [Debug](core::fmt::Debug). See [Dynamic::fmt_debug].
+        ///
+        fn fmt_debug(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{}::{}", self.namespace_name(), self.symbol_name())
+        }
+
+        ///
+        /// A thread-safe copy of this symbol, used by [LocalSymbol::to_symbol](crate::local::LocalSymbol::to_symbol).
+        /// Namespaces whose payload can be shared between threads return `Some`.
+        ///
+        fn to_dynamic(&self) -> Option<Box<dyn Dynamic>> {
+            None
+        }
+
+        ///
+        /// Dynamic [eq](core::cmp::PartialEq::eq). See [Dynamic::dyn_eq].
+        ///
+        fn dyn_eq(&self, rhs: &dyn LocalDynamic) -> bool;
 
         ///
-        /// Dynamic [cmp](std::cmp::Ord::cmp). `rhs` can be unconditionally downcasted to `Self`.
+        /// Dynamic [cmp](core::cmp::Ord::cmp). See [Dynamic::dyn_cmp].
         ///
-        fn dyn_cmp(&self, rhs: &dyn Dynamic) -> std::cmp::Ordering;
+        fn dyn_cmp(&self, rhs: &dyn LocalDynamic) -> core::cmp::Ordering;
 
         ///
-        /// Dynamic [hash](std::hash::Hash::hash). `rhs` can be unconditionally downcasted to `Self`.
+        /// Dynamic [hash](core::hash::Hash::hash). See [Dynamic::dyn_hash].
         ///
-        fn dyn_hash(&self, state: &mut dyn std::hash::Hasher);
+        fn dyn_hash(&self, state: &mut dyn core::hash::Hasher);
     }
 
-    impl_downcast!(Dynamic);
+    impl_downcast!(LocalDynamic);
+}
+
+impl<T: namespace::DynValue> From<namespace::DynWrap<T>> for Symbol {
+    fn from(wrap: namespace::DynWrap<T>) -> Self {
+        Self::Dynamic(Arc::new(wrap))
+    }
 }
 
 #[cfg(test)]
@@ -380,7 +1877,7 @@ mod tests {
     use super::*;
     use std::hash::{BuildHasher, Hash, Hasher};
 
-    mod _static {
+    pub(crate) mod _static {
         use super::*;
 
         pub struct ClassN<const N: u8> {
@@ -393,9 +1890,20 @@ mod tests {
                 self.class_name
             }
 
+            fn symbol_count(&self) -> u32 {
+                self.names.len() as u32
+            }
+
             fn symbol_name(&self, id: u32) -> &str {
                 self.names[id as usize]
             }
+
+            fn symbol_by_name(&self, name: &str) -> Option<u32> {
+                self.names
+                    .iter()
+                    .position(|n| *n == name)
+                    .map(|id| id as u32)
+            }
         }
 
         pub const STATIC_NS_CLASS_A: ClassN<1> = ClassN {
@@ -408,10 +1916,10 @@ mod tests {
         };
     }
 
-    mod dynamic {
+    pub(crate) mod dynamic {
         use super::*;
 
-        pub struct TestDynamic<const N: u8>(pub String, &'static str);
+        pub struct TestDynamic<const N: u8>(pub String, pub &'static str);
 
         impl<const N: u8> namespace::Dynamic for TestDynamic<N> {
             fn namespace_name(&self) -> &str {
@@ -441,17 +1949,69 @@ mod tests {
         }
 
         pub fn sym0(str: &str) -> Symbol {
-            Symbol::Dynamic(Box::new(TestDynamic::<0>(str.into(), "dyn0")))
+            Symbol::Dynamic(Arc::new(TestDynamic::<0>(str.into(), "dyn0")))
         }
 
         pub fn sym1(str: &str) -> Symbol {
-            Symbol::Dynamic(Box::new(TestDynamic::<1>(str.into(), "dyn1")))
+            Symbol::Dynamic(Arc::new(TestDynamic::<1>(str.into(), "dyn1")))
+        }
+
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Wrapped(pub String);
+
+        impl namespace::DynValue for Wrapped {
+            fn namespace_name(&self) -> &str {
+                "wrapped"
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.0
+            }
+
+            fn fmt_display(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "wrapped:{}", self.0)
+            }
+
+            fn metadata(&self, type_id: std::any::TypeId) -> Option<&dyn std::any::Any> {
+                if type_id == std::any::TypeId::of::<String>() {
+                    Some(&self.0)
+                } else {
+                    None
+                }
+            }
+        }
+
+        pub fn wrapped(str: &str) -> Symbol {
+            Symbol::from(namespace::DynWrap(Wrapped(str.into())))
+        }
+
+        /// A dynamic version of the `A` namespace, equivalent to its static symbols.
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct DynamicA(pub String);
+
+        impl namespace::DynValue for DynamicA {
+            fn namespace_name(&self) -> &str {
+                "A"
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.0
+            }
+
+            fn static_equivalent(&self) -> Option<(&'static dyn namespace::Static, u32)> {
+                let ns = &_static::STATIC_NS_CLASS_A;
+                namespace::Static::symbol_by_name(ns, &self.0).map(|id| (ns as _, id))
+            }
+        }
+
+        pub fn dynamic_a(str: &str) -> Symbol {
+            Symbol::from(namespace::DynWrap(DynamicA(str.into())))
         }
     }
 
-    const STATIC_A_0: Symbol = Symbol::Static(&_static::STATIC_NS_CLASS_A, 0);
-    const STATIC_A_1: Symbol = Symbol::Static(&_static::STATIC_NS_CLASS_A, 1);
-    const STATIC_B_0: Symbol = Symbol::Static(&_static::STATIC_NS_CLASS_B, 0);
+    pub(crate) const STATIC_A_0: Symbol = Symbol::Static(&_static::STATIC_NS_CLASS_A, 0);
+    pub(crate) const STATIC_A_1: Symbol = Symbol::Static(&_static::STATIC_NS_CLASS_A, 1);
+    pub(crate) const STATIC_B_0: Symbol = Symbol::Static(&_static::STATIC_NS_CLASS_B, 0);
 
     struct TestState {
         random_state: std::collections::hash_map::RandomState,
@@ -508,6 +2068,7 @@ mod tests {
         };
 
         assert_eq!(std::mem::size_of::<Symbol>(), expected_word_size);
+        assert_eq!(std::mem::size_of::<Option<Symbol>>(), expected_word_size);
     }
 
     #[test]
@@ -520,6 +2081,93 @@ mod tests {
         assert_eq!(format!("{:?}", dynamic::sym1("bar")), "dyn1::bar");
     }
 
+    #[test]
+    fn test_fmt_debug() {
+        struct Html;
+
+        impl namespace::Static for Html {
+            fn namespace_name(&self) -> &str {
+                "html"
+            }
+
+            fn symbol_count(&self) -> u32 {
+                2
+            }
+
+            fn symbol_name(&self, id: u32) -> &str {
+                ["class", "href"][id as usize]
+            }
+
+            fn fmt_debug(&self, id: u32, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                let scope = ["global", "a"][id as usize];
+                write!(f, "html::attr({}, {})", self.symbol_name(id), scope)
+            }
+        }
+
+        static HREF: indexed::IndexedBase = indexed::IndexedBase::new(&Html, 1);
+
+        assert_eq!(
+            format!("{:?}", Symbol::Static(&Html, 0)),
+            "html::attr(class, global)"
+        );
+        assert_eq!(format!("{}", Symbol::Static(&Html, 0)), "html::class");
+        assert_eq!(format!("{:?}", HREF.index(2)), "html::attr(href, a)[2]");
+        assert_eq!(
+            format!("{:?}", Symbol::Static(&Html, 1).as_symbol_ref()),
+            "html::attr(href, a)"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", STATIC_A_0), "A::0");
+        assert_eq!(format!("{}", dynamic::sym0("foo")), "dyn0::foo");
+        assert_eq!(format!("{}", dynamic::wrapped("foo")), "wrapped:foo");
+        assert_eq!(format!("{:?}", dynamic::wrapped("foo")), "wrapped::foo");
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(STATIC_A_1.namespace(), "A");
+        assert_eq!(STATIC_A_1.name(), "1");
+        assert_eq!(dynamic::sym1("bar").namespace(), "dyn1");
+        assert_eq!(dynamic::sym1("bar").name(), "bar");
+    }
+
+    #[test]
+    fn test_resolve_static() {
+        assert_eq!(
+            Symbol::resolve_static(&_static::STATIC_NS_CLASS_A, "1"),
+            Some(STATIC_A_1)
+        );
+        assert_eq!(
+            Symbol::resolve_static(&_static::STATIC_NS_CLASS_B, "1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_iter_static() {
+        assert_eq!(
+            Symbol::iter_static(&_static::STATIC_NS_CLASS_A).collect::<Vec<_>>(),
+            vec![STATIC_A_0, STATIC_A_1]
+        );
+        assert_eq!(Symbol::iter_static(&_static::STATIC_NS_CLASS_B).count(), 1);
+    }
+
+    #[test]
+    fn test_metadata() {
+        assert_eq!(
+            dynamic::wrapped("foo")
+                .metadata::<String>()
+                .map(String::as_str),
+            Some("foo")
+        );
+        assert!(dynamic::wrapped("foo").metadata::<u32>().is_none());
+        assert!(dynamic::sym0("foo").metadata::<String>().is_none());
+        assert!(STATIC_A_0.metadata::<String>().is_none());
+    }
+
     #[test]
     fn test_equality() {
         let test_state = TestState::new();
@@ -534,6 +2182,92 @@ mod tests {
         test_state.assert_full_eq(&dynamic::sym0("foo"), &dynamic::sym0("foo"));
     }
 
+    #[test]
+    fn test_namespace_helpers() {
+        use _static::ClassN;
+        use dynamic::TestDynamic;
+
+        static INLINE: inline::InlineNamespace = inline::InlineNamespace::new("inline");
+        let long = INLINE.symbol("a name that is too long to be stored inline");
+
+        assert!(STATIC_A_0.is::<ClassN<1>>());
+        assert!(!STATIC_A_0.is::<ClassN<2>>());
+        assert!(dynamic::sym0("foo").is::<TestDynamic<0>>());
+        assert!(long.is::<inline::InlineNamespace>());
+        assert_eq!(
+            STATIC_B_0.namespace_type_id(),
+            core::any::TypeId::of::<ClassN<2>>()
+        );
+
+        assert!(STATIC_A_0.same_namespace(&STATIC_A_1));
+        assert!(!STATIC_A_0.same_namespace(&STATIC_B_0));
+        assert!(dynamic::sym0("foo").same_namespace(&dynamic::sym0("bar")));
+        assert!(!dynamic::sym0("foo").same_namespace(&dynamic::sym1("foo")));
+        assert!(INLINE.symbol("short").same_namespace(&long));
+        // Equal to STATIC_A_1, but created in another namespace:
+        assert!(!dynamic::dynamic_a("1").same_namespace(&STATIC_A_1));
+    }
+
+    #[test]
+    fn test_pointer_identity() {
+        struct Zst<const N: u8>;
+
+        impl<const N: u8> namespace::Static for Zst<N> {
+            fn namespace_name(&self) -> &str {
+                "zst"
+            }
+
+            fn symbol_count(&self) -> u32 {
+                1
+            }
+
+            fn symbol_name(&self, _: u32) -> &str {
+                "0"
+            }
+        }
+
+        static ZST_1: Zst<1> = Zst;
+        static ZST_2: Zst<2> = Zst;
+
+        // Zero-sized namespaces may share an address, but are not the same namespace:
+        assert_ne!(Symbol::Static(&ZST_1, 0), Symbol::Static(&ZST_2, 0));
+        assert_eq!(Symbol::Static(&ZST_1, 0), Symbol::Static(&ZST_1, 0));
+        assert_ne!(
+            Symbol::Static(&ZST_1, 0).cmp(&Symbol::Static(&ZST_2, 0)),
+            Ordering::Equal
+        );
+
+        struct Opaque;
+
+        impl namespace::Dynamic for Opaque {
+            fn namespace_name(&self) -> &str {
+                "opaque"
+            }
+
+            fn symbol_name(&self) -> &str {
+                "opaque"
+            }
+
+            fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+                Box::new(Opaque)
+            }
+
+            fn dyn_eq(&self, _: &dyn namespace::Dynamic) -> bool {
+                unreachable!("compared by identity")
+            }
+
+            fn dyn_cmp(&self, _: &dyn namespace::Dynamic) -> Ordering {
+                unreachable!("compared by identity")
+            }
+
+            fn dyn_hash(&self, _: &mut dyn std::hash::Hasher) {}
+        }
+
+        let opaque = Symbol::Dynamic(Arc::new(Opaque));
+        assert_eq!(opaque, opaque.clone());
+        assert_eq!(opaque.cmp(&opaque.clone()), Ordering::Equal);
+    }
+
     #[test]
     fn test_inequality() {
         let test_state = TestState::new();
@@ -545,10 +2279,389 @@ mod tests {
         test_state.assert_full_ne(&dynamic::sym0("foo"), &dynamic::sym1("foo"));
     }
 
+    #[test]
+    fn test_dynamic_clone_is_shared() {
+        let foo = dynamic::sym0("foo");
+        match (&foo, &foo.clone()) {
+            (Symbol::Dynamic(a), Symbol::Dynamic(b)) => assert!(Arc::ptr_eq(a, b)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_downcast_dyn_mut() {
+        type Wrap = namespace::DynWrap<dynamic::Wrapped>;
+
+        let mut foo = dynamic::wrapped("foo");
+        let shared = foo.clone();
+
+        // Shared, so the instance gets copied:
+        foo.downcast_dyn_mut::<Wrap>().unwrap().0 .0 = "bar".into();
+        assert_eq!(foo, dynamic::wrapped("bar"));
+        assert_eq!(shared, dynamic::wrapped("foo"));
+
+        // Unique, so the instance is mutated in place:
+        let before = foo.downcast_dyn::<Wrap>().unwrap() as *const Wrap;
+        foo.downcast_dyn_mut::<Wrap>().unwrap().0 .0 = "baz".into();
+        assert_eq!(foo.downcast_dyn::<Wrap>().unwrap() as *const Wrap, before);
+        assert_eq!(foo, dynamic::wrapped("baz"));
+
+        assert!(foo.downcast_dyn_mut::<String>().is_none());
+        assert!(STATIC_A_0.clone().downcast_dyn_mut::<String>().is_none());
+    }
+
+    #[test]
+    fn test_into_dynamic() {
+        type Wrap = namespace::DynWrap<dynamic::Wrapped>;
+
+        // Unique, so the instance is moved out without copying its name:
+        let foo = dynamic::wrapped("foo");
+        let before = foo.downcast_dyn::<Wrap>().unwrap().0 .0.as_ptr();
+        let boxed = foo.into_boxed_dynamic::<Wrap>().unwrap();
+        assert_eq!(boxed.0 .0.as_ptr(), before);
+
+        // Shared, so the caller gets a copy:
+        let foo = dynamic::wrapped("foo");
+        let shared = foo.clone();
+        assert_eq!(foo.into_dynamic::<Wrap>().unwrap().0 .0, "foo");
+        assert_eq!(shared, dynamic::wrapped("foo"));
+
+        assert!(
+            matches!(shared.clone().into_dynamic::<dynamic::TestDynamic<0>>(), Err(s) if s == shared)
+        );
+        assert!(
+            matches!(STATIC_A_0.into_dynamic::<dynamic::TestDynamic<0>>(), Err(s) if s == STATIC_A_0)
+        );
+    }
+
+    #[test]
+    fn test_dyn_wrap() {
+        let test_state = TestState::new();
+
+        test_state.assert_full_eq(&dynamic::wrapped("foo"), &dynamic::wrapped("foo"));
+        test_state.assert_full_eq(&dynamic::wrapped("foo"), &dynamic::wrapped("foo").clone());
+        test_state.assert_full_ne(&dynamic::wrapped("foo"), &dynamic::wrapped("bar"));
+        test_state.assert_full_ne(&dynamic::wrapped("foo"), &dynamic::sym0("foo"));
+
+        assert_eq!(format!("{:?}", dynamic::wrapped("foo")), "wrapped::foo");
+        assert_eq!(
+            dynamic::wrapped("bar").cmp(&dynamic::wrapped("foo")),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_cached_hash() {
+        struct Cached(u64);
+
+        impl namespace::Dynamic for Cached {
+            fn namespace_name(&self) -> &str {
+                "cached"
+            }
+
+            fn symbol_name(&self) -> &str {
+                "cached"
+            }
+
+            fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+                Box::new(Cached(self.0))
+            }
+
+            fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+                self.0 == rhs.downcast_ref::<Cached>().unwrap().0
+            }
+
+            fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> std::cmp::Ordering {
+                self.0.cmp(&rhs.downcast_ref::<Cached>().unwrap().0)
+            }
+
+            fn dyn_hash(&self, _: &mut dyn std::hash::Hasher) {
+                panic!("the cached hash should be used")
+            }
+
+            fn cached_hash(&self) -> Option<u64> {
+                Some(self.0)
+            }
+        }
+
+        let test_state = TestState::new();
+        let cached = |hash| Symbol::Dynamic(Arc::new(Cached(hash)));
+
+        test_state.assert_full_eq(&cached(1), &cached(1));
+        test_state.assert_full_ne(&cached(1), &cached(2));
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        struct Paths;
+
+        impl namespace::Static for Paths {
+            fn namespace_name(&self) -> &str {
+                "paths"
+            }
+
+            fn symbol_count(&self) -> u32 {
+                3
+            }
+
+            fn symbol_name(&self, id: u32) -> &str {
+                ["data", "data::user", "data::user::id"][id as usize]
+            }
+
+            fn parent(&self, id: u32) -> Option<u32> {
+                id.checked_sub(1)
+            }
+
+            fn path_segments(&self, id: u32) -> Option<Vec<&str>> {
+                Some(self.symbol_name(id).split("::").collect())
+            }
+        }
+
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Path(String);
+
+        impl namespace::DynValue for Path {
+            fn namespace_name(&self) -> &str {
+                "dyn_paths"
+            }
+
+            fn symbol_name(&self) -> &str {
+                &self.0
+            }
+
+            fn parent(&self) -> Option<Symbol> {
+                match self.0.rsplit_once("::") {
+                    Some((parent, _)) => {
+                        Some(Symbol::from(namespace::DynWrap(Path(parent.into()))))
+                    }
+                    None => Some(Symbol::Static(&Paths, 2)),
+                }
+            }
+        }
+
+        let id = Symbol::Static(&Paths, 2);
+        assert_eq!(id.parent(), Some(Symbol::Static(&Paths, 1)));
+        assert_eq!(Symbol::Static(&Paths, 0).parent(), None);
+        assert_eq!(id.ancestors().count(), 2);
+        assert!(id.is_descendant_of(&Symbol::Static(&Paths, 0)));
+        assert!(!id.is_descendant_of(&id));
+        assert!(!Symbol::Static(&Paths, 0).is_descendant_of(&id));
+
+        let email = Symbol::from(namespace::DynWrap(Path("contact::email".into())));
+        let names: Vec<_> = email.ancestors().map(|symbol| symbol.to_string()).collect();
+        assert_eq!(
+            names,
+            [
+                "dyn_paths::contact",
+                "paths::data::user::id",
+                "paths::data::user",
+                "paths::data"
+            ]
+        );
+        assert!(email.is_descendant_of(&Symbol::Static(&Paths, 1)));
+        assert_eq!(STATIC_A_0.parent(), None);
+
+        assert_eq!(id.path_segments(), ["data", "user", "id"]);
+        // Built from the ancestors:
+        assert_eq!(
+            email.path_segments(),
+            [
+                "data",
+                "data::user",
+                "data::user::id",
+                "contact",
+                "contact::email"
+            ]
+        );
+        assert_eq!(STATIC_A_0.path_segments(), ["0"]);
+
+        static ID: indexed::IndexedBase = indexed::IndexedBase::new(&Paths, 2);
+        assert_eq!(ID.index(3).parent(), Some(Symbol::Static(&Paths, 1)));
+        assert_eq!(ID.index(3).path_segments(), ["data", "user", "id"]);
+    }
+
+    #[test]
+    fn test_partial_eq_str() {
+        assert_eq!(STATIC_A_0, "A::0");
+        assert_ne!(STATIC_A_0, "A::1");
+        assert_ne!(STATIC_A_0, "A:0");
+        assert_ne!(STATIC_A_0, "A");
+        assert!(*"dyn0::foo" == dynamic::sym0("foo"));
+    }
+
+    #[test]
+    fn test_hash_by_name() {
+        static NS: inline::InlineNamespace = inline::InlineNamespace::new("ns");
+
+        let test_state = TestState::new();
+
+        let short = NS.symbol("foo");
+        let long = NS.symbol("not inlined, too long");
+        let random_state = &test_state.random_state;
+
+        assert_eq!(
+            random_state.hash_one(&short),
+            random_state.hash_one(("ns", "foo"))
+        );
+        assert_eq!(
+            random_state.hash_one(&long),
+            random_state.hash_one(("ns", "not inlined, too long"))
+        );
+        test_state.assert_full_eq(&long, &NS.symbol("not inlined, too long"));
+        test_state.assert_full_ne(&long, &short);
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        fn stable_hash(symbol: &Symbol) -> u64 {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            symbol.stable_hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut expected = std::collections::hash_map::DefaultHasher::new();
+        expected.write(b"A\xff1\xff");
+
+        assert_eq!(stable_hash(&STATIC_A_1), expected.finish());
+        assert_eq!(stable_hash(&STATIC_A_0), stable_hash(&STATIC_A_0));
+        assert_ne!(stable_hash(&STATIC_A_0), stable_hash(&STATIC_A_1));
+        assert_ne!(stable_hash(&STATIC_A_0), stable_hash(&STATIC_B_0));
+        assert_eq!(
+            stable_hash(&dynamic::sym0("foo")),
+            stable_hash(&dynamic::sym0("foo"))
+        );
+    }
+
+    #[test]
+    fn test_namespace_uid() {
+        struct Versioned<const N: u8>;
+
+        impl<const N: u8> namespace::Static for Versioned<N> {
+            fn namespace_name(&self) -> &str {
+                "versioned"
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(0x5eed)
+            }
+
+            fn symbol_count(&self) -> u32 {
+                2
+            }
+
+            fn symbol_name(&self, id: u32) -> &str {
+                ["foo", "bar"][id as usize]
+            }
+        }
+
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct VersionedValue<const N: u8>(&'static str);
+
+        impl<const N: u8> namespace::DynValue for VersionedValue<N> {
+            fn namespace_name(&self) -> &str {
+                "versioned_dyn"
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(0xd00d)
+            }
+
+            fn symbol_name(&self) -> &str {
+                self.0
+            }
+        }
+
+        let test_state = TestState::new();
+
+        test_state.assert_full_eq(
+            &Symbol::Static(&Versioned::<1>, 0),
+            &Symbol::Static(&Versioned::<2>, 0),
+        );
+        test_state.assert_full_ne(
+            &Symbol::Static(&Versioned::<1>, 0),
+            &Symbol::Static(&Versioned::<2>, 1),
+        );
+        test_state.assert_full_ne(&Symbol::Static(&Versioned::<1>, 0), &STATIC_A_0);
+
+        let versioned_dyn = |n, name| match n {
+            1 => Symbol::from(namespace::DynWrap(VersionedValue::<1>(name))),
+            _ => Symbol::from(namespace::DynWrap(VersionedValue::<2>(name))),
+        };
+
+        test_state.assert_full_eq(&versioned_dyn(1, "foo"), &versioned_dyn(2, "foo"));
+        test_state.assert_full_ne(&versioned_dyn(1, "foo"), &versioned_dyn(2, "bar"));
+        test_state.assert_full_ne(&versioned_dyn(1, "foo"), &dynamic::wrapped("foo"));
+    }
+
+    #[test]
+    fn test_static_equivalent() {
+        let test_state = TestState::new();
+
+        test_state.assert_full_eq(&dynamic::dynamic_a("1"), &STATIC_A_1);
+        test_state.assert_full_eq(&STATIC_A_0, &dynamic::dynamic_a("0"));
+        test_state.assert_full_eq(&dynamic::dynamic_a("1"), &dynamic::dynamic_a("1"));
+        test_state.assert_full_ne(&dynamic::dynamic_a("0"), &STATIC_A_1);
+        test_state.assert_full_ne(&dynamic::dynamic_a("0"), &dynamic::dynamic_a("1"));
+        test_state.assert_full_ne(&dynamic::dynamic_a("2"), &dynamic::dynamic_a("1"));
+        test_state.assert_full_eq(&dynamic::dynamic_a("2"), &dynamic::dynamic_a("2"));
+
+        assert_eq!(dynamic::dynamic_a("2").cmp(&STATIC_B_0), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        assert!(matches!(
+            dynamic::dynamic_a("1").canonicalize(),
+            Symbol::Static(_, 1)
+        ));
+        assert!(matches!(
+            dynamic::dynamic_a("2").canonicalize(),
+            Symbol::Dynamic(_)
+        ));
+        assert_eq!(dynamic::sym0("foo").canonicalize(), dynamic::sym0("foo"));
+        assert_eq!(STATIC_B_0.canonicalize(), STATIC_B_0);
+    }
+
+    #[test]
+    fn test_inline() {
+        static NS_A: inline::InlineNamespace = inline::InlineNamespace::new("A");
+        static NS_C: inline::InlineNamespace = inline::InlineNamespace::new("C");
+
+        let test_state = TestState::new();
+
+        test_state.assert_full_eq(&NS_C.symbol("foo"), &NS_C.symbol("foo"));
+        test_state.assert_full_eq(&NS_C.symbol("foo"), &NS_C.symbol("foo").clone());
+        test_state.assert_full_ne(&NS_C.symbol("foo"), &NS_C.symbol("bar"));
+        test_state.assert_full_ne(&NS_C.symbol("0"), &NS_A.symbol("0"));
+        test_state.assert_full_ne(&NS_A.symbol("0"), &STATIC_A_0);
+        test_state.assert_full_ne(&NS_C.symbol("foo"), &dynamic::sym0("foo"));
+
+        assert_eq!(format!("{:?}", NS_C.symbol("foo")), "C::foo");
+        assert_eq!(format!("{}", NS_C.symbol("foo")), "C::foo");
+        assert_eq!(NS_C.symbol("foo").name(), "foo");
+        assert!(NS_C.symbol("foo").downcast_dyn::<String>().is_none());
+        assert!(NS_C.symbol("foo").metadata::<String>().is_none());
+        assert!(NS_C.symbol("foo").as_any().is::<inline::InlineNamespace>());
+
+        assert_eq!(STATIC_A_0.cmp(&NS_A.symbol("0")), Ordering::Less);
+        assert_eq!(dynamic::sym0("foo").cmp(&NS_A.symbol("0")), Ordering::Less);
+    }
+
     #[test]
     fn test_ord() {
         assert_ne!(STATIC_A_0.cmp(&STATIC_A_1), Ordering::Equal);
         assert_ne!(STATIC_A_0.cmp(&STATIC_B_0), Ordering::Equal);
         assert_ne!(STATIC_A_1.cmp(&STATIC_B_0), Ordering::Equal);
     }
+
+    #[test]
+    fn test_ord_by_namespace_name() {
+        assert_eq!(STATIC_A_0.cmp(&STATIC_B_0), Ordering::Less);
+        assert_eq!(STATIC_B_0.cmp(&STATIC_A_1), Ordering::Greater);
+        assert_eq!(dynamic::sym0("b").cmp(&dynamic::sym1("a")), Ordering::Less);
+        assert_eq!(
+            dynamic::wrapped("a").cmp(&dynamic::sym1("b")),
+            Ordering::Greater
+        );
+    }
 }
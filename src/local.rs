@@ -0,0 +1,314 @@
+//!
+//! Symbols that can't be shared between threads.
+//!
+//! The dynamic namespaces of [Symbol] must be `Send` and `Sync`. A [LocalSymbol] can also hold instances of a
+//! [LocalDynamic](namespace::LocalDynamic), which can be backed by e.g. [Rc] based interner data in a single-threaded program:
+//!
+//! ```
+//! use dyn_symbol::local::LocalSymbol;
+//! use dyn_symbol::*;
+//! use std::rc::Rc;
+//!
+//! struct Widget(Rc<str>);
+//!
+//! impl namespace::LocalDynamic for Widget {
+//!     fn namespace_name(&self) -> &str {
+//!         "widget"
+//!     }
+//!
+//!     fn symbol_name(&self) -> &str {
+//!         &self.0
+//!     }
+//!
+//!     fn dyn_eq(&self, rhs: &dyn namespace::LocalDynamic) -> bool {
+//!         self.0 == rhs.downcast_ref::<Self>().unwrap().0
+//!     }
+//!
+//!     fn dyn_cmp(&self, rhs: &dyn namespace::LocalDynamic) -> std::cmp::Ordering {
+//!         self.0.cmp(&rhs.downcast_ref::<Self>().unwrap().0)
+//!     }
+//!
+//!     fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+//!         state.write(self.0.as_bytes())
+//!     }
+//! }
+//!
+//! let name: Rc<str> = "button".into();
+//! let button = LocalSymbol::Local(Rc::new(Widget(name.clone())));
+//!
+//! assert_eq!(button, LocalSymbol::Local(Rc::new(Widget(name))));
+//! assert_eq!(button.to_string(), "widget::button");
+//! assert_eq!(button.to_symbol(), None);
+//! ```
+//!
+//! Local symbols compare, order and hash in the same way as [Symbol]s, and any [Symbol] can be used as a
+//! [Shared](LocalSymbol::Shared) local symbol.
+//! A [Local](LocalSymbol::Local) symbol is never equal to a shared one, even if it [converts](LocalSymbol::to_symbol) to it.
+//!
+
+use crate::{namespace, Canonical, Symbol};
+
+use alloc::rc::Rc;
+
+///
+/// A [Symbol], or a symbol instance that is not thread-safe.
+///
+#[derive(Clone)]
+pub enum LocalSymbol {
+    /// A thread-safe symbol.
+    Shared(Symbol),
+
+    /// A symbol instance of a [LocalDynamic](namespace::LocalDynamic) namespace.
+    Local(Rc<dyn namespace::LocalDynamic>),
+}
+
+impl LocalSymbol {
+    ///
+    /// The symbol's name within its namespace.
+    ///
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Shared(symbol) => symbol.name(),
+            Self::Local(instance) => instance.symbol_name(),
+        }
+    }
+
+    ///
+    /// The name of the symbol's namespace.
+    ///
+    pub fn namespace(&self) -> &str {
+        match self {
+            Self::Shared(symbol) => symbol.namespace(),
+            Self::Local(instance) => instance.namespace_name(),
+        }
+    }
+
+    ///
+    /// The [Shared](Self::Shared) symbol, if this is one.
+    ///
+    pub fn as_symbol(&self) -> Option<&Symbol> {
+        match self {
+            Self::Shared(symbol) => Some(symbol),
+            Self::Local(_) => None,
+        }
+    }
+
+    ///
+    /// This symbol as a thread-safe [Symbol], if it is [Shared](Self::Shared) or its instance has a
+    /// [thread-safe copy](namespace::LocalDynamic::to_dynamic).
+    ///
+    pub fn to_symbol(&self) -> Option<Symbol> {
+        match self {
+            Self::Shared(symbol) => Some(symbol.clone()),
+            Self::Local(instance) => instance
+                .to_dynamic()
+                .map(|dynamic| Symbol::Dynamic(dynamic.into())),
+        }
+    }
+
+    ///
+    /// Downcast a [Local](Self::Local) symbol instance to a concrete type.
+    ///
+    pub fn downcast_local<T: namespace::LocalDynamic>(&self) -> Option<&T> {
+        match self {
+            Self::Shared(_) => None,
+            Self::Local(instance) => instance.downcast_ref::<T>(),
+        }
+    }
+
+    fn canonical(&self) -> Canonical<'_> {
+        match self {
+            Self::Shared(symbol) => symbol.canonical(),
+            Self::Local(instance) => Canonical::Local(instance.as_ref()),
+        }
+    }
+}
+
+impl From<Symbol> for LocalSymbol {
+    fn from(symbol: Symbol) -> Self {
+        Self::Shared(symbol)
+    }
+}
+
+impl PartialEq for LocalSymbol {
+    fn eq(&self, rhs: &LocalSymbol) -> bool {
+        self.canonical() == rhs.canonical()
+    }
+}
+
+impl Eq for LocalSymbol {}
+
+impl PartialEq<Symbol> for LocalSymbol {
+    fn eq(&self, rhs: &Symbol) -> bool {
+        self.canonical() == rhs.canonical()
+    }
+}
+
+impl Ord for LocalSymbol {
+    fn cmp(&self, rhs: &LocalSymbol) -> core::cmp::Ordering {
+        self.canonical().cmp(&rhs.canonical())
+    }
+}
+
+impl PartialOrd for LocalSymbol {
+    fn partial_cmp(&self, rhs: &LocalSymbol) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::hash::Hash for LocalSymbol {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+impl core::fmt::Display for LocalSymbol {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Shared(symbol) => core::fmt::Display::fmt(symbol, f),
+            Self::Local(instance) => instance.fmt_display(f),
+        }
+    }
+}
+
+impl core::fmt::Debug for LocalSymbol {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Shared(symbol) => core::fmt::Debug::fmt(symbol, f),
+            Self::Local(instance) => instance.fmt_debug(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{dynamic, STATIC_A_0};
+    use std::cell::RefCell;
+    use std::collections::{BTreeSet, HashSet};
+    use std::hash::BuildHasher;
+
+    /// A name in a single-threaded interner.
+    struct Interned(Rc<str>);
+
+    thread_local! {
+        static INTERNER: RefCell<HashSet<Rc<str>>> = RefCell::new(HashSet::new());
+    }
+
+    fn interned(name: &str) -> LocalSymbol {
+        let name = INTERNER.with(|interner| {
+            let mut interner = interner.borrow_mut();
+            match interner.get(name) {
+                Some(name) => name.clone(),
+                None => {
+                    let name: Rc<str> = name.into();
+                    interner.insert(name.clone());
+                    name
+                }
+            }
+        });
+        LocalSymbol::Local(Rc::new(Interned(name)))
+    }
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Plain(String);
+
+    impl namespace::DynValue for Plain {
+        fn namespace_name(&self) -> &str {
+            "plain"
+        }
+
+        fn symbol_name(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl namespace::LocalDynamic for Interned {
+        fn namespace_name(&self) -> &str {
+            "interned"
+        }
+
+        fn symbol_name(&self) -> &str {
+            &self.0
+        }
+
+        fn to_dynamic(&self) -> Option<Box<dyn namespace::Dynamic>> {
+            Some(Box::new(namespace::DynWrap(Plain(self.0.to_string()))))
+        }
+
+        fn dyn_eq(&self, rhs: &dyn namespace::LocalDynamic) -> bool {
+            Rc::ptr_eq(&self.0, &rhs.downcast_ref::<Self>().unwrap().0)
+        }
+
+        fn dyn_cmp(&self, rhs: &dyn namespace::LocalDynamic) -> core::cmp::Ordering {
+            self.0.cmp(&rhs.downcast_ref::<Self>().unwrap().0)
+        }
+
+        fn dyn_hash(&self, state: &mut dyn core::hash::Hasher) {
+            state.write_usize(Rc::as_ptr(&self.0) as *const u8 as usize)
+        }
+    }
+
+    #[test]
+    fn test_local_symbols() {
+        let random_state = std::collections::hash_map::RandomState::new();
+
+        assert_eq!(interned("foo"), interned("foo"));
+        assert_ne!(interned("foo"), interned("bar"));
+        assert_eq!(
+            random_state.hash_one(interned("foo")),
+            random_state.hash_one(interned("foo"))
+        );
+        assert_eq!(
+            interned("foo")
+                .downcast_local::<Interned>()
+                .unwrap()
+                .0
+                .as_ref(),
+            "foo"
+        );
+        assert_eq!(format!("{:?}", interned("foo")), "interned::foo");
+    }
+
+    #[test]
+    fn test_shared_symbols() {
+        let random_state = std::collections::hash_map::RandomState::new();
+        let shared = LocalSymbol::from(dynamic::sym0("foo"));
+
+        assert_eq!(shared, dynamic::sym0("foo"));
+        assert_eq!(
+            random_state.hash_one(&shared),
+            random_state.hash_one(dynamic::sym0("foo"))
+        );
+        assert_eq!(shared.to_symbol(), Some(dynamic::sym0("foo")));
+        assert!(interned("foo").as_symbol().is_none());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let set: BTreeSet<_> = vec![
+            interned("b"),
+            LocalSymbol::from(dynamic::sym0("foo")),
+            interned("a"),
+            LocalSymbol::from(STATIC_A_0),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            set.iter().map(LocalSymbol::name).collect::<Vec<_>>(),
+            ["0", "foo", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_to_symbol() {
+        let symbol = interned("foo").to_symbol().unwrap();
+
+        assert_eq!(
+            symbol,
+            Symbol::from(namespace::DynWrap(Plain("foo".into())))
+        );
+        assert_ne!(interned("foo"), symbol);
+    }
+}
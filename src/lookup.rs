@@ -0,0 +1,138 @@
+//!
+//! Name based lookups into [hashbrown] maps keyed by [Symbol].
+//!
+//! Symbols from namespaces that [hash by name](crate::namespace::Static::hash_by_name), which includes all
+//! [inline](crate::inline) symbols, can be looked up by a `(namespace, name)` tuple or a [QualifiedName],
+//! without constructing a temporary symbol. The [Equivalent] trait is shared with `indexmap`, so this works
+//! for `IndexMap<Symbol, V>` too.
+//!
+//! ```
+//! use dyn_symbol::inline::InlineNamespace;
+//! use dyn_symbol::lookup::QualifiedName;
+//! use hashbrown::HashMap;
+//!
+//! static ROUTES: InlineNamespace = InlineNamespace::new("routes");
+//!
+//! let mut map = HashMap::new();
+//! map.insert(ROUTES.symbol("index"), 0);
+//! map.insert(ROUTES.symbol("a-really-long-route-name"), 1);
+//!
+//! assert_eq!(map.get(&("routes", "index")), Some(&0));
+//! assert_eq!(map.get(&QualifiedName::parse("routes::a-really-long-route-name").unwrap()), Some(&1));
+//! assert_eq!(map.get(&("routes", "missing")), None);
+//! ```
+//!
+
+use crate::Symbol;
+
+use hashbrown::Equivalent;
+
+///
+/// A namespace name and a symbol name, to look up a symbol by.
+///
+/// Its [Hash](core::hash::Hash) is the same as that of the `(namespace, name)` tuple.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct QualifiedName<'a> {
+    namespace: &'a str,
+    name: &'a str,
+}
+
+impl<'a> QualifiedName<'a> {
+    ///
+    /// The symbol `name` in the namespace named `namespace`.
+    ///
+    pub const fn new(namespace: &'a str, name: &'a str) -> Self {
+        Self { namespace, name }
+    }
+
+    ///
+    /// Parse a qualified `namespace::symbol` name. The string is split at the first `::`.
+    ///
+    pub fn parse(qualified_name: &'a str) -> Option<Self> {
+        let (namespace, name) = qualified_name.split_once("::")?;
+        Some(Self::new(namespace, name))
+    }
+
+    ///
+    /// The namespace name.
+    ///
+    pub fn namespace(&self) -> &'a str {
+        self.namespace
+    }
+
+    ///
+    /// The symbol name.
+    ///
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+}
+
+impl Equivalent<Symbol> for QualifiedName<'_> {
+    fn equivalent(&self, key: &Symbol) -> bool {
+        key.hashed_names() == Some((self.namespace, self.name))
+    }
+}
+
+impl Equivalent<Symbol> for (&str, &str) {
+    fn equivalent(&self, key: &Symbol) -> bool {
+        key.hashed_names() == Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inline::InlineNamespace;
+    use crate::namespace;
+    use crate::tests::{dynamic, STATIC_A_0};
+    use hashbrown::HashMap;
+
+    static NS: InlineNamespace = InlineNamespace::new("ns");
+
+    struct ByName;
+
+    impl namespace::Static for ByName {
+        fn namespace_name(&self) -> &str {
+            "by_name"
+        }
+
+        fn symbol_count(&self) -> u32 {
+            2
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["foo", "bar"][id as usize]
+        }
+
+        fn hash_by_name(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_name_lookup() {
+        let mut map = HashMap::new();
+        map.insert(NS.symbol("foo"), 0);
+        map.insert(NS.symbol("not inlined, too long"), 1);
+        map.insert(Symbol::Static(&ByName, 1), 2);
+        map.insert(STATIC_A_0, 3);
+        map.insert(dynamic::sym0("foo"), 4);
+
+        assert_eq!(map.get(&("ns", "foo")), Some(&0));
+        assert_eq!(
+            map.get(&QualifiedName::new("ns", "not inlined, too long")),
+            Some(&1)
+        );
+        assert_eq!(
+            map.get(&QualifiedName::parse("by_name::bar").unwrap()),
+            Some(&2)
+        );
+        assert_eq!(map.get(&("by_name", "foo")), None);
+
+        // Not hashed by name:
+        assert_eq!(map.get(&("A", "0")), None);
+        assert_eq!(map.get(&("dyn0", "foo")), None);
+    }
+}
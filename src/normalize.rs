@@ -0,0 +1,300 @@
+//!
+//! Normalized names, for namespaces with case-insensitive or otherwise normalized symbol identity.
+//!
+//! A dynamic namespace implements [normalized_name](crate::namespace::Dynamic::normalized_name), and uses
+//! [dyn_eq], [dyn_cmp] and [dyn_hash] from this module, so that equality, ordering and hashing agree on the normalized
+//! name. The original spelling is kept for [symbol_name](crate::namespace::Dynamic::symbol_name):
+//!
+//! ```
+//! use dyn_symbol::normalize::{self, AsciiCaseInsensitive, NormalizedStr};
+//! use dyn_symbol::*;
+//! use std::borrow::Cow;
+//!
+//! #[derive(Clone)]
+//! struct Attr(NormalizedStr<AsciiCaseInsensitive>);
+//!
+//! impl namespace::Dynamic for Attr {
+//!     fn namespace_name(&self) -> &str {
+//!         "attr"
+//!     }
+//!
+//!     fn symbol_name(&self) -> &str {
+//!         self.0.as_str()
+//!     }
+//!
+//!     fn normalized_name(&self) -> Cow<'_, str> {
+//!         self.0.normalized().into()
+//!     }
+//!
+//!     fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+//!         Box::new(self.clone())
+//!     }
+//!
+//!     fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+//!         normalize::dyn_eq(self, rhs)
+//!     }
+//!
+//!     fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> std::cmp::Ordering {
+//!         normalize::dyn_cmp(self, rhs)
+//!     }
+//!
+//!     fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+//!         normalize::dyn_hash(self, state)
+//!     }
+//! }
+//!
+//! let on_click = Symbol::Dynamic(std::sync::Arc::new(Attr(NormalizedStr::new("onClick"))));
+//! let onclick = Symbol::Dynamic(std::sync::Arc::new(Attr(NormalizedStr::new("onclick"))));
+//!
+//! assert_eq!(on_click, onclick);
+//! assert_eq!(on_click.name(), "onClick");
+//! ```
+//!
+//! A [DynValue](crate::namespace::DynValue) can instead derive its traits from a [NormalizedStr] field, which compares and hashes
+//! by its normalized form.
+//!
+
+use crate::namespace::Dynamic;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::marker::PhantomData;
+
+///
+/// [Dynamic::dyn_eq] by [normalized_name](Dynamic::normalized_name).
+///
+pub fn dyn_eq<T: Dynamic + ?Sized>(this: &T, rhs: &dyn Dynamic) -> bool {
+    this.normalized_name() == rhs.normalized_name()
+}
+
+///
+/// [Dynamic::dyn_cmp] by [normalized_name](Dynamic::normalized_name).
+///
+pub fn dyn_cmp<T: Dynamic + ?Sized>(this: &T, rhs: &dyn Dynamic) -> core::cmp::Ordering {
+    this.normalized_name().cmp(&rhs.normalized_name())
+}
+
+///
+/// [Dynamic::dyn_hash] by [normalized_name](Dynamic::normalized_name).
+///
+pub fn dyn_hash<T: Dynamic + ?Sized>(this: &T, state: &mut dyn core::hash::Hasher) {
+    state.write(this.normalized_name().as_bytes());
+    state.write_u8(0xff)
+}
+
+///
+/// A normalization of strings, used by [NormalizedStr].
+///
+pub trait Normalizer: 'static {
+    ///
+    /// Normalize `s`, borrowing it if it's already normalized.
+    ///
+    fn normalize(s: &str) -> Cow<'_, str>;
+}
+
+///
+/// Normalizes ASCII letters to lowercase, leaving other characters as they are.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct AsciiCaseInsensitive;
+
+impl Normalizer for AsciiCaseInsensitive {
+    fn normalize(s: &str) -> Cow<'_, str> {
+        if s.bytes().any(|b| b.is_ascii_uppercase()) {
+            Cow::Owned(s.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+}
+
+///
+/// A string that compares, orders and hashes by its normalized form, and remembers its original spelling.
+///
+/// The normalized form is computed once, when the string is created, and only stored if it differs from the original.
+///
+pub struct NormalizedStr<N: Normalizer> {
+    original: Box<str>,
+    normalized: Option<Box<str>>,
+    normalizer: PhantomData<fn() -> N>,
+}
+
+impl<N: Normalizer> NormalizedStr<N> {
+    ///
+    /// Create a normalized string.
+    ///
+    pub fn new(s: impl Into<String>) -> Self {
+        let original: Box<str> = s.into().into_boxed_str();
+        let normalized = match N::normalize(&original) {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(normalized) => Some(normalized.into_boxed_str()),
+        };
+
+        Self {
+            original,
+            normalized,
+            normalizer: PhantomData,
+        }
+    }
+
+    ///
+    /// The original string.
+    ///
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    ///
+    /// The normalized string.
+    ///
+    pub fn normalized(&self) -> &str {
+        self.normalized.as_deref().unwrap_or(&self.original)
+    }
+}
+
+impl<N: Normalizer> Clone for NormalizedStr<N> {
+    fn clone(&self) -> Self {
+        Self {
+            original: self.original.clone(),
+            normalized: self.normalized.clone(),
+            normalizer: PhantomData,
+        }
+    }
+}
+
+impl<N: Normalizer> PartialEq for NormalizedStr<N> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.normalized() == rhs.normalized()
+    }
+}
+
+impl<N: Normalizer> Eq for NormalizedStr<N> {}
+
+impl<N: Normalizer> Ord for NormalizedStr<N> {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        self.normalized().cmp(rhs.normalized())
+    }
+}
+
+impl<N: Normalizer> PartialOrd for NormalizedStr<N> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<N: Normalizer> core::hash::Hash for NormalizedStr<N> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state)
+    }
+}
+
+impl<N: Normalizer> core::fmt::Debug for NormalizedStr<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{namespace, Symbol};
+    use std::collections::HashMap;
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Attr(NormalizedStr<AsciiCaseInsensitive>);
+
+    impl namespace::DynValue for Attr {
+        fn namespace_name(&self) -> &str {
+            "attr"
+        }
+
+        fn symbol_name(&self) -> &str {
+            self.0.as_str()
+        }
+
+        fn normalized_name(&self) -> Cow<'_, str> {
+            self.0.normalized().into()
+        }
+    }
+
+    fn attr(name: &str) -> Symbol {
+        Symbol::from(namespace::DynWrap(Attr(NormalizedStr::new(name))))
+    }
+
+    #[test]
+    fn test_normalized_str() {
+        let a = NormalizedStr::<AsciiCaseInsensitive>::new("ÅriA-Label");
+        assert_eq!(a.as_str(), "ÅriA-Label");
+        assert_eq!(a.normalized(), "Åria-label");
+        assert!(NormalizedStr::<AsciiCaseInsensitive>::new("lower")
+            .normalized
+            .is_none());
+        assert_eq!(a, NormalizedStr::new("ÅRIA-label"));
+        assert_ne!(a, NormalizedStr::new("åria-label"));
+    }
+
+    #[test]
+    fn test_case_insensitive_symbols() {
+        let mut map = HashMap::new();
+        map.insert(attr("onClick"), 0);
+
+        assert_eq!(map.get(&attr("ONCLICK")), Some(&0));
+        assert_eq!(map.get(&attr("onclick")), Some(&0));
+        assert_eq!(map.get(&attr("onchange")), None);
+        assert_eq!(map.keys().next().unwrap().name(), "onClick");
+    }
+
+    #[test]
+    fn test_uid_namespace_normalized() {
+        struct Versioned<const N: u8>(NormalizedStr<AsciiCaseInsensitive>);
+
+        impl<const N: u8> namespace::Dynamic for Versioned<N> {
+            fn namespace_name(&self) -> &str {
+                "versioned"
+            }
+
+            fn symbol_name(&self) -> &str {
+                self.0.as_str()
+            }
+
+            fn normalized_name(&self) -> Cow<'_, str> {
+                self.0.normalized().into()
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(7)
+            }
+
+            fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+                Box::new(Versioned::<N>(self.0.clone()))
+            }
+
+            fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+                dyn_eq(self, rhs)
+            }
+
+            fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> core::cmp::Ordering {
+                dyn_cmp(self, rhs)
+            }
+
+            fn dyn_hash(&self, state: &mut dyn core::hash::Hasher) {
+                dyn_hash(self, state)
+            }
+        }
+
+        let v1 = Symbol::Dynamic(std::sync::Arc::new(Versioned::<1>(NormalizedStr::new(
+            "Foo",
+        ))));
+        let v2 = Symbol::Dynamic(std::sync::Arc::new(Versioned::<2>(NormalizedStr::new(
+            "fOO",
+        ))));
+
+        let random_state = std::collections::hash_map::RandomState::new();
+        assert_eq!(v1, v2);
+        assert_eq!(
+            std::hash::BuildHasher::hash_one(&random_state, &v1),
+            std::hash::BuildHasher::hash_one(&random_state, &v2)
+        );
+    }
+}
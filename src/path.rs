@@ -0,0 +1,355 @@
+//!
+//! Sequences of symbols, used as hierarchical keys.
+//!
+//! A [SymbolPath] keeps the namespace of each segment, unlike a key built by concatenating names:
+//!
+//! ```
+//! use dyn_symbol::path::SymbolPath;
+//! use dyn_symbol::*;
+//!
+//! struct Device;
+//!
+//! impl namespace::Static for Device {
+//!     fn namespace_name(&self) -> &str {
+//!         "device"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         3
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["sensors", "temperature", "humidity"][id as usize]
+//!     }
+//! }
+//!
+//! let sensors = SymbolPath::from(Symbol::Static(&Device, 0));
+//! let temperature = sensors.join(Symbol::Static(&Device, 1));
+//!
+//! assert!(temperature.starts_with(&sensors));
+//! assert_eq!(temperature.parent(), Some(sensors));
+//! assert_eq!(temperature.to_string(), "device::sensors/device::temperature");
+//! ```
+//!
+
+use crate::Symbol;
+
+use smallvec::SmallVec;
+
+/// Paths of up to this many segments are stored inline.
+const INLINE_SEGMENTS: usize = 4;
+
+const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const PRIME: u64 = 0x00000100000001b3;
+
+/// FNV-1a, so that a path's hash can be computed from its parent's hash, regardless of the hasher of a map.
+struct PathHasher(u64);
+
+impl core::hash::Hasher for PathHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(PRIME);
+        }
+    }
+}
+
+///
+/// A sequence of [Symbol]s, e.g. `device::sensors/device::temperature`.
+///
+/// Paths compare and order segment by segment. The hash of every prefix of the path is computed as segments are pushed,
+/// so [parent](SymbolPath::parent) and [prefix](SymbolPath::prefix) don't rehash their segments,
+/// and unequal paths rarely need to compare their segments.
+///
+#[derive(Clone, Default)]
+pub struct SymbolPath {
+    segments: SmallVec<[Symbol; INLINE_SEGMENTS]>,
+    /// `hashes[i]` is the hash of the first `i + 1` segments.
+    hashes: SmallVec<[u64; INLINE_SEGMENTS]>,
+}
+
+impl SymbolPath {
+    ///
+    /// An empty path.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// The number of segments.
+    ///
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    ///
+    /// Whether the path has no segments.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    ///
+    /// The segments of the path.
+    ///
+    pub fn as_slice(&self) -> &[Symbol] {
+        &self.segments
+    }
+
+    ///
+    /// Iterate the segments of the path.
+    ///
+    pub fn iter(&self) -> core::slice::Iter<'_, Symbol> {
+        self.segments.iter()
+    }
+
+    ///
+    /// The last segment.
+    ///
+    pub fn last(&self) -> Option<&Symbol> {
+        self.segments.last()
+    }
+
+    ///
+    /// Append a segment.
+    ///
+    pub fn push(&mut self, symbol: Symbol) {
+        let mut hasher = PathHasher(self.path_hash());
+        core::hash::Hash::hash(&symbol, &mut hasher);
+
+        self.hashes.push(hasher.0);
+        self.segments.push(symbol);
+    }
+
+    ///
+    /// Remove and return the last segment.
+    ///
+    pub fn pop(&mut self) -> Option<Symbol> {
+        self.hashes.pop();
+        self.segments.pop()
+    }
+
+    ///
+    /// Shorten the path to its first `len` segments. Does nothing if the path is not longer than `len`.
+    ///
+    pub fn truncate(&mut self, len: usize) {
+        self.hashes.truncate(len);
+        self.segments.truncate(len);
+    }
+
+    ///
+    /// This path with `symbol` appended.
+    ///
+    pub fn join(&self, symbol: Symbol) -> Self {
+        let mut path = self.clone();
+        path.push(symbol);
+        path
+    }
+
+    ///
+    /// The path without its last segment, or [None] if the path is empty.
+    ///
+    pub fn parent(&self) -> Option<Self> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.prefix(self.len() - 1))
+        }
+    }
+
+    ///
+    /// The first `len` segments of the path.
+    ///
+    /// # Panics
+    /// If `len` is greater than the length of the path.
+    ///
+    pub fn prefix(&self, len: usize) -> Self {
+        assert!(len <= self.len(), "prefix longer than the path");
+
+        Self {
+            segments: self.segments[..len].iter().cloned().collect(),
+            hashes: self.hashes[..len].iter().copied().collect(),
+        }
+    }
+
+    ///
+    /// Whether `prefix` is a prefix of this path. Every path starts with itself and with the empty path.
+    ///
+    pub fn starts_with(&self, prefix: &SymbolPath) -> bool {
+        self.strip_prefix(prefix).is_some()
+    }
+
+    ///
+    /// The segments following `prefix`, if this path starts with `prefix`.
+    ///
+    pub fn strip_prefix(&self, prefix: &SymbolPath) -> Option<&[Symbol]> {
+        let len = prefix.len();
+        if len > self.len() || self.prefix_hash(len) != prefix.path_hash() {
+            return None;
+        }
+
+        if self.segments[..len] == prefix.segments[..] {
+            Some(&self.segments[len..])
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// The hash of the path, which doesn't depend on any [Hasher](core::hash::Hasher).
+    ///
+    /// This is the value the path feeds to a hasher, so it's only as collision resistant as a 64-bit FNV hash.
+    ///
+    pub fn path_hash(&self) -> u64 {
+        self.prefix_hash(self.len())
+    }
+
+    fn prefix_hash(&self, len: usize) -> u64 {
+        match len {
+            0 => OFFSET_BASIS,
+            len => self.hashes[len - 1],
+        }
+    }
+}
+
+impl From<Symbol> for SymbolPath {
+    fn from(symbol: Symbol) -> Self {
+        let mut path = Self::new();
+        path.push(symbol);
+        path
+    }
+}
+
+impl core::iter::FromIterator<Symbol> for SymbolPath {
+    fn from_iter<I: IntoIterator<Item = Symbol>>(iter: I) -> Self {
+        let mut path = Self::new();
+        path.extend(iter);
+        path
+    }
+}
+
+impl Extend<Symbol> for SymbolPath {
+    fn extend<I: IntoIterator<Item = Symbol>>(&mut self, iter: I) {
+        for symbol in iter {
+            self.push(symbol);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a SymbolPath {
+    type Item = &'a Symbol;
+    type IntoIter = core::slice::Iter<'a, Symbol>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for SymbolPath {
+    fn eq(&self, rhs: &SymbolPath) -> bool {
+        self.path_hash() == rhs.path_hash() && self.segments == rhs.segments
+    }
+}
+
+impl Eq for SymbolPath {}
+
+impl Ord for SymbolPath {
+    fn cmp(&self, rhs: &SymbolPath) -> core::cmp::Ordering {
+        self.segments.cmp(&rhs.segments)
+    }
+}
+
+impl PartialOrd for SymbolPath {
+    fn partial_cmp(&self, rhs: &SymbolPath) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::hash::Hash for SymbolPath {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.path_hash())
+    }
+}
+
+///
+/// Formats the qualified name of each segment, separated by `/`.
+///
+impl core::fmt::Display for SymbolPath {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        for (i, symbol) in self.segments.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{}", symbol.display())?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for SymbolPath {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(self.segments.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1, STATIC_B_0};
+    use std::collections::HashMap;
+
+    fn path(symbols: &[Symbol]) -> SymbolPath {
+        symbols.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_prefix_and_parent() {
+        let a = path(&[STATIC_A_0, dynamic::sym0("x"), STATIC_B_0]);
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.parent(), Some(path(&[STATIC_A_0, dynamic::sym0("x")])));
+        assert_eq!(a.prefix(0), SymbolPath::new());
+        assert_eq!(SymbolPath::new().parent(), None);
+
+        assert!(a.starts_with(&a.prefix(1)));
+        assert!(a.starts_with(&SymbolPath::new()));
+        assert!(!a.starts_with(&path(&[STATIC_A_1])));
+        assert!(!a.prefix(1).starts_with(&a));
+        assert_eq!(a.strip_prefix(&a.prefix(1)), Some(&a.as_slice()[1..]));
+
+        let mut b = a.clone();
+        assert_eq!(b.pop(), Some(STATIC_B_0));
+        b.push(STATIC_B_0);
+        assert_eq!(a, b);
+        assert_eq!(a.path_hash(), b.path_hash());
+    }
+
+    #[test]
+    fn test_namespaces_are_distinct() {
+        let a = path(&[STATIC_A_0, dynamic::sym0("x")]);
+        let b = path(&[STATIC_A_0, dynamic::sym1("x")]);
+
+        assert_ne!(a, b);
+        assert_eq!(a.to_string(), "A::0/dyn0::x");
+    }
+
+    #[test]
+    fn test_ordering_and_map_keys() {
+        let short = path(&[STATIC_A_0]);
+        let long = path(&[STATIC_A_0, STATIC_A_1]);
+        let other = path(&[STATIC_A_1]);
+
+        assert!(short < long);
+        assert!(long < other);
+
+        let mut map = HashMap::new();
+        map.insert(long.clone(), 1);
+        map.insert(other.clone(), 2);
+        assert_eq!(map.get(&short.join(STATIC_A_1)), Some(&1));
+        assert_eq!(map.get(&long.parent().unwrap()), None);
+    }
+}
@@ -0,0 +1,307 @@
+//!
+//! Patterns matching sets of symbols, for queries like "all `data-*` attributes" or "everything in namespace `svg`".
+//!
+//! ```
+//! use dyn_symbol::pattern::SymbolPattern;
+//! use dyn_symbol::inline::InlineNamespace;
+//!
+//! static HTML: InlineNamespace = InlineNamespace::new("html");
+//! static SVG: InlineNamespace = InlineNamespace::new("svg");
+//!
+//! let attributes = vec![HTML.symbol("id"), HTML.symbol("data-foo"), HTML.symbol("data-bar"), SVG.symbol("data-x")];
+//!
+//! let data = SymbolPattern::any().namespace_name("html").name_glob("data-*");
+//! let data: Vec<_> = data.filter(attributes.iter()).collect();
+//! assert_eq!(data, [&HTML.symbol("data-foo"), &HTML.symbol("data-bar")]);
+//!
+//! let svg = SymbolPattern::parse("svg::*").unwrap();
+//! assert!(svg.matches(&SVG.symbol("data-x")));
+//! assert!(!svg.matches(&HTML.symbol("id")));
+//! ```
+//!
+
+use crate::symbol_ref::SymbolRef;
+use crate::Symbol;
+
+use core::any::TypeId;
+use core::borrow::Borrow;
+
+///
+/// A pattern matching symbols by namespace and name.
+///
+/// The empty pattern, [SymbolPattern::any], matches every symbol. Each builder method narrows it down.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct SymbolPattern<'a> {
+    namespace: NamespacePattern<'a>,
+    name: NamePattern<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NamespacePattern<'a> {
+    Any,
+    Name(&'a str),
+    Type(TypeId),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NamePattern<'a> {
+    Any,
+    Exact(&'a str),
+    Prefix(&'a str),
+    Glob(&'a str),
+}
+
+impl<'a> SymbolPattern<'a> {
+    ///
+    /// A pattern matching any symbol.
+    ///
+    pub const fn any() -> Self {
+        Self {
+            namespace: NamespacePattern::Any,
+            name: NamePattern::Any,
+        }
+    }
+
+    ///
+    /// Parse a `namespace::name` pattern. The string is split at the first `::`.
+    ///
+    /// The namespace is matched by name, or is `*` to match any namespace. The name is a [glob](Self::name_glob).
+    ///
+    pub fn parse(pattern: &'a str) -> Option<Self> {
+        let (namespace, name) = pattern.split_once("::")?;
+        let pattern = Self::any().name_glob(name);
+
+        Some(match namespace {
+            "*" => pattern,
+            namespace => pattern.namespace_name(namespace),
+        })
+    }
+
+    ///
+    /// Only match symbols in namespaces named `namespace`.
+    ///
+    pub const fn namespace_name(self, namespace: &'a str) -> Self {
+        Self {
+            namespace: NamespacePattern::Name(namespace),
+            ..self
+        }
+    }
+
+    ///
+    /// Only match symbols whose namespace is the type `T`.
+    ///
+    /// `T` is the [Static](crate::namespace::Static) namespace type, or the [Dynamic](crate::namespace::Dynamic) instance type
+    /// (e.g. `DynWrap<T>` for [DynValue](crate::namespace::DynValue)s). Inline symbols have the namespace type
+    /// [InlineNamespace].
+    ///
+    pub fn namespace_type<T: 'static>(self) -> Self {
+        Self {
+            namespace: NamespacePattern::Type(TypeId::of::<T>()),
+            ..self
+        }
+    }
+
+    ///
+    /// Only match symbols named exactly `name`.
+    ///
+    pub const fn name(self, name: &'a str) -> Self {
+        Self {
+            name: NamePattern::Exact(name),
+            ..self
+        }
+    }
+
+    ///
+    /// Only match symbols whose name starts with `prefix`.
+    ///
+    pub const fn name_prefix(self, prefix: &'a str) -> Self {
+        Self {
+            name: NamePattern::Prefix(prefix),
+            ..self
+        }
+    }
+
+    ///
+    /// Only match symbols whose name matches the glob `glob`, in which `*` matches any sequence of characters
+    /// and `?` matches any single character.
+    ///
+    pub const fn name_glob(self, glob: &'a str) -> Self {
+        Self {
+            name: NamePattern::Glob(glob),
+            ..self
+        }
+    }
+
+    ///
+    /// Whether `symbol` matches this pattern.
+    ///
+    pub fn matches(&self, symbol: &Symbol) -> bool {
+        self.matches_ref(symbol.as_symbol_ref())
+    }
+
+    ///
+    /// Whether the borrowed `symbol` matches this pattern.
+    ///
+    pub fn matches_ref(&self, symbol: SymbolRef<'_>) -> bool {
+        let namespace_matches = match self.namespace {
+            NamespacePattern::Any => true,
+            NamespacePattern::Name(name) => symbol.namespace() == name,
+            NamespacePattern::Type(type_id) => symbol.namespace_type_id() == type_id,
+        };
+
+        namespace_matches
+            && match self.name {
+                NamePattern::Any => true,
+                NamePattern::Exact(name) => symbol.name() == name,
+                NamePattern::Prefix(prefix) => symbol.name().starts_with(prefix),
+                NamePattern::Glob(glob) => glob_matches(glob, symbol.name()),
+            }
+    }
+
+    ///
+    /// Filter an iterator of symbols, keeping those matching this pattern.
+    ///
+    pub fn filter<I>(self, symbols: I) -> impl Iterator<Item = I::Item> + 'a
+    where
+        I: IntoIterator,
+        I::IntoIter: 'a,
+        I::Item: Borrow<Symbol>,
+    {
+        symbols
+            .into_iter()
+            .filter(move |symbol| self.matches(symbol.borrow()))
+    }
+
+    ///
+    /// Filter an iterator of `(symbol, value)` pairs, like a map's iterator, keeping those whose symbol matches this pattern.
+    ///
+    pub fn filter_keys<I, K, V>(self, entries: I) -> impl Iterator<Item = (K, V)> + 'a
+    where
+        I: IntoIterator<Item = (K, V)>,
+        I::IntoIter: 'a,
+        K: Borrow<Symbol>,
+    {
+        entries
+            .into_iter()
+            .filter(move |(symbol, _)| self.matches(symbol.borrow()))
+    }
+}
+
+impl Default for SymbolPattern<'_> {
+    fn default() -> Self {
+        Self::any()
+    }
+}
+
+fn glob_matches(glob: &str, name: &str) -> bool {
+    let mut glob_chars = glob.chars();
+    let mut name_chars = name.chars();
+
+    // Where to resume after the last `*`, letting it match one more character.
+    let mut backtrack = None;
+
+    loop {
+        match (glob_chars.next(), name_chars.clone().next()) {
+            (Some('*'), _) => backtrack = Some((glob_chars.clone(), name_chars.clone())),
+            (Some(g), Some(n)) if g == '?' || g == n => {
+                name_chars.next();
+            }
+            (None, None) => return true,
+            _ => match &mut backtrack {
+                Some((resume_glob, resume_name)) => {
+                    if resume_name.next().is_none() {
+                        return false;
+                    }
+                    glob_chars = resume_glob.clone();
+                    name_chars = resume_name.clone();
+                }
+                None => return false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inline::InlineNamespace;
+    use crate::namespace;
+    use crate::tests::_static::ClassN;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1, STATIC_B_0};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_glob() {
+        assert!(glob_matches("", ""));
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("*", "foo"));
+        assert!(glob_matches("data-*", "data-foo"));
+        assert!(glob_matches("data-*", "data-"));
+        assert!(!glob_matches("data-*", "data"));
+        assert!(glob_matches("*-*-z", "x-y-y-z"));
+        assert!(glob_matches("f?o", "føo"));
+        assert!(!glob_matches("f?o", "fo"));
+        assert!(!glob_matches("foo", "foobar"));
+        assert!(glob_matches("**bar", "foobar"));
+    }
+
+    #[test]
+    fn test_symbol_pattern() {
+        assert!(SymbolPattern::any().matches(&STATIC_A_0));
+        assert!(SymbolPattern::any().name("0").matches(&STATIC_A_0));
+        assert!(!SymbolPattern::any().name("0").matches(&STATIC_A_1));
+        assert!(SymbolPattern::any()
+            .namespace_name("A")
+            .matches(&STATIC_A_1));
+        assert!(!SymbolPattern::any()
+            .namespace_name("A")
+            .matches(&STATIC_B_0));
+        assert!(SymbolPattern::any()
+            .namespace_type::<ClassN<1>>()
+            .matches(&STATIC_A_1));
+        assert!(!SymbolPattern::any()
+            .namespace_type::<ClassN<1>>()
+            .matches(&STATIC_B_0));
+        assert!(SymbolPattern::any()
+            .namespace_type::<dynamic::TestDynamic<0>>()
+            .name_prefix("fo")
+            .matches(&dynamic::sym0("foo")));
+        assert!(!SymbolPattern::any()
+            .namespace_type::<namespace::DynWrap<dynamic::Wrapped>>()
+            .matches(&dynamic::sym0("foo")));
+
+        static NS: InlineNamespace = InlineNamespace::new("ns");
+        assert!(SymbolPattern::any()
+            .namespace_type::<InlineNamespace>()
+            .matches(&NS.symbol("not inlined, too long")));
+
+        assert!(SymbolPattern::parse("*::f*")
+            .unwrap()
+            .matches(&dynamic::sym1("foo")));
+        assert!(SymbolPattern::parse("dyn1::f*")
+            .unwrap()
+            .matches(&dynamic::sym1("foo")));
+        assert!(!SymbolPattern::parse("dyn0::f*")
+            .unwrap()
+            .matches(&dynamic::sym1("foo")));
+        assert!(SymbolPattern::parse("foo").is_none());
+    }
+
+    #[test]
+    fn test_filter() {
+        let map: BTreeMap<Symbol, u32> = vec![(STATIC_A_0, 0), (STATIC_A_1, 1), (STATIC_B_0, 2)]
+            .into_iter()
+            .collect();
+
+        let zeros: Vec<_> = SymbolPattern::any().name("0").filter(map.keys()).collect();
+        assert_eq!(zeros, [&STATIC_A_0, &STATIC_B_0]);
+
+        let a: Vec<_> = SymbolPattern::any()
+            .namespace_name("A")
+            .filter_keys(map)
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(a, [0, 1]);
+    }
+}
@@ -0,0 +1,576 @@
+//!
+//! Pooled allocation of short-lived dynamic symbols.
+//!
+//! Each dynamic symbol owns the heap buffer of its name. A [SymbolPool] keeps a free list of name buffers:
+//! when the last clone of a pooled symbol is dropped, its buffer is returned to the pool it was created by,
+//! and reused for the next symbol instead of being freed:
+//!
+//! ```
+//! use dyn_symbol::pool::SymbolPool;
+//!
+//! let pool = SymbolPool::new("event");
+//!
+//! for i in 0..1000 {
+//!     let symbol = pool.symbol(&format!("tick-{}", i));
+//!     assert_eq!(symbol.name(), format!("tick-{}", i));
+//!     // The name buffer of `symbol` is reused on the next iteration.
+//! }
+//! ```
+//!
+//! The reference counted allocation of a symbol is freed by [Arc] right after the symbol is dropped, with no way
+//! for the pool to intercept it. A symbol handed back with [SymbolPool::recycle] is kept whole instead,
+//! so the next symbol needs no allocation at all:
+//!
+//! ```
+//! # use dyn_symbol::pool::SymbolPool;
+//! let pool = SymbolPool::new("event");
+//!
+//! for i in 0..1000 {
+//!     let symbol = pool.symbol(&format!("tick-{}", i));
+//!     pool.recycle(symbol);
+//! }
+//! ```
+//!
+//! Pooled symbols behave like any other dynamic [Symbol]. Each thread keeps a small cache of free buffers per pool,
+//! which spills to and refills from a free list shared by all threads, so a symbol can be created and dropped
+//! on any thread, usually without locking. Caches return their buffers to the shared free list when their thread exits.
+//! Dropping the pool frees its shared free list and the current thread's cache, and the caches of other threads
+//! the next time they use a pool. The buffers of symbols outliving the pool are freed as usual.
+//!
+
+use crate::{namespace, Symbol};
+
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex, Weak};
+
+/// The most free buffers, and symbols, each thread keeps for a pool before spilling to the shared free list.
+const LOCAL_CAPACITY: usize = 32;
+
+thread_local! {
+    /// The free buffers and recycled symbols of this thread, per pool.
+    static LOCAL: RefCell<Vec<Local>> = const { RefCell::new(Vec::new()) };
+}
+
+///
+/// A pool of dynamic symbols in the namespace [Pooled].
+///
+pub struct SymbolPool {
+    namespace_name: &'static str,
+    /// Pooled symbols link back to this, so that they can return their buffers while the pool is alive.
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    capacity: usize,
+    free: Mutex<Vec<String>>,
+}
+
+impl Shared {
+    fn local_capacity(&self) -> usize {
+        self.capacity.min(LOCAL_CAPACITY)
+    }
+}
+
+/// One thread's cache for one pool.
+struct Local {
+    pool: Weak<Shared>,
+    capacity: usize,
+    buffers: Vec<String>,
+    symbols: Vec<Arc<dyn namespace::Dynamic>>,
+}
+
+impl Local {
+    fn new(pool: Weak<Shared>, capacity: usize) -> Self {
+        Self {
+            pool,
+            capacity,
+            buffers: Vec::new(),
+            symbols: Vec::new(),
+        }
+    }
+
+    fn is_for(&self, pool: *const Shared) -> bool {
+        self.pool.as_ptr() == pool
+    }
+
+    fn pop_buffer(&mut self, shared: &Shared) -> String {
+        if self.buffers.is_empty() {
+            if let Ok(mut free) = shared.free.lock() {
+                let at = free.len().saturating_sub(self.batch());
+                self.buffers.extend(free.drain(at..));
+            }
+        }
+        self.buffers.pop().unwrap_or_default()
+    }
+
+    fn push_buffer(&mut self, mut buffer: String) {
+        buffer.clear();
+        if self.buffers.len() >= self.capacity {
+            match self.pool.upgrade() {
+                Some(shared) => {
+                    let at = self.buffers.len() - self.batch().min(self.buffers.len());
+                    spill(&shared, self.buffers.drain(at..));
+                }
+                None => {
+                    self.buffers.clear();
+                    return;
+                }
+            }
+        }
+        if self.buffers.len() < self.capacity {
+            self.buffers.push(buffer);
+        }
+    }
+
+    /// The number of buffers moved to or from the shared free list at a time.
+    fn batch(&self) -> usize {
+        self.capacity.div_ceil(2)
+    }
+}
+
+/// Return this thread's buffers to the pool when the thread exits.
+impl Drop for Local {
+    fn drop(&mut self) {
+        if let Some(shared) = self.pool.upgrade() {
+            spill(&shared, self.buffers.drain(..));
+        }
+    }
+}
+
+/// Move cleared `buffers` into the shared free list, freeing those that don't fit.
+fn spill(shared: &Shared, buffers: impl Iterator<Item = String>) {
+    if let Ok(mut free) = shared.free.lock() {
+        let room = shared.capacity.saturating_sub(free.len());
+        free.extend(buffers.take(room));
+    }
+}
+
+/// The cache of the pool `pool` in `locals`, which is created if the pool is still alive.
+fn local<'a>(
+    locals: &'a mut Vec<Local>,
+    pool: &Weak<Shared>,
+    capacity: usize,
+) -> Option<&'a mut Local> {
+    match locals.iter().position(|local| local.is_for(pool.as_ptr())) {
+        Some(index) => Some(&mut locals[index]),
+        None if pool.strong_count() > 0 => Some(insert_local(locals, pool.clone(), capacity)),
+        None => None,
+    }
+}
+
+fn insert_local(locals: &mut Vec<Local>, pool: Weak<Shared>, capacity: usize) -> &mut Local {
+    // Free the caches of dropped pools while we're here.
+    locals.retain(|local| local.pool.strong_count() > 0);
+    locals.push(Local::new(pool, capacity));
+    locals.last_mut().unwrap()
+}
+
+impl SymbolPool {
+    ///
+    /// Create a pool of symbols in the namespace `namespace_name`, keeping up to 1024 free buffers.
+    ///
+    pub fn new(namespace_name: &'static str) -> Self {
+        Self::with_capacity(namespace_name, 1024)
+    }
+
+    ///
+    /// Create a pool of symbols in the namespace `namespace_name`, keeping up to `capacity` free buffers
+    /// shared by all threads.
+    ///
+    /// Each thread also keeps up to `capacity` or 32 free buffers of its own, whichever is fewer.
+    ///
+    pub fn with_capacity(namespace_name: &'static str, capacity: usize) -> Self {
+        Self {
+            namespace_name,
+            shared: Arc::new(Shared {
+                capacity,
+                free: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    ///
+    /// The namespace of the pool's symbols.
+    ///
+    pub fn namespace_name(&self) -> &'static str {
+        self.namespace_name
+    }
+
+    ///
+    /// Create the symbol `name`, reusing a [recycled](SymbolPool::recycle) symbol or the buffer of a dropped symbol
+    /// if there is one.
+    ///
+    pub fn symbol(&self, name: &str) -> Symbol {
+        let reused = LOCAL
+            .try_with(|locals| {
+                let mut locals = locals.try_borrow_mut().ok()?;
+                let local = self.local(&mut locals);
+                Some(match local.symbols.pop() {
+                    Some(instance) => Ok(instance),
+                    None => Err(local.pop_buffer(&self.shared)),
+                })
+            })
+            .ok()
+            .flatten();
+
+        let mut buffer = match reused {
+            Some(Ok(mut instance)) => {
+                if let Some(pooled) = Arc::get_mut(&mut instance)
+                    .and_then(|instance| instance.as_any_mut().downcast_mut::<Pooled>())
+                {
+                    pooled.name.clear();
+                    pooled.name.push_str(name);
+                    return Symbol::Dynamic(instance);
+                }
+                String::new()
+            }
+            Some(Err(buffer)) => buffer,
+            None => String::new(),
+        };
+        buffer.push_str(name);
+
+        Symbol::Dynamic(Arc::new(Pooled {
+            namespace_name: self.namespace_name,
+            name: buffer,
+            pool: Arc::downgrade(&self.shared),
+            local_capacity: self.shared.local_capacity(),
+        }))
+    }
+
+    ///
+    /// Drop `symbol`, keeping its whole allocation for the next [symbol](SymbolPool::symbol) created on this thread,
+    /// if it is a symbol of this pool without other clones.
+    ///
+    /// Other symbols are dropped as usual.
+    ///
+    pub fn recycle(&self, symbol: Symbol) {
+        let mut instance = match symbol {
+            Symbol::Dynamic(instance) => instance,
+            _ => return,
+        };
+        let is_recyclable = Arc::get_mut(&mut instance)
+            .and_then(|instance| instance.as_any_mut().downcast_mut::<Pooled>())
+            .is_some_and(|pooled| pooled.pool.as_ptr() == Arc::as_ptr(&self.shared));
+        if !is_recyclable {
+            return;
+        }
+
+        // A rejected symbol is dropped after the cache is released, so that it can return its buffer.
+        let _rejected = LOCAL.try_with(move |locals| {
+            let mut locals = match locals.try_borrow_mut() {
+                Ok(locals) => locals,
+                Err(_) => return Some(instance),
+            };
+            let local = self.local(&mut locals);
+            if local.symbols.len() < local.capacity {
+                local.symbols.push(instance);
+                None
+            } else {
+                Some(instance)
+            }
+        });
+    }
+
+    /// This thread's cache of the pool.
+    fn local<'a>(&self, locals: &'a mut Vec<Local>) -> &'a mut Local {
+        match locals
+            .iter()
+            .position(|local| local.is_for(Arc::as_ptr(&self.shared)))
+        {
+            Some(index) => &mut locals[index],
+            None => insert_local(
+                locals,
+                Arc::downgrade(&self.shared),
+                self.shared.local_capacity(),
+            ),
+        }
+    }
+
+    ///
+    /// The number of free buffers available to this thread, returned by dropped symbols:
+    /// those in the shared free list and those kept by this thread.
+    ///
+    pub fn free_buffers(&self) -> usize {
+        let local = LOCAL
+            .try_with(|locals| {
+                locals.try_borrow().map_or(0, |locals| {
+                    locals
+                        .iter()
+                        .find(|local| local.is_for(Arc::as_ptr(&self.shared)))
+                        .map_or(0, |local| local.buffers.len())
+                })
+            })
+            .unwrap_or(0);
+
+        local + self.shared.free.lock().map_or(0, |free| free.len())
+    }
+}
+
+/// Free this thread's cache of the pool. Other threads free theirs the next time they use a pool, or when they exit.
+impl Drop for SymbolPool {
+    fn drop(&mut self) {
+        let _ = LOCAL.try_with(|locals| {
+            let mut locals = locals.try_borrow_mut().ok()?;
+            let index = locals
+                .iter()
+                .position(|local| local.is_for(Arc::as_ptr(&self.shared)))?;
+            let mut local = locals.swap_remove(index);
+            local.buffers.clear();
+            // Dropped while the cache is borrowed, so the recycled symbols can't cache their buffers again.
+            drop(local);
+            Some(())
+        });
+    }
+}
+
+///
+/// The dynamic namespace of pooled symbols.
+///
+/// Two pooled symbols are equal when they have the same namespace name and name, even if they were created by
+/// different [SymbolPool]s.
+///
+#[derive(Clone, Debug)]
+pub struct Pooled {
+    namespace_name: &'static str,
+    name: String,
+    pool: Weak<Shared>,
+    /// The number of buffers each thread keeps for the pool, known without upgrading `pool`.
+    local_capacity: usize,
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        if self.name.capacity() == 0 {
+            return;
+        }
+        let mut buffer = Some(std::mem::take(&mut self.name));
+
+        // This thread's cache, unless the thread is exiting or the cache is in use further up the stack.
+        let _ = LOCAL.try_with(|locals| {
+            let mut locals = locals.try_borrow_mut().ok()?;
+            let local = local(&mut locals, &self.pool, self.local_capacity)?;
+            local.push_buffer(buffer.take()?);
+            Some(())
+        });
+
+        if let Some(mut buffer) = buffer {
+            buffer.clear();
+            if let Some(shared) = self.pool.upgrade() {
+                spill(&shared, std::iter::once(buffer));
+            }
+        }
+    }
+}
+
+impl namespace::Dynamic for Pooled {
+    fn namespace_name(&self) -> &str {
+        self.namespace_name
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.name
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        (self.namespace_name, &self.name) == (rhs.namespace_name, &rhs.name)
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> std::cmp::Ordering {
+        let rhs = rhs.downcast_ref::<Self>().unwrap();
+        (self.namespace_name, &self.name).cmp(&(rhs.namespace_name, &rhs.name))
+    }
+
+    fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+        state.write(self.namespace_name.as_bytes());
+        state.write_u8(0xff);
+        state.write(self.name.as_bytes());
+        state.write_u8(0xff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::hash::BuildHasher;
+
+    fn buffer(symbol: &Symbol) -> *const u8 {
+        symbol.name().as_ptr()
+    }
+
+    #[test]
+    fn test_reuse() {
+        let pool = SymbolPool::new("pool");
+
+        let foo = pool.symbol("foo");
+        let foo_buffer = buffer(&foo);
+        let bar = pool.symbol("bar");
+        assert_ne!(buffer(&bar), foo_buffer);
+
+        drop(foo);
+        assert_eq!(pool.free_buffers(), 1);
+        let baz = pool.symbol("baz");
+        assert_eq!(buffer(&baz), foo_buffer);
+        assert_eq!(baz.name(), "baz");
+        assert_eq!(bar.name(), "bar");
+        assert_eq!(pool.free_buffers(), 0);
+    }
+
+    #[test]
+    fn test_reuse_many() {
+        let pool = SymbolPool::new("pool");
+
+        let symbols: Vec<_> = (0..100).map(|i| pool.symbol(&i.to_string())).collect();
+        let buffers: HashSet<_> = symbols.iter().map(buffer).collect();
+        drop(symbols);
+        assert_eq!(pool.free_buffers(), 100);
+
+        let symbols: Vec<_> = (0..100).map(|i| pool.symbol(&i.to_string())).collect();
+        assert!(symbols
+            .iter()
+            .all(|symbol| buffers.contains(&buffer(symbol))));
+        assert_eq!(pool.free_buffers(), 0);
+    }
+
+    #[test]
+    fn test_clones_are_not_reused() {
+        let pool = SymbolPool::new("pool");
+
+        let foo = pool.symbol("foo");
+        let foo_clone = foo.clone();
+        drop(foo);
+
+        assert_eq!(pool.free_buffers(), 0);
+        assert_ne!(buffer(&pool.symbol("bar")), buffer(&foo_clone));
+        assert_eq!(foo_clone.name(), "foo");
+    }
+
+    #[test]
+    fn test_capacity() {
+        let pool = SymbolPool::with_capacity("pool", 1);
+        let symbols: Vec<_> = (0..3).map(|i| pool.symbol(&i.to_string())).collect();
+        drop(symbols);
+
+        // One kept by this thread, and one in the shared free list.
+        assert_eq!(pool.free_buffers(), 2);
+    }
+
+    #[test]
+    fn test_drop_on_other_thread() {
+        let pool = SymbolPool::new("pool");
+        let foo = pool.symbol("foo");
+
+        std::thread::spawn(move || drop(foo)).join().unwrap();
+        assert_eq!(pool.free_buffers(), 1);
+    }
+
+    #[test]
+    fn test_symbol_semantics() {
+        let random_state = std::collections::hash_map::RandomState::new();
+        let a = SymbolPool::new("pool").symbol("foo");
+        let b = SymbolPool::new("pool").symbol("foo");
+
+        assert_eq!(a, b);
+        assert_eq!(random_state.hash_one(&a), random_state.hash_one(&b));
+        assert_ne!(a, SymbolPool::new("other").symbol("foo"));
+        assert_eq!(format!("{:?}", a), "pool::foo");
+    }
+
+    #[test]
+    fn test_dropped_pool() {
+        let pool = SymbolPool::new("pool");
+        let foo = pool.symbol("foo");
+        let shared = Arc::downgrade(&pool.shared);
+        drop(pool);
+
+        assert!(shared.upgrade().is_none());
+        assert_eq!(foo.name(), "foo");
+    }
+
+    fn instance(symbol: &Symbol) -> *const Pooled {
+        symbol.downcast_dyn::<Pooled>().unwrap()
+    }
+
+    fn cached_pools() -> usize {
+        LOCAL.with(|locals| locals.borrow().len())
+    }
+
+    #[test]
+    fn test_recycle() {
+        let pool = SymbolPool::new("pool");
+
+        let foo = pool.symbol("foo");
+        let foo_instance = instance(&foo);
+        pool.recycle(foo);
+        let bar = pool.symbol("bar");
+        assert_eq!(instance(&bar), foo_instance);
+        assert_eq!(bar.name(), "bar");
+        assert_eq!(bar, SymbolPool::new("pool").symbol("bar"));
+
+        // Shared and foreign symbols are dropped as usual.
+        let bar_instance = instance(&bar);
+        pool.recycle(bar.clone());
+        assert_ne!(instance(&pool.symbol("baz")), bar_instance);
+        pool.recycle(SymbolPool::new("pool").symbol("qux"));
+        pool.recycle(crate::tests::STATIC_A_0);
+        assert_ne!(instance(&pool.symbol("baz")), bar_instance);
+    }
+
+    #[test]
+    fn test_spill_to_other_threads() {
+        let pool = Arc::new(SymbolPool::new("pool"));
+
+        let symbols: Vec<_> = (0..100).map(|i| pool.symbol(&i.to_string())).collect();
+        let buffers: HashSet<_> = symbols.iter().map(buffer).collect();
+        std::thread::spawn(move || drop(symbols)).join().unwrap();
+        assert_eq!(pool.free_buffers(), 100);
+
+        let thread_pool = pool.clone();
+        let symbols = std::thread::spawn(move || {
+            (0..100)
+                .map(|i| thread_pool.symbol(&i.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .join()
+        .unwrap();
+        assert!(symbols
+            .iter()
+            .all(|symbol| buffers.contains(&buffer(symbol))));
+    }
+
+    #[test]
+    fn test_dropped_pool_frees_caches() {
+        let pool = SymbolPool::new("pool");
+        let cached_pools_before = cached_pools();
+        let recycled = pool.symbol("foo");
+        pool.recycle(recycled);
+        drop(pool.symbol("bar"));
+        assert_eq!(cached_pools(), cached_pools_before + 1);
+
+        let (dropped_tx, dropped_rx) = std::sync::mpsc::channel();
+        let (used_tx, used_rx) = std::sync::mpsc::channel::<()>();
+        let symbol = pool.symbol("baz");
+        let thread = std::thread::spawn(move || {
+            drop(symbol);
+            let cached_before_drop = cached_pools();
+            dropped_tx.send(()).unwrap();
+            used_rx.recv().unwrap();
+
+            let other = SymbolPool::new("other");
+            drop(other.symbol("foo"));
+            (cached_before_drop, cached_pools())
+        });
+
+        dropped_rx.recv().unwrap();
+        drop(pool);
+        assert_eq!(cached_pools(), cached_pools_before);
+        used_tx.send(()).unwrap();
+        // The cache of the dropped pool was replaced by that of `other`.
+        assert_eq!(thread.join().unwrap(), (1, 1));
+    }
+}
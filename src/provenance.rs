@@ -0,0 +1,175 @@
+//!
+//! Creation-site tracking of dynamic symbols, for finding out where a symbol came from.
+//!
+//! Dynamic symbols created with [Symbol::new_dynamic] or [Symbol::new_dyn_value] record the source location
+//! of the call, and a sequence number counting the symbols created so far:
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+//! struct Word(String);
+//!
+//! impl namespace::DynValue for Word {
+//!     fn namespace_name(&self) -> &str {
+//!         "word"
+//!     }
+//!
+//!     fn symbol_name(&self) -> &str {
+//!         &self.0
+//!     }
+//! }
+//!
+//! let foo = Symbol::new_dyn_value(Word("foo".into()));
+//! let provenance = foo.clone().provenance().unwrap();
+//!
+//! assert_eq!(provenance.location().line(), line!() - 3);
+//! assert!(Symbol::from(namespace::DynWrap(Word("bar".into()))).provenance().is_none());
+//! ```
+//!
+//! The provenance belongs to the shared instance, so clones of a symbol have the same provenance.
+//! Operations that copy the instance, like [Symbol::downcast_dyn_mut] of a shared symbol, lose it.
+//!
+
+use crate::{namespace, Symbol};
+
+use std::collections::HashMap;
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock, Weak};
+
+///
+/// Where and when a dynamic symbol was created.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    location: &'static Location<'static>,
+    sequence: u64,
+}
+
+impl Provenance {
+    ///
+    /// The source location of the constructor call.
+    ///
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    ///
+    /// The number of symbols with recorded provenance created before this one, in this process.
+    ///
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{} at {}", self.sequence, self.location)
+    }
+}
+
+/// Entries are keyed by instance address. The weak reference keeps the address from being reused
+/// by another instance while the entry exists.
+struct Entry {
+    instance: Weak<dyn namespace::Dynamic>,
+    provenance: Provenance,
+}
+
+/// Dead entries are swept when the table has doubled since the last sweep.
+#[derive(Default)]
+struct Table {
+    entries: HashMap<usize, Entry>,
+    sweep_at: usize,
+}
+
+fn table() -> &'static RwLock<Table> {
+    static TABLE: OnceLock<RwLock<Table>> = OnceLock::new();
+    TABLE.get_or_init(Default::default)
+}
+
+fn address(instance: &Arc<dyn namespace::Dynamic>) -> usize {
+    Arc::as_ptr(instance) as *const () as usize
+}
+
+pub(crate) fn record(symbol: &Symbol, location: &'static Location<'static>) {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+    if let Symbol::Dynamic(instance) = symbol {
+        let provenance = Provenance {
+            location,
+            sequence: SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        };
+
+        let mut table = table().write().unwrap();
+        if table.entries.len() >= table.sweep_at {
+            table
+                .entries
+                .retain(|_, entry| entry.instance.strong_count() > 0);
+            table.sweep_at = (table.entries.len() * 2).max(64);
+        }
+
+        table.entries.insert(
+            address(instance),
+            Entry {
+                instance: Arc::downgrade(instance),
+                provenance,
+            },
+        );
+    }
+}
+
+impl Symbol {
+    ///
+    /// Where this dynamic symbol was created, if it was created by [Symbol::new_dynamic] or [Symbol::new_dyn_value].
+    ///
+    pub fn provenance(&self) -> Option<Provenance> {
+        let instance = match self {
+            Self::Dynamic(instance) => instance,
+            _ => return None,
+        };
+
+        table()
+            .read()
+            .unwrap()
+            .entries
+            .get(&address(instance))
+            .map(|entry| entry.provenance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::dynamic::{TestDynamic, Wrapped};
+    use crate::tests::STATIC_A_0;
+
+    #[test]
+    fn test_provenance() {
+        let line = line!() + 1;
+        let foo = Symbol::new_dynamic(TestDynamic::<0>("foo".into(), "dyn0"));
+        let bar = Symbol::new_dyn_value(Wrapped("bar".into()));
+
+        let foo_provenance = foo.provenance().unwrap();
+        assert_eq!(foo_provenance.location().file(), file!());
+        assert_eq!(foo_provenance.location().line(), line);
+        assert_eq!(bar.provenance().unwrap().location().line(), line + 1);
+        assert!(bar.provenance().unwrap().sequence() > foo_provenance.sequence());
+
+        assert_eq!(foo.clone().provenance(), Some(foo_provenance));
+        assert_eq!(crate::tests::dynamic::sym0("foo").provenance(), None);
+        assert_eq!(STATIC_A_0.provenance(), None);
+    }
+
+    #[test]
+    fn test_provenance_of_dropped_instance() {
+        for _ in 0..200 {
+            drop(Symbol::new_dyn_value(Wrapped("tmp".into())));
+        }
+
+        // Dropped instances are swept:
+        let symbol = crate::tests::dynamic::wrapped("tmp");
+        assert_eq!(symbol.provenance(), None);
+        assert!(table().read().unwrap().entries.len() < 200);
+    }
+}
@@ -0,0 +1,408 @@
+//!
+//! Global namespace registry, for resolving qualified symbol names back into [Symbol]s.
+//!
+//! A qualified name has the form `namespace::symbol`, like in the [Debug](std::fmt::Debug) output of [Symbol].
+//! Static namespaces are registered with [register], and resolve names using
+//! [symbol_by_name](crate::namespace::Static::symbol_by_name). Dynamic namespaces are registered with
+//! [register_dynamic] and a constructor closure. [Wide](crate::wide) namespaces are registered with [register_wide].
+//! When several are registered under the same namespace name, the static namespace is tried first,
+//! then the wide namespace, and the dynamic constructor acts as a fallback.
+//!
+//! ```
+//! use dyn_symbol::*;
+//! use std::str::FromStr;
+//!
+//! struct MyStaticNS;
+//!
+//! impl namespace::Static for MyStaticNS {
+//!     fn namespace_name(&self) -> &str {
+//!         "my"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["foo", "bar"][id as usize]
+//!     }
+//!
+//!     fn symbol_by_name(&self, name: &str) -> Option<u32> {
+//!         match name {
+//!             "foo" => Some(0),
+//!             "bar" => Some(1),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! registry::register(&MyStaticNS);
+//!
+//! assert_eq!(Symbol::from_str("my::bar").unwrap(), Symbol::Static(&MyStaticNS, 1));
+//! assert!(Symbol::from_str("my::baz").is_err());
+//! assert!(Symbol::from_str("unknown::bar").is_err());
+//! ```
+//!
+
+use crate::{indexed, namespace, validation, wide, Symbol};
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+type ConstructorFn = dyn Fn(&str) -> Option<Symbol> + Send + Sync;
+
+#[derive(Default)]
+struct Entry {
+    static_ns: Option<&'static dyn namespace::Static>,
+    wide_ns: Option<&'static wide::WideNamespace>,
+    constructor: Option<Arc<ConstructorFn>>,
+}
+
+fn entries() -> &'static RwLock<HashMap<String, Entry>> {
+    static ENTRIES: OnceLock<RwLock<HashMap<String, Entry>>> = OnceLock::new();
+    ENTRIES.get_or_init(Default::default)
+}
+
+fn uids() -> &'static RwLock<HashMap<u128, &'static dyn namespace::Static>> {
+    static UIDS: OnceLock<RwLock<HashMap<u128, &'static dyn namespace::Static>>> = OnceLock::new();
+    UIDS.get_or_init(Default::default)
+}
+
+fn wide_uids() -> &'static RwLock<HashMap<u128, &'static wide::WideNamespace>> {
+    static UIDS: OnceLock<RwLock<HashMap<u128, &'static wide::WideNamespace>>> = OnceLock::new();
+    UIDS.get_or_init(Default::default)
+}
+
+fn indexed_bases() -> &'static RwLock<HashMap<(u128, u32), &'static indexed::IndexedBase>> {
+    static BASES: OnceLock<RwLock<HashMap<(u128, u32), &'static indexed::IndexedBase>>> =
+        OnceLock::new();
+    BASES.get_or_init(Default::default)
+}
+
+///
+/// Register a static namespace under its [namespace_name](crate::namespace::Static::namespace_name).
+///
+/// Registering another static namespace with the same name replaces the old one.
+/// Namespaces with a [namespace_uid](crate::namespace::Static::namespace_uid) can also be looked up by it.
+/// The namespace is also [registered for validation](crate::validation::register).
+///
+pub fn register(ns: &'static dyn namespace::Static) {
+    validation::register(ns);
+    if let Some(uid) = ns.namespace_uid() {
+        uids().write().unwrap().insert(uid, ns);
+    }
+
+    entries()
+        .write()
+        .unwrap()
+        .entry(ns.namespace_name().into())
+        .or_default()
+        .static_ns = Some(ns);
+}
+
+///
+/// Register a wide namespace under its [namespace_name](crate::namespace::StaticWide::namespace_name).
+///
+/// Registering another wide namespace with the same name replaces the old one.
+/// Namespaces with a [namespace_uid](crate::namespace::StaticWide::namespace_uid) can also be looked up by it.
+/// The namespace is also [registered for validation](crate::validation::register_wide).
+///
+pub fn register_wide(ns: &'static wide::WideNamespace) {
+    validation::register_wide(ns.namespace());
+    if let Some(uid) = ns.namespace().namespace_uid() {
+        wide_uids().write().unwrap().insert(uid, ns);
+    }
+
+    entries()
+        .write()
+        .unwrap()
+        .entry(ns.namespace().namespace_name().into())
+        .or_default()
+        .wide_ns = Some(ns);
+}
+
+///
+/// Register the base of [indexed](crate::indexed) symbols, so that it can be looked up with [indexed_by_uid].
+///
+/// Indexed symbols refer to a `'static` base, which can't be created when resolving them, e.g. from a
+/// [StableSymbol](crate::stable_abi::StableSymbol). Only bases in namespaces with a
+/// [namespace_uid](crate::namespace::Static::namespace_uid) are registered.
+///
+pub fn register_indexed(base: &'static indexed::IndexedBase) {
+    if let Some(uid) = base.namespace().namespace_uid() {
+        indexed_bases()
+            .write()
+            .unwrap()
+            .insert((uid, base.id()), base);
+    }
+}
+
+///
+/// Register a constructor for dynamic symbols in the namespace named `namespace_name`.
+///
+/// The constructor receives the symbol name, and returns `None` if it's not a valid name for this namespace.
+/// Registering another constructor with the same name replaces the old one.
+///
+pub fn register_dynamic<F>(namespace_name: &str, constructor: F)
+where
+    F: Fn(&str) -> Option<Symbol> + Send + Sync + 'static,
+{
+    entries()
+        .write()
+        .unwrap()
+        .entry(namespace_name.into())
+        .or_default()
+        .constructor = Some(Arc::new(constructor));
+}
+
+///
+/// Look up a registered static namespace by its [namespace_uid](crate::namespace::Static::namespace_uid).
+///
+pub fn static_by_uid(uid: u128) -> Option<&'static dyn namespace::Static> {
+    uids().read().unwrap().get(&uid).copied()
+}
+
+///
+/// Look up a registered wide namespace by its [namespace_uid](crate::namespace::StaticWide::namespace_uid).
+///
+pub fn wide_by_uid(uid: u128) -> Option<&'static wide::WideNamespace> {
+    wide_uids().read().unwrap().get(&uid).copied()
+}
+
+///
+/// Look up a registered [indexed](crate::indexed) base by the uid of its namespace and its `id`.
+///
+pub fn indexed_by_uid(uid: u128, id: u32) -> Option<&'static indexed::IndexedBase> {
+    indexed_bases().read().unwrap().get(&(uid, id)).copied()
+}
+
+///
+/// Resolve a symbol from its namespace name and symbol name.
+///
+pub fn resolve(namespace_name: &str, symbol_name: &str) -> Result<Symbol, ParseSymbolError> {
+    let (static_ns, wide_ns, constructor) = {
+        let entries = entries().read().unwrap();
+        let entry = entries
+            .get(namespace_name)
+            .ok_or_else(|| ParseSymbolError::UnknownNamespace(namespace_name.into()))?;
+
+        (entry.static_ns, entry.wide_ns, entry.constructor.clone())
+    };
+
+    static_ns
+        .and_then(|ns| {
+            ns.symbol_by_name(symbol_name)
+                .map(|id| Symbol::Static(ns, id))
+        })
+        .or_else(|| wide_ns.and_then(|ns| ns.resolve(symbol_name)))
+        .or_else(|| constructor.and_then(|constructor| constructor(symbol_name)))
+        .ok_or_else(|| ParseSymbolError::UnknownSymbol {
+            namespace_name: namespace_name.into(),
+            symbol_name: symbol_name.into(),
+        })
+}
+
+///
+/// Error returned when a qualified symbol name cannot be resolved.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSymbolError {
+    /// The string is not of the form `namespace::symbol`.
+    MissingSeparator,
+    /// No namespace is registered under this name.
+    UnknownNamespace(String),
+    /// The namespace does not recognize the symbol name.
+    UnknownSymbol {
+        namespace_name: String,
+        symbol_name: String,
+    },
+}
+
+impl std::fmt::Display for ParseSymbolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingSeparator => write!(f, "expected a qualified `namespace::symbol` name"),
+            Self::UnknownNamespace(namespace_name) => {
+                write!(f, "unknown namespace `{}`", namespace_name)
+            }
+            Self::UnknownSymbol {
+                namespace_name,
+                symbol_name,
+            } => write!(
+                f,
+                "unknown symbol `{}` in namespace `{}`",
+                symbol_name, namespace_name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseSymbolError {}
+
+///
+/// Parse a qualified `namespace::symbol` name, using the global registry.
+/// The string is split at the first `::`.
+///
+impl std::str::FromStr for Symbol {
+    type Err = ParseSymbolError;
+
+    fn from_str(qualified_name: &str) -> Result<Self, Self::Err> {
+        let (namespace_name, symbol_name) = qualified_name
+            .split_once("::")
+            .ok_or(ParseSymbolError::MissingSeparator)?;
+
+        resolve(namespace_name, symbol_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    struct Html;
+
+    impl namespace::Static for Html {
+        fn namespace_name(&self) -> &str {
+            "registry_html"
+        }
+
+        fn symbol_count(&self) -> u32 {
+            2
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["class", "id"][id as usize]
+        }
+
+        fn symbol_by_name(&self, name: &str) -> Option<u32> {
+            ["class", "id"]
+                .iter()
+                .position(|n| *n == name)
+                .map(|id| id as u32)
+        }
+    }
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Data(String);
+
+    impl namespace::DynValue for Data {
+        fn namespace_name(&self) -> &str {
+            "registry_html"
+        }
+
+        fn symbol_name(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn test_static_with_dynamic_fallback() {
+        register(&Html);
+        register_dynamic("registry_html", |name| {
+            if name.starts_with("data-") {
+                Some(Symbol::from(namespace::DynWrap(Data(name.into()))))
+            } else {
+                None
+            }
+        });
+
+        assert_eq!(
+            Symbol::from_str("registry_html::id").unwrap(),
+            Symbol::Static(&Html, 1)
+        );
+        assert_eq!(
+            Symbol::from_str("registry_html::data-foo").unwrap(),
+            Symbol::from(namespace::DynWrap(Data("data-foo".into())))
+        );
+        assert_eq!(
+            Symbol::from_str("registry_html::style"),
+            Err(ParseSymbolError::UnknownSymbol {
+                namespace_name: "registry_html".into(),
+                symbol_name: "style".into(),
+            })
+        );
+    }
+
+    struct Cells;
+
+    impl namespace::StaticWide for Cells {
+        fn namespace_name(&self) -> &str {
+            "registry_cells"
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            Some(0xce11)
+        }
+
+        fn symbol_name(&self, _: u64) -> &str {
+            "cell"
+        }
+
+        fn symbol_by_name(&self, name: &str) -> Option<u64> {
+            name.strip_prefix("cell-")?.parse().ok()
+        }
+    }
+
+    static CELLS: wide::WideNamespace = wide::WideNamespace::new(&Cells);
+
+    #[test]
+    fn test_wide() {
+        register_wide(&CELLS);
+
+        assert_eq!(
+            Symbol::from_str("registry_cells::cell-12345678901").unwrap(),
+            CELLS.symbol(12345678901)
+        );
+        assert!(Symbol::from_str("registry_cells::row-1").is_err());
+        assert!(wide_by_uid(0xce11).is_some());
+    }
+
+    #[test]
+    fn test_indexed() {
+        struct Uid;
+
+        impl namespace::Static for Uid {
+            fn namespace_name(&self) -> &str {
+                "registry_uid"
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(0x1d)
+            }
+
+            fn symbol_count(&self) -> u32 {
+                1
+            }
+
+            fn symbol_name(&self, _: u32) -> &str {
+                "row"
+            }
+        }
+
+        static ROW: indexed::IndexedBase = indexed::IndexedBase::new(&Uid, 0);
+        static CLASS: indexed::IndexedBase = indexed::IndexedBase::new(&Html, 0);
+        register_indexed(&ROW);
+        register_indexed(&CLASS);
+
+        assert_eq!(
+            indexed_by_uid(0x1d, 0).map(|base| base.index(3)),
+            Some(ROW.index(3))
+        );
+        assert!(indexed_by_uid(0x1d, 1).is_none());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Symbol::from_str("no_separator"),
+            Err(ParseSymbolError::MissingSeparator)
+        );
+        assert_eq!(
+            Symbol::from_str("registry_unknown::foo"),
+            Err(ParseSymbolError::UnknownNamespace(
+                "registry_unknown".into()
+            ))
+        );
+    }
+}
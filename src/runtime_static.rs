@@ -0,0 +1,247 @@
+//!
+//! Static namespaces created at runtime, for symbol tables loaded from configuration or schema files.
+//!
+//! [load] takes an owned symbol table and leaks it, returning a `&'static` [RuntimeNamespace]. Its symbols
+//! are ordinary [Symbol::Static] symbols, which are compared by `id` and created without memory allocation:
+//!
+//! ```
+//! use dyn_symbol::*;
+//! use std::str::FromStr;
+//!
+//! let schema = vec!["id".to_string(), "title".to_string()];
+//! let ns = runtime_static::load("schema", schema).unwrap();
+//!
+//! let title = ns.symbol("title").unwrap();
+//! assert_eq!(title, Symbol::Static(ns, 1));
+//! assert_eq!(format!("{:?}", title), "schema::title");
+//!
+//! // Loaded namespaces can be looked up by name, and are also registered in the registry:
+//! assert!(std::ptr::eq(runtime_static::get("schema").unwrap(), ns));
+//! assert_eq!(Symbol::from_str("schema::id").unwrap(), ns.symbol("id").unwrap());
+//! ```
+//!
+//! Runtime namespaces are identified by their namespace name, through a
+//! [namespace_uid](crate::namespace::Static::namespace_uid) derived from it.
+//! Loading the same table under the same name again returns the namespace loaded first, and a namespace
+//! name can't be loaded with a different table. Leaked tables are never freed, so namespaces should be loaded once,
+//! at startup.
+//!
+
+use crate::{namespace, registry, Symbol};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{OnceLock, RwLock};
+
+///
+/// A static namespace created at runtime. See [load].
+///
+pub struct RuntimeNamespace {
+    name: String,
+    uid: u128,
+    names: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+impl RuntimeNamespace {
+    ///
+    /// The symbol with the given name.
+    ///
+    pub fn symbol(&'static self, name: &str) -> Option<Symbol> {
+        self.ids.get(name).map(|id| Symbol::Static(self, *id))
+    }
+
+    ///
+    /// The symbol with the given `id`, which is its position in the loaded table.
+    ///
+    pub fn symbol_by_id(&'static self, id: u32) -> Option<Symbol> {
+        if (id as usize) < self.names.len() {
+            Some(Symbol::Static(self, id))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// The symbol names, in `id` order.
+    ///
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+impl namespace::Static for RuntimeNamespace {
+    fn namespace_name(&self) -> &str {
+        &self.name
+    }
+
+    fn namespace_uid(&self) -> Option<u128> {
+        Some(self.uid)
+    }
+
+    fn symbol_count(&self) -> u32 {
+        self.names.len() as u32
+    }
+
+    fn symbol_name(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    fn symbol_by_name(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+}
+
+impl std::fmt::Debug for RuntimeNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("RuntimeNamespace")
+            .field("name", &self.name)
+            .field("names", &self.names)
+            .finish()
+    }
+}
+
+fn namespaces() -> &'static RwLock<HashMap<String, &'static RuntimeNamespace>> {
+    static NAMESPACES: OnceLock<RwLock<HashMap<String, &'static RuntimeNamespace>>> =
+        OnceLock::new();
+    NAMESPACES.get_or_init(Default::default)
+}
+
+/// FNV-1a, so that uids don't depend on the standard library's hasher.
+fn uid_of(namespace_name: &str) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    "dyn_symbol::runtime_static::"
+        .bytes()
+        .chain(namespace_name.bytes())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u128).wrapping_mul(PRIME)
+        })
+}
+
+///
+/// Load a symbol table as a static namespace named `namespace_name`. The `id` of each symbol is its position in `names`.
+///
+/// The namespace is also [registered](crate::registry::register) in the registry.
+///
+pub fn load(
+    namespace_name: &str,
+    names: Vec<String>,
+) -> Result<&'static RuntimeNamespace, RuntimeStaticError> {
+    let mut namespaces = namespaces().write().unwrap();
+    if let Some(ns) = namespaces.get(namespace_name) {
+        return if ns.names == names {
+            Ok(*ns)
+        } else {
+            Err(RuntimeStaticError::Conflict(namespace_name.into()))
+        };
+    }
+
+    if u32::try_from(names.len()).is_err() {
+        return Err(RuntimeStaticError::TooManySymbols);
+    }
+
+    let mut ids = HashMap::with_capacity(names.len());
+    for (id, name) in names.iter().enumerate() {
+        if ids.insert(name.clone(), id as u32).is_some() {
+            return Err(RuntimeStaticError::DuplicateName(name.clone()));
+        }
+    }
+
+    let ns: &'static RuntimeNamespace = Box::leak(Box::new(RuntimeNamespace {
+        name: namespace_name.into(),
+        uid: uid_of(namespace_name),
+        names,
+        ids,
+    }));
+
+    namespaces.insert(namespace_name.into(), ns);
+    registry::register(ns);
+
+    Ok(ns)
+}
+
+///
+/// Look up a loaded namespace by its name.
+///
+pub fn get(namespace_name: &str) -> Option<&'static RuntimeNamespace> {
+    namespaces().read().unwrap().get(namespace_name).copied()
+}
+
+///
+/// Error returned when a symbol table cannot be loaded.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuntimeStaticError {
+    /// The table contains this symbol name more than once.
+    DuplicateName(String),
+    /// A namespace with this name is already loaded, with a different table.
+    Conflict(String),
+    /// The table has more symbols than fit in a `u32` id.
+    TooManySymbols,
+}
+
+impl std::fmt::Display for RuntimeStaticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::DuplicateName(name) => write!(f, "duplicate symbol name `{}`", name),
+            Self::Conflict(namespace_name) => write!(
+                f,
+                "namespace `{}` is already loaded with a different table",
+                namespace_name
+            ),
+            Self::TooManySymbols => write!(f, "too many symbols"),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeStaticError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::STATIC_A_0;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_load() {
+        let colors = load("test_colors", names(&["red", "green"])).unwrap();
+        let sizes = load("test_sizes", names(&["small", "large"])).unwrap();
+
+        assert_eq!(colors.symbol("green"), colors.symbol_by_id(1));
+        assert_eq!(colors.symbol("blue"), None);
+        assert_eq!(colors.symbol_by_id(2), None);
+        assert_eq!(colors.names(), ["red", "green"]);
+
+        // Same type, but different namespaces:
+        assert_ne!(colors.symbol_by_id(0), sizes.symbol_by_id(0));
+        assert_ne!(colors.symbol_by_id(0).unwrap(), STATIC_A_0);
+
+        let again = load("test_colors", names(&["red", "green"])).unwrap();
+        assert!(std::ptr::eq(again, colors));
+        assert_eq!(
+            get("test_sizes").unwrap().symbol("large"),
+            sizes.symbol("large")
+        );
+        assert!(get("test_unknown").is_none());
+    }
+
+    #[test]
+    fn test_load_errors() {
+        assert_eq!(
+            load("test_duplicates", names(&["a", "b", "a"])).err(),
+            Some(RuntimeStaticError::DuplicateName("a".into()))
+        );
+        assert!(get("test_duplicates").is_none());
+
+        load("test_conflict", names(&["a"])).unwrap();
+        assert_eq!(
+            load("test_conflict", names(&["a", "b"])).err(),
+            Some(RuntimeStaticError::Conflict("test_conflict".into()))
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//!
+//! Namespace-driven `serde` support for [Symbol].
+//!
+//! A [Symbol] is serialized as a `(namespace_name, representation)` pair, where the representation
+//! is provided by the namespace's `serialize_symbol` method. Deserialization looks up a deserializer
+//! registered for the namespace name, and passes it the representation.
+//!
+//! Namespaces that don't implement `serialize_symbol`, or that have no registered deserializer, fail to ser/de.
+//!
+//! ```
+//! use dyn_symbol::*;
+//!
+//! struct MyStaticNS;
+//!
+//! impl namespace::Static for MyStaticNS {
+//!     fn namespace_name(&self) -> &str {
+//!         "my"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["foo", "bar"][id as usize]
+//!     }
+//!
+//!     fn serialize_symbol(&self, id: u32) -> Option<std::borrow::Cow<'_, str>> {
+//!         Some(self.symbol_name(id).into())
+//!     }
+//! }
+//!
+//! const BAR: Symbol = Symbol::Static(&MyStaticNS, 1);
+//!
+//! serialization::register_deserializer("my", |repr| match repr {
+//!     "foo" => Some(Symbol::Static(&MyStaticNS, 0)),
+//!     "bar" => Some(Symbol::Static(&MyStaticNS, 1)),
+//!     _ => None,
+//! });
+//!
+//! let json = serde_json::to_string(&BAR).unwrap();
+//! assert_eq!(json, r#"["my","bar"]"#);
+//! assert_eq!(serde_json::from_str::<Symbol>(&json).unwrap(), BAR);
+//! ```
+//!
+
+use crate::Symbol;
+
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+type DeserializeFn = dyn Fn(&str) -> Option<Symbol> + Send + Sync;
+
+fn deserializers() -> &'static RwLock<HashMap<String, Arc<DeserializeFn>>> {
+    static DESERIALIZERS: OnceLock<RwLock<HashMap<String, Arc<DeserializeFn>>>> = OnceLock::new();
+    DESERIALIZERS.get_or_init(Default::default)
+}
+
+///
+/// Register a deserializer for symbols in the namespace named `namespace_name`.
+///
+/// The deserializer receives the representation produced by the namespace's `serialize_symbol`,
+/// and returns `None` if it's not recognized. Registering a deserializer for a namespace name that
+/// already has one replaces the old deserializer.
+///
+pub fn register_deserializer<F>(namespace_name: &str, deserialize: F)
+where
+    F: Fn(&str) -> Option<Symbol> + Send + Sync + 'static,
+{
+    deserializers()
+        .write()
+        .unwrap()
+        .insert(namespace_name.into(), Arc::new(deserialize));
+}
+
+///
+/// Deserialize a symbol from its namespace name and representation, using the registered deserializers.
+///
+pub fn deserialize_symbol(namespace_name: &str, repr: &str) -> Option<Symbol> {
+    let deserialize = deserializers()
+        .read()
+        .unwrap()
+        .get(namespace_name)
+        .cloned()?;
+    deserialize(repr)
+}
+
+impl Symbol {
+    fn serialize_repr(&self) -> Option<std::borrow::Cow<'_, str>> {
+        match self {
+            Self::Static(ns, id) => ns.serialize_symbol(*id),
+            Self::Dynamic(instance) => instance.serialize_symbol(),
+            Self::Inline(_, name) => Some(name.as_str().into()),
+            Self::StaticIndexed(base, index) => base
+                .namespace()
+                .serialize_symbol(base.id())
+                .map(|repr| format!("{}[{}]", repr, index).into()),
+            Self::StaticWide(ns, id) => ns.namespace().serialize_symbol(*id),
+        }
+    }
+}
+
+impl serde::Serialize for Symbol {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.serialize_repr() {
+            Some(repr) => (self.namespace(), repr.as_ref()).serialize(serializer),
+            None => Err(S::Error::custom(format!(
+                "symbol `{:?}` is not serializable",
+                self
+            ))),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Symbol {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (namespace_name, repr) = <(String, String)>::deserialize(deserializer)?;
+
+        deserialize_symbol(&namespace_name, &repr).ok_or_else(|| {
+            D::Error::custom(format!(
+                "could not deserialize symbol `{}` in namespace `{}`",
+                repr, namespace_name
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::namespace;
+
+    struct Unserializable;
+
+    impl namespace::Static for Unserializable {
+        fn namespace_name(&self) -> &str {
+            "unserializable"
+        }
+
+        fn symbol_count(&self) -> u32 {
+            1
+        }
+
+        fn symbol_name(&self, _: u32) -> &str {
+            "sym"
+        }
+    }
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Text(String);
+
+    impl namespace::DynValue for Text {
+        fn namespace_name(&self) -> &str {
+            "text"
+        }
+
+        fn symbol_name(&self) -> &str {
+            &self.0
+        }
+
+        fn serialize_symbol(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(self.0.as_str().into())
+        }
+    }
+
+    fn text(str: &str) -> Symbol {
+        Symbol::from(namespace::DynWrap(Text(str.into())))
+    }
+
+    #[test]
+    fn test_dynamic_roundtrip() {
+        register_deserializer("text", |repr| Some(text(repr)));
+
+        let json = serde_json::to_string(&text("foo")).unwrap();
+        assert_eq!(json, r#"["text","foo"]"#);
+        assert_eq!(serde_json::from_str::<Symbol>(&json).unwrap(), text("foo"));
+    }
+
+    #[test]
+    fn test_not_serializable() {
+        assert!(serde_json::to_string(&Symbol::Static(&Unserializable, 0)).is_err());
+    }
+
+    #[test]
+    fn test_unknown_namespace() {
+        assert!(serde_json::from_str::<Symbol>(r#"["unregistered","foo"]"#).is_err());
+    }
+}
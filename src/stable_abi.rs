@@ -0,0 +1,637 @@
+//!
+//! FFI-safe symbol representation for passing symbols across dynamic library boundaries, using [abi_stable].
+//!
+//! A [Symbol] can't cross a `cdylib` boundary: its vtables, [TypeId](std::any::TypeId)s and
+//! allocations all belong to the library that created it. A [StableSymbol] instead carries the namespace name,
+//! the symbol name and the [namespace_uid](crate::namespace::Static::namespace_uid) of the namespace, and implements
+//! [StableAbi]. The receiving side turns it back into a [Symbol] local to that side:
+//!
+//! 1. A static namespace with the same uid, registered in the receiving side's [registry], is
+//!    preferred. The symbol's id is carried too, as ids mean the same symbols in namespaces sharing a uid.
+//! 2. Otherwise the names are resolved through the [registry].
+//! 3. Otherwise the symbol's [StableDynamic] payload, if it has one, becomes a [ForeignDynamic] symbol.
+//!
+//! [Indexed](crate::indexed) symbols carry their index, and [wide](crate::wide) symbols their `u64` id. Their names
+//! don't identify them, so they are only resolved by uid: through a [registered](registry::register_indexed) base,
+//! and a [registered](registry::register_wide) wide namespace.
+//!
+//! ```
+//! use dyn_symbol::stable_abi::StableSymbol;
+//! use dyn_symbol::*;
+//!
+//! struct MyStaticNS;
+//!
+//! impl namespace::Static for MyStaticNS {
+//!     fn namespace_name(&self) -> &str {
+//!         "my"
+//!     }
+//!
+//!     fn namespace_uid(&self) -> Option<u128> {
+//!         Some(0x5ab1e)
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         1
+//!     }
+//!
+//!     fn symbol_name(&self, _: u32) -> &str {
+//!         "foo"
+//!     }
+//! }
+//!
+//! registry::register(&MyStaticNS);
+//!
+//! // In the plugin:
+//! let stable = StableSymbol::from(&Symbol::Static(&MyStaticNS, 0));
+//!
+//! // In the host:
+//! assert_eq!(stable.resolve().unwrap(), Symbol::Static(&MyStaticNS, 0));
+//! ```
+//!
+//! ## Dynamic symbols
+//! A dynamic namespace whose symbols should survive the boundary without being registered on the other side
+//! implements [StableDynamic], the FFI-safe vtable of its symbols, and provides [ToStableDynamic] through
+//! `query_interface`:
+//!
+//! ```
+//! use abi_stable::sabi_trait::TD_Opaque;
+//! use abi_stable::std_types::{RArc, RStr};
+//! use dyn_symbol::stable_abi::*;
+//! use dyn_symbol::*;
+//!
+//! #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+//! struct Event(String);
+//!
+//! impl StableDynamic for Event {
+//!     fn namespace_name(&self) -> RStr<'_> {
+//!         "event".into()
+//!     }
+//!
+//!     fn symbol_name(&self) -> RStr<'_> {
+//!         self.0.as_str().into()
+//!     }
+//! }
+//!
+//! impl ToStableDynamic for Event {
+//!     fn to_stable_dynamic(&self) -> StableDynamicArc {
+//!         StableDynamic_TO::from_ptr(RArc::new(self.clone()), TD_Opaque)
+//!     }
+//! }
+//!
+//! impl namespace::DynValue for Event {
+//!     fn namespace_name(&self) -> &str {
+//!         "event"
+//!     }
+//!
+//!     fn symbol_name(&self) -> &str {
+//!         &self.0
+//!     }
+//!
+//!     dyn_symbol::query_interfaces!(dyn ToStableDynamic);
+//! }
+//!
+//! let stable = StableSymbol::from(&Symbol::from(namespace::DynWrap(Event("click".into()))));
+//! let symbol = stable.resolve().unwrap();
+//!
+//! assert_eq!(format!("{:?}", symbol), "event::click");
+//! assert!(symbol.downcast_dyn::<ForeignDynamic>().is_some());
+//! ```
+//!
+//! A [ForeignDynamic] symbol is equal to the other [ForeignDynamic] symbols with the same namespace and symbol names.
+//! If the namespace has a uid, it is also equal to the receiving side's own dynamic symbols with that uid and name.
+//!
+
+use crate::registry::{self, ParseSymbolError};
+use crate::{namespace, Symbol};
+
+use abi_stable::std_types::{RArc, ROption, RString, Tuple2};
+use abi_stable::StableAbi;
+use std::sync::Arc;
+
+pub use self::vtable::*;
+
+mod vtable {
+    // The code generated by `sabi_trait` has `impl`s inside a `const` block.
+    #![allow(non_local_definitions)]
+
+    use abi_stable::sabi_trait;
+    use abi_stable::std_types::RStr;
+
+    ///
+    /// The FFI-safe vtable of a dynamic symbol, see [ToStableDynamic](crate::stable_abi::ToStableDynamic).
+    ///
+    #[sabi_trait]
+    pub trait StableDynamic: Send + Sync + Clone {
+        ///
+        /// The namespace's name. See [Dynamic::namespace_name](crate::namespace::Dynamic::namespace_name).
+        ///
+        fn namespace_name(&self) -> RStr<'_>;
+
+        ///
+        /// The symbol name. See [Dynamic::symbol_name](crate::namespace::Dynamic::symbol_name).
+        ///
+        #[sabi(last_prefix_field)]
+        fn symbol_name(&self) -> RStr<'_>;
+    }
+}
+
+///
+/// A shared, FFI-safe dynamic symbol payload.
+///
+pub type StableDynamicArc = StableDynamic_TO<'static, RArc<()>>;
+
+///
+/// An interface provided through `query_interface` by dynamic namespaces whose symbols carry a
+/// [StableDynamic] payload in their [StableSymbol].
+///
+pub trait ToStableDynamic {
+    ///
+    /// The FFI-safe payload of this symbol.
+    ///
+    fn to_stable_dynamic(&self) -> StableDynamicArc;
+}
+
+///
+/// An FFI-safe, owned representation of a [Symbol].
+///
+/// Stable symbols are equal when their namespace names, symbol names, namespace uids, ids and indexes are equal.
+/// The [StableDynamic] payload is not compared.
+///
+#[repr(C)]
+#[derive(StableAbi, Clone)]
+pub struct StableSymbol {
+    namespace: RString,
+    name: RString,
+    /// The namespace uid, split into its high and low halves.
+    namespace_uid: ROption<Tuple2<u64, u64>>,
+    id: ROption<u32>,
+    index: ROption<u32>,
+    wide_id: ROption<u64>,
+    payload: ROption<StableDynamicArc>,
+}
+
+impl StableSymbol {
+    ///
+    /// Create a stable symbol from a namespace name and a symbol name.
+    ///
+    pub fn new(namespace: &str, name: &str) -> Self {
+        Self {
+            namespace: namespace.into(),
+            name: name.into(),
+            namespace_uid: ROption::RNone,
+            id: ROption::RNone,
+            index: ROption::RNone,
+            wide_id: ROption::RNone,
+            payload: ROption::RNone,
+        }
+    }
+
+    ///
+    /// Identify the symbol's namespace by `uid` too.
+    ///
+    pub fn with_namespace_uid(mut self, uid: u128) -> Self {
+        self.namespace_uid = ROption::RSome(Tuple2((uid >> 64) as u64, uid as u64));
+        self
+    }
+
+    ///
+    /// Identify the symbol by its `id` within a static namespace with a [namespace_uid](Self::namespace_uid) too.
+    ///
+    pub fn with_id(mut self, id: u32) -> Self {
+        self.id = ROption::RSome(id);
+        self
+    }
+
+    ///
+    /// Identify an [indexed](crate::indexed) symbol by its `index`, and its base symbol by the [id](Self::with_id).
+    ///
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = ROption::RSome(index);
+        self
+    }
+
+    ///
+    /// Identify a [wide](crate::wide) symbol by its `id`.
+    ///
+    pub fn with_wide_id(mut self, id: u64) -> Self {
+        self.wide_id = ROption::RSome(id);
+        self
+    }
+
+    ///
+    /// Carry a dynamic symbol payload, used when the receiving side can't resolve the names.
+    ///
+    pub fn with_payload(mut self, payload: StableDynamicArc) -> Self {
+        self.payload = ROption::RSome(payload);
+        self
+    }
+
+    ///
+    /// The name of the symbol's namespace.
+    ///
+    pub fn namespace(&self) -> &str {
+        self.namespace.as_str()
+    }
+
+    ///
+    /// The symbol's name within its namespace.
+    ///
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    ///
+    /// The [namespace_uid](crate::namespace::Static::namespace_uid) of the symbol's namespace, if it has one.
+    ///
+    pub fn namespace_uid(&self) -> Option<u128> {
+        self.namespace_uid
+            .as_ref()
+            .into_option()
+            .map(|Tuple2(high, low)| (u128::from(*high) << 64) | u128::from(*low))
+    }
+
+    ///
+    /// The symbol's id within its static namespace, if it came from one with a uid.
+    ///
+    pub fn id(&self) -> Option<u32> {
+        self.id.into_option()
+    }
+
+    ///
+    /// The index of an [indexed](crate::indexed) symbol.
+    ///
+    pub fn index(&self) -> Option<u32> {
+        self.index.into_option()
+    }
+
+    ///
+    /// The id of a [wide](crate::wide) symbol.
+    ///
+    pub fn wide_id(&self) -> Option<u64> {
+        self.wide_id.into_option()
+    }
+
+    ///
+    /// The dynamic symbol payload, if there is one.
+    ///
+    pub fn payload(&self) -> Option<&StableDynamicArc> {
+        self.payload.as_ref().into_option()
+    }
+
+    ///
+    /// Resolve this into a [Symbol] on the calling side. See the [module documentation](self) for the order
+    /// of resolution.
+    ///
+    pub fn resolve(&self) -> Result<Symbol, ParseSymbolError> {
+        if let Some(symbol) = self
+            .namespace_uid()
+            .and_then(|uid| self.resolve_by_uid(uid))
+        {
+            return Ok(symbol);
+        }
+
+        if self.index().is_some() || self.wide_id().is_some() {
+            return Err(ParseSymbolError::UnknownSymbol {
+                namespace_name: self.namespace().into(),
+                symbol_name: self.name().into(),
+            });
+        }
+
+        registry::resolve(self.namespace(), self.name()).or_else(|err| match self.payload() {
+            Some(payload) => Ok(Symbol::Dynamic(Arc::new(ForeignDynamic {
+                payload: payload.clone(),
+                namespace_uid: self.namespace_uid(),
+            }))),
+            None => Err(err),
+        })
+    }
+
+    fn resolve_by_uid(&self, uid: u128) -> Option<Symbol> {
+        if let Some(id) = self.wide_id() {
+            return registry::wide_by_uid(uid).map(|ns| ns.symbol(id));
+        }
+        if let Some(index) = self.index() {
+            return registry::indexed_by_uid(uid, self.id()?).map(|base| base.index(index));
+        }
+
+        let ns = registry::static_by_uid(uid)?;
+        self.id()
+            .filter(|id| *id < ns.symbol_count())
+            .or_else(|| ns.symbol_by_name(self.name()))
+            .map(|id| Symbol::Static(ns, id))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn key(
+        &self,
+    ) -> (
+        &str,
+        &str,
+        Option<u128>,
+        Option<u32>,
+        Option<u32>,
+        Option<u64>,
+    ) {
+        (
+            self.namespace(),
+            self.name(),
+            self.namespace_uid(),
+            self.id(),
+            self.index(),
+            self.wide_id(),
+        )
+    }
+}
+
+impl From<&Symbol> for StableSymbol {
+    fn from(symbol: &Symbol) -> Self {
+        let stable = Self::new(symbol.namespace(), symbol.name());
+
+        match symbol {
+            Symbol::Static(ns, id) => match ns.namespace_uid() {
+                Some(uid) => stable.with_namespace_uid(uid).with_id(*id),
+                None => stable,
+            },
+            Symbol::Dynamic(instance) => {
+                let payload = match instance.downcast_ref::<ForeignDynamic>() {
+                    Some(foreign) => Some(foreign.payload.clone()),
+                    None => symbol
+                        .as_trait::<dyn ToStableDynamic>()
+                        .map(ToStableDynamic::to_stable_dynamic),
+                };
+                let stable = match instance.namespace_uid() {
+                    Some(uid) => stable.with_namespace_uid(uid),
+                    None => stable,
+                };
+                match payload {
+                    Some(payload) => stable.with_payload(payload),
+                    None => stable,
+                }
+            }
+            Symbol::StaticIndexed(base, index) => {
+                let stable = stable.with_id(base.id()).with_index(*index);
+                match base.namespace().namespace_uid() {
+                    Some(uid) => stable.with_namespace_uid(uid),
+                    None => stable,
+                }
+            }
+            Symbol::StaticWide(ns, id) => {
+                let stable = stable.with_wide_id(*id);
+                match ns.namespace().namespace_uid() {
+                    Some(uid) => stable.with_namespace_uid(uid),
+                    None => stable,
+                }
+            }
+            Symbol::Inline(_, _) => stable,
+        }
+    }
+}
+
+impl PartialEq for StableSymbol {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.key() == rhs.key()
+    }
+}
+
+impl Eq for StableSymbol {}
+
+impl Ord for StableSymbol {
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&rhs.key())
+    }
+}
+
+impl PartialOrd for StableSymbol {
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl std::hash::Hash for StableSymbol {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl std::fmt::Debug for StableSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}::{}", self.namespace(), self.name())?;
+        match self.index() {
+            Some(index) => write!(f, "[{}]", index),
+            None => Ok(()),
+        }
+    }
+}
+
+///
+/// The dynamic namespace of symbols resolved from a [StableDynamic] payload created on the other side of
+/// a library boundary.
+///
+#[derive(Clone)]
+pub struct ForeignDynamic {
+    payload: StableDynamicArc,
+    namespace_uid: Option<u128>,
+}
+
+impl ForeignDynamic {
+    ///
+    /// The payload of this symbol.
+    ///
+    pub fn payload(&self) -> &StableDynamicArc {
+        &self.payload
+    }
+}
+
+impl namespace::Dynamic for ForeignDynamic {
+    fn namespace_name(&self) -> &str {
+        self.payload.namespace_name().as_str()
+    }
+
+    fn symbol_name(&self) -> &str {
+        self.payload.symbol_name().as_str()
+    }
+
+    fn namespace_uid(&self) -> Option<u128> {
+        self.namespace_uid
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        (self.namespace_name(), self.symbol_name()) == (rhs.namespace_name(), rhs.symbol_name())
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> std::cmp::Ordering {
+        (self.namespace_name(), self.symbol_name()).cmp(&(rhs.namespace_name(), rhs.symbol_name()))
+    }
+
+    fn dyn_hash(&self, state: &mut dyn std::hash::Hasher) {
+        state.write(self.namespace_name().as_bytes());
+        state.write_u8(0xff);
+        state.write(self.symbol_name().as_bytes());
+        state.write_u8(0xff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexed::IndexedBase;
+    use crate::tests::{dynamic, STATIC_A_1};
+    use crate::wide::WideNamespace;
+    use abi_stable::sabi_trait::TD_Opaque;
+    use abi_stable::std_types::RStr;
+
+    struct Versioned<const N: u8>;
+
+    impl<const N: u8> namespace::Static for Versioned<N> {
+        fn namespace_name(&self) -> &str {
+            "stable_versioned"
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            Some(u128::MAX - 1)
+        }
+
+        fn symbol_count(&self) -> u32 {
+            2
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["foo", "bar"][id as usize]
+        }
+
+        fn symbol_by_name(&self, name: &str) -> Option<u32> {
+            ["foo", "bar"]
+                .iter()
+                .position(|n| *n == name)
+                .map(|id| id as u32)
+        }
+    }
+
+    #[derive(Clone)]
+    struct Payload(String);
+
+    impl StableDynamic for Payload {
+        fn namespace_name(&self) -> RStr<'_> {
+            "stable_payload".into()
+        }
+
+        fn symbol_name(&self) -> RStr<'_> {
+            self.0.as_str().into()
+        }
+    }
+
+    fn foreign(name: &str) -> StableSymbol {
+        StableSymbol::new("stable_payload", name).with_payload(StableDynamic_TO::from_ptr(
+            RArc::new(Payload(name.into())),
+            TD_Opaque,
+        ))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        registry::register(&crate::tests::_static::STATIC_NS_CLASS_A);
+
+        let stable = StableSymbol::from(&STATIC_A_1);
+        assert_eq!(format!("{:?}", stable), "A::1");
+        assert_eq!(stable.resolve().unwrap(), STATIC_A_1);
+
+        let unknown = StableSymbol::from(&dynamic::sym0("foo"));
+        assert_eq!(unknown.namespace(), "dyn0");
+        assert!(unknown.payload().is_none());
+        assert!(unknown.resolve().is_err());
+    }
+
+    #[test]
+    fn test_namespace_uid() {
+        // Only one of the namespace types sharing the uid is registered, by uid:
+        registry::register(&Versioned::<1>);
+
+        let stable = StableSymbol::from(&Symbol::Static(&Versioned::<2>, 1));
+        assert_eq!(stable.namespace_uid(), Some(u128::MAX - 1));
+        assert_eq!(stable.id(), Some(1));
+        assert_eq!(
+            stable.resolve().unwrap(),
+            Symbol::Static(&Versioned::<1>, 1)
+        );
+
+        let by_name = StableSymbol::new("renamed", "bar").with_namespace_uid(u128::MAX - 1);
+        assert_eq!(
+            by_name.resolve().unwrap(),
+            Symbol::Static(&Versioned::<1>, 1)
+        );
+    }
+
+    #[test]
+    fn test_indexed() {
+        static BAR: IndexedBase = IndexedBase::new(&Versioned::<1>, 1);
+        static BAR_AGAIN: IndexedBase = IndexedBase::new(&Versioned::<2>, 1);
+        registry::register_indexed(&BAR);
+
+        let stable = StableSymbol::from(&BAR_AGAIN.index(17));
+        assert_eq!((stable.id(), stable.index()), (Some(1), Some(17)));
+        assert_eq!(format!("{:?}", stable), "stable_versioned::bar[17]");
+        assert_ne!(stable, StableSymbol::from(&BAR_AGAIN.index(18)));
+        assert_ne!(
+            stable,
+            StableSymbol::from(&Symbol::Static(&Versioned::<2>, 1))
+        );
+        assert_eq!(stable.resolve().unwrap(), BAR.index(17));
+
+        // The base name doesn't identify the indexed symbol:
+        static A_0: IndexedBase = IndexedBase::new(&crate::tests::_static::STATIC_NS_CLASS_A, 0);
+        registry::register(&crate::tests::_static::STATIC_NS_CLASS_A);
+        assert!(StableSymbol::from(&A_0.index(3)).resolve().is_err());
+    }
+
+    #[test]
+    fn test_wide() {
+        struct Cells;
+
+        impl namespace::StaticWide for Cells {
+            fn namespace_name(&self) -> &str {
+                "stable_cells"
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(u128::MAX - 2)
+            }
+
+            fn symbol_name(&self, _: u64) -> &str {
+                "cell"
+            }
+
+            fn symbol_by_name(&self, _: &str) -> Option<u64> {
+                Some(0)
+            }
+        }
+
+        static CELLS: WideNamespace = WideNamespace::new(&Cells);
+
+        let cell = CELLS.symbol(u64::MAX);
+        let stable = StableSymbol::from(&cell);
+        assert_eq!(stable.wide_id(), Some(u64::MAX));
+        assert_ne!(stable, StableSymbol::from(&CELLS.symbol(0)));
+        assert!(stable.resolve().is_err());
+
+        registry::register_wide(&CELLS);
+        assert_eq!(stable.resolve().unwrap(), cell);
+    }
+
+    #[test]
+    fn test_dynamic_payload() {
+        let symbol = foreign("foo").resolve().unwrap();
+
+        assert_eq!(symbol.name(), "foo");
+        assert_eq!(symbol, foreign("foo").resolve().unwrap());
+        assert_ne!(symbol, foreign("bar").resolve().unwrap());
+
+        // The payload is passed on again:
+        let again = StableSymbol::from(&symbol);
+        assert_eq!(again, foreign("foo"));
+        assert_eq!(again.payload().unwrap().symbol_name().as_str(), "foo");
+    }
+}
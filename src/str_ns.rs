@@ -0,0 +1,146 @@
+//!
+//! A plain string dynamic namespace, for prototypes and fallthrough cases.
+//!
+//! [StrSymbol] puts any string into the namespace `str`. Strings convert directly into symbols:
+//!
+//! ```
+//! use dyn_symbol::str_ns::StrSymbol;
+//! use dyn_symbol::Symbol;
+//!
+//! let foo = Symbol::from("foo");
+//!
+//! assert_eq!(foo, Symbol::from(String::from("foo")));
+//! assert_ne!(foo, Symbol::from("bar"));
+//! assert_eq!(format!("{:?}", foo), "str::foo");
+//! assert_eq!(foo.downcast_dyn::<StrSymbol>().unwrap().as_str(), "foo");
+//!
+//! // Take back the string:
+//! assert_eq!(foo.into_dynamic::<StrSymbol>().unwrap().into_string(), "foo");
+//! ```
+//!
+//! With the `serde` feature, deserializing string symbols requires registering a deserializer for the namespace:
+//!
+//! ```ignore
+//! dyn_symbol::serialization::register_deserializer(NAMESPACE_NAME, |name| Some(Symbol::from(name)));
+//! ```
+//!
+
+use crate::{namespace, Symbol};
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+
+///
+/// The name of the namespace of [StrSymbol]s.
+///
+pub const NAMESPACE_NAME: &str = "str";
+
+///
+/// A dynamic symbol that is just a string.
+///
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct StrSymbol(String);
+
+impl StrSymbol {
+    ///
+    /// Create a string symbol.
+    ///
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    ///
+    /// The string.
+    ///
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    ///
+    /// Unwrap the string.
+    ///
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl namespace::Dynamic for StrSymbol {
+    fn namespace_name(&self) -> &str {
+        NAMESPACE_NAME
+    }
+
+    fn symbol_name(&self) -> &str {
+        &self.0
+    }
+
+    fn hash_by_name(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "serde")]
+    fn serialize_symbol(&self) -> Option<alloc::borrow::Cow<'_, str>> {
+        Some(self.0.as_str().into())
+    }
+
+    #[cfg(feature = "rkyv")]
+    fn archive_bytes(&self) -> Option<alloc::borrow::Cow<'_, [u8]>> {
+        Some(self.symbol_name().as_bytes().into())
+    }
+
+    fn dyn_clone(&self) -> Box<dyn namespace::Dynamic> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, rhs: &dyn namespace::Dynamic) -> bool {
+        self == rhs.downcast_ref::<Self>().unwrap()
+    }
+
+    fn dyn_cmp(&self, rhs: &dyn namespace::Dynamic) -> core::cmp::Ordering {
+        self.cmp(rhs.downcast_ref::<Self>().unwrap())
+    }
+
+    fn dyn_hash(&self, state: &mut dyn core::hash::Hasher) {
+        state.write(self.0.as_bytes());
+        state.write_u8(0xff)
+    }
+}
+
+impl From<StrSymbol> for Symbol {
+    fn from(symbol: StrSymbol) -> Self {
+        Symbol::Dynamic(Arc::new(symbol))
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        StrSymbol::new(name).into()
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(name: String) -> Self {
+        StrSymbol::new(name).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::dynamic;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_str_symbols() {
+        let mut map = HashMap::new();
+        map.insert(Symbol::from("foo"), 0);
+        map.insert(dynamic::sym0("foo"), 1);
+
+        assert_eq!(map.get(&Symbol::from("foo")), Some(&0));
+        assert_eq!(map.get(&Symbol::from("bar")), None);
+        assert_eq!(Symbol::from("foo").namespace(), NAMESPACE_NAME);
+        let (a, b) = (Symbol::from("a"), Symbol::from("b"));
+        assert!(a < b);
+        assert_eq!(Symbol::from("foo"), "str::foo");
+    }
+}
@@ -0,0 +1,314 @@
+//!
+//! Borrowed symbols, for looking up maps without constructing an owned [Symbol].
+//!
+//! A [SymbolRef] has the same equality, ordering and hashing semantics as [Symbol], but borrows its dynamic
+//! instance instead of owning it. Maps keyed by [Symbol] can be queried by [SymbolRef] through the
+//! [SymbolKey] trait object, which [Symbol] implements [Borrow](core::borrow::Borrow) for:
+//!
+//! ```
+//! use dyn_symbol::symbol_ref::SymbolRef;
+//! use dyn_symbol::*;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+//! struct Data(String);
+//!
+//! impl namespace::DynValue for Data {
+//!     fn namespace_name(&self) -> &str {
+//!         "data"
+//!     }
+//!
+//!     fn symbol_name(&self) -> &str {
+//!         &self.0
+//!     }
+//! }
+//!
+//! let mut map = HashMap::new();
+//! map.insert(Symbol::from(namespace::DynWrap(Data("foo".into()))), 42);
+//!
+//! // No reference counted allocation needed for the lookup:
+//! let key = namespace::DynWrap(Data("foo".into()));
+//! assert_eq!(map.get(SymbolRef::Dynamic(&key).as_key()), Some(&42));
+//! ```
+//!
+
+use crate::{indexed, inline, namespace, wide, Canonical, NamespaceId, Symbol};
+
+use alloc::sync::Arc;
+
+///
+/// A borrowed symbol.
+///
+#[derive(Clone, Copy)]
+pub enum SymbolRef<'a> {
+    /// A symbol from a static namespace, see [Symbol::Static].
+    Static(&'static dyn namespace::Static, u32),
+
+    /// A borrowed dynamic symbol instance, see [Symbol::Dynamic].
+    Dynamic(&'a dyn namespace::Dynamic),
+
+    /// A borrowed inline symbol, see [Symbol::Inline].
+    Inline(&'static inline::InlineNamespace, &'a inline::InlineName),
+
+    /// An indexed static symbol, see [Symbol::StaticIndexed].
+    StaticIndexed(&'static indexed::IndexedBase, u32),
+
+    /// A symbol from a wide static namespace, see [Symbol::StaticWide].
+    StaticWide(&'static wide::WideNamespace, u64),
+}
+
+impl<'a> SymbolRef<'a> {
+    ///
+    /// The symbol's name within its namespace.
+    ///
+    pub fn name(&self) -> &'a str {
+        match *self {
+            Self::Static(ns, id) => ns.symbol_name(id),
+            Self::Dynamic(instance) => instance.symbol_name(),
+            Self::Inline(_, name) => name.as_str(),
+            Self::StaticIndexed(base, _) => base.namespace().symbol_name(base.id()),
+            Self::StaticWide(ns, id) => ns.namespace().symbol_name(id),
+        }
+    }
+
+    ///
+    /// The name of the symbol's namespace.
+    ///
+    pub fn namespace(&self) -> &'a str {
+        match *self {
+            Self::Static(ns, _) => ns.namespace_name(),
+            Self::Dynamic(instance) => instance.namespace_name(),
+            Self::Inline(ns, _) => ns.name(),
+            Self::StaticIndexed(base, _) => base.namespace().namespace_name(),
+            Self::StaticWide(ns, _) => ns.namespace().namespace_name(),
+        }
+    }
+
+    ///
+    /// The [TypeId](core::any::TypeId) of the symbol's namespace type.
+    ///
+    /// Inline symbols, also those with names too long to be stored inline, have the type id of [InlineNamespace](inline::InlineNamespace).
+    ///
+    pub fn namespace_type_id(&self) -> core::any::TypeId {
+        match *self {
+            Self::Static(ns, _) => core::any::Any::type_id(ns.as_any()),
+            Self::Dynamic(instance) if inline::is_long_name(instance) => {
+                core::any::TypeId::of::<inline::InlineNamespace>()
+            }
+            Self::Dynamic(instance) => core::any::Any::type_id(instance.as_any()),
+            Self::Inline(ns, _) => core::any::Any::type_id(ns),
+            Self::StaticIndexed(base, _) => core::any::Any::type_id(base.namespace().as_any()),
+            Self::StaticWide(ns, _) => core::any::Any::type_id(ns.namespace().as_any()),
+        }
+    }
+
+    ///
+    /// Whether the symbol's namespace is of type `NS`.
+    ///
+    pub fn is<NS: 'static>(&self) -> bool {
+        self.namespace_type_id() == core::any::TypeId::of::<NS>()
+    }
+
+    ///
+    /// Whether the two symbols are from the same namespace.
+    ///
+    /// Namespaces with a [namespace_uid](namespace::Static::namespace_uid) are the same if their uids are,
+    /// and inline namespaces are the same if their names are. A dynamic symbol is not in the namespace of its
+    /// [static_equivalent](namespace::Dynamic::static_equivalent).
+    ///
+    pub fn same_namespace(&self, other: &SymbolRef<'_>) -> bool {
+        match (self.origin(), other.origin()) {
+            (Origin::Id(this), Origin::Id(other)) => this == other,
+            (Origin::Inline(this), Origin::Inline(other)) => this.same_namespace(other),
+            _ => false,
+        }
+    }
+
+    fn origin(&self) -> Origin {
+        match *self {
+            Self::Static(ns, _) => Origin::Id(NamespaceId::of_static(ns)),
+            Self::StaticIndexed(base, _) => Origin::Id(NamespaceId::of_static(base.namespace())),
+            Self::StaticWide(ns, _) => Origin::Id(NamespaceId::of_wide(ns.namespace())),
+            Self::Dynamic(instance) => match inline::long_name_namespace(instance) {
+                Some(ns) => Origin::Inline(ns),
+                None => Origin::Id(NamespaceId::of_dynamic(instance)),
+            },
+            Self::Inline(ns, _) => Origin::Inline(ns),
+        }
+    }
+
+    ///
+    /// Create an owned [Symbol]. Dynamic instances are cloned using [dyn_clone](namespace::Dynamic::dyn_clone).
+    ///
+    pub fn to_symbol(&self) -> Symbol {
+        match *self {
+            Self::Static(ns, id) => Symbol::Static(ns, id),
+            Self::Dynamic(instance) => Symbol::Dynamic(Arc::from(instance.dyn_clone())),
+            Self::Inline(ns, name) => Symbol::Inline(ns, *name),
+            Self::StaticIndexed(base, index) => Symbol::StaticIndexed(base, index),
+            Self::StaticWide(ns, id) => Symbol::StaticWide(ns, id),
+        }
+    }
+
+    ///
+    /// This symbol as a [SymbolKey], for looking up maps keyed by [Symbol].
+    ///
+    pub fn as_key(&self) -> &(dyn SymbolKey + 'a) {
+        self
+    }
+}
+
+enum Origin {
+    Id(NamespaceId),
+    Inline(&'static inline::InlineNamespace),
+}
+
+impl<'a> From<&'a Symbol> for SymbolRef<'a> {
+    fn from(symbol: &'a Symbol) -> Self {
+        symbol.as_symbol_ref()
+    }
+}
+
+impl core::fmt::Debug for SymbolRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Static(ns, id) => ns.fmt_debug(*id, f),
+            Self::Dynamic(instance) => instance.fmt_debug(f),
+            Self::Inline(ns, name) => write!(f, "{}::{}", ns.name(), name.as_str()),
+            Self::StaticIndexed(base, index) => {
+                base.namespace().fmt_debug(base.id(), f)?;
+                write!(f, "[{}]", index)
+            }
+            Self::StaticWide(ns, id) => ns.namespace().fmt_debug(*id, f),
+        }
+    }
+}
+
+impl PartialEq for SymbolRef<'_> {
+    fn eq(&self, rhs: &Self) -> bool {
+        Canonical::of(*self) == Canonical::of(*rhs)
+    }
+}
+
+impl Eq for SymbolRef<'_> {}
+
+impl PartialEq<Symbol> for SymbolRef<'_> {
+    fn eq(&self, rhs: &Symbol) -> bool {
+        *self == rhs.as_symbol_ref()
+    }
+}
+
+impl PartialEq<SymbolRef<'_>> for Symbol {
+    fn eq(&self, rhs: &SymbolRef<'_>) -> bool {
+        self.as_symbol_ref() == *rhs
+    }
+}
+
+impl Ord for SymbolRef<'_> {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        Canonical::of(*self).cmp(&Canonical::of(*rhs))
+    }
+}
+
+impl PartialOrd for SymbolRef<'_> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::hash::Hash for SymbolRef<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        Canonical::of(*self).hash(state)
+    }
+}
+
+///
+/// Anything that can be viewed as a [SymbolRef]. `dyn SymbolKey` is the borrowed form of [Symbol]
+/// used for map lookups, with the same equality, ordering and hashing.
+///
+pub trait SymbolKey {
+    ///
+    /// View this key as a [SymbolRef].
+    ///
+    fn symbol_ref(&self) -> SymbolRef<'_>;
+}
+
+impl SymbolKey for Symbol {
+    fn symbol_ref(&self) -> SymbolRef<'_> {
+        self.as_symbol_ref()
+    }
+}
+
+impl SymbolKey for SymbolRef<'_> {
+    fn symbol_ref(&self) -> SymbolRef<'_> {
+        *self
+    }
+}
+
+impl<'a> core::borrow::Borrow<dyn SymbolKey + 'a> for Symbol {
+    fn borrow(&self) -> &(dyn SymbolKey + 'a) {
+        self
+    }
+}
+
+impl PartialEq for dyn SymbolKey + '_ {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.symbol_ref() == rhs.symbol_ref()
+    }
+}
+
+impl Eq for dyn SymbolKey + '_ {}
+
+impl Ord for dyn SymbolKey + '_ {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        self.symbol_ref().cmp(&rhs.symbol_ref())
+    }
+}
+
+impl PartialOrd for dyn SymbolKey + '_ {
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::hash::Hash for dyn SymbolKey + '_ {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.symbol_ref().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::dynamic::TestDynamic;
+    use crate::tests::{dynamic, STATIC_A_0, STATIC_A_1};
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn test_symbol_ref() {
+        let foo = TestDynamic::<0>("foo".into(), "dyn0");
+
+        assert_eq!(SymbolRef::Dynamic(&foo), dynamic::sym0("foo"));
+        assert_ne!(SymbolRef::Dynamic(&foo), dynamic::sym0("bar"));
+        assert_eq!(SymbolRef::from(&STATIC_A_0), STATIC_A_0);
+        assert_eq!(format!("{:?}", SymbolRef::Dynamic(&foo)), "dyn0::foo");
+        assert_eq!(SymbolRef::Dynamic(&foo).to_symbol(), dynamic::sym0("foo"));
+    }
+
+    #[test]
+    fn test_map_lookup() {
+        let mut map = HashMap::new();
+        map.insert(dynamic::sym0("foo"), 0);
+        map.insert(STATIC_A_1, 1);
+
+        let foo = TestDynamic::<0>("foo".into(), "dyn0");
+        let foo1 = TestDynamic::<1>("foo".into(), "dyn1");
+
+        assert_eq!(map.get(SymbolRef::Dynamic(&foo).as_key()), Some(&0));
+        assert_eq!(map.get(SymbolRef::Dynamic(&foo1).as_key()), None);
+        assert_eq!(map.get(STATIC_A_1.as_symbol_ref().as_key()), Some(&1));
+
+        let set: BTreeSet<Symbol> = vec![dynamic::sym0("foo"), STATIC_A_0].into_iter().collect();
+        assert!(set.contains(SymbolRef::Dynamic(&foo).as_key()));
+    }
+}
@@ -0,0 +1,163 @@
+//!
+//! Logging and tracing of symbols.
+//!
+//! [Symbol::display] formats a symbol as its qualified `namespace::symbol` name, regardless of any
+//! [fmt_display](crate::namespace::Static::fmt_display) customizations, for log lines that can be searched by namespace:
+//!
+//! ```
+//! # use dyn_symbol::*;
+//! # struct Html;
+//! # impl namespace::Static for Html {
+//! #     fn namespace_name(&self) -> &str { "html" }
+//! #     fn symbol_count(&self) -> u32 { 1 }
+//! #     fn symbol_name(&self, _: u32) -> &str { "class" }
+//! # }
+//! let class = Symbol::Static(&Html, 0);
+//!
+//! assert_eq!(format!("resolved {}", class.display()), "resolved html::class");
+//! ```
+//!
+//! With the `tracing` feature, [Symbol::record_in] records a symbol's namespace and name as separate span fields,
+//! so spans can be filtered by namespace:
+//!
+//! ```ignore
+//! let span = tracing::info_span!("lookup", symbol.namespace = tracing::field::Empty, symbol.name = tracing::field::Empty);
+//! symbol.record_in(&span, "symbol.namespace", "symbol.name");
+//! ```
+//!
+
+use crate::Symbol;
+
+///
+/// Formats a [Symbol] as `namespace::symbol`. Returned by [Symbol::display].
+///
+#[derive(Clone, Copy)]
+pub struct SymbolDisplay<'a>(&'a Symbol);
+
+impl core::fmt::Display for SymbolDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}::{}", self.0.namespace(), self.0.name())?;
+        match self.0.index() {
+            Some(index) => write!(f, "[{}]", index),
+            None => Ok(()),
+        }
+    }
+}
+
+impl core::fmt::Debug for SymbolDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+impl Symbol {
+    ///
+    /// Format this symbol as its qualified `namespace::symbol` name, e.g. for `log` messages.
+    ///
+    pub fn display(&self) -> SymbolDisplay<'_> {
+        SymbolDisplay(self)
+    }
+
+    ///
+    /// This symbol as a `tracing` field value, formatted like [Symbol::display].
+    ///
+    #[cfg(feature = "tracing")]
+    pub fn trace_value(&self) -> tracing::field::DisplayValue<SymbolDisplay<'_>> {
+        tracing::field::display(self.display())
+    }
+
+    ///
+    /// Record this symbol's namespace and name in the fields `namespace_field` and `name_field` of `span`.
+    ///
+    /// The fields must be declared when the span is created, e.g. as [Empty](tracing::field::Empty).
+    ///
+    #[cfg(feature = "tracing")]
+    pub fn record_in(&self, span: &tracing::Span, namespace_field: &str, name_field: &str) {
+        span.record(namespace_field, self.namespace());
+        span.record(name_field, self.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::indexed::IndexedBase;
+    use crate::tests::_static::STATIC_NS_CLASS_A;
+    use crate::tests::{dynamic, STATIC_A_1};
+
+    #[test]
+    fn test_display() {
+        static A_0: IndexedBase = IndexedBase::new(&STATIC_NS_CLASS_A, 0);
+
+        assert_eq!(STATIC_A_1.display().to_string(), "A::1");
+        assert_eq!(A_0.index(2).display().to_string(), "A::0[2]");
+        // Not affected by custom Display formatting:
+        assert_eq!(dynamic::wrapped("foo").to_string(), "wrapped:foo");
+        assert_eq!(
+            dynamic::wrapped("foo").display().to_string(),
+            "wrapped::foo"
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<(String, String)>>>);
+
+        impl Visit for Recorder {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                let entry = (field.name().to_string(), format!("{:?}", value));
+                self.0.lock().unwrap().push(entry);
+            }
+        }
+
+        impl tracing::Subscriber for Recorder {
+            fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                span.record(&mut self.clone());
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, values: &Record<'_>) {
+                values.record(&mut self.clone());
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &tracing::Event<'_>) {
+                event.record(&mut self.clone());
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let span = tracing::info_span!(
+                "lookup",
+                ns = tracing::field::Empty,
+                name = tracing::field::Empty
+            );
+            STATIC_A_1.record_in(&span, "ns", "name");
+            tracing::info!(symbol = STATIC_A_1.trace_value());
+        });
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                ("ns".to_string(), "\"A\"".to_string()),
+                ("name".to_string(), "\"1\"".to_string()),
+                ("symbol".to_string(), "A::1".to_string()),
+            ]
+        );
+    }
+}
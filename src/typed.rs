@@ -0,0 +1,265 @@
+//!
+//! Symbols statically known to originate from one particular static namespace.
+//!
+//! ```
+//! use dyn_symbol::typed::TypedSymbol;
+//! use dyn_symbol::*;
+//! use std::convert::TryFrom;
+//!
+//! struct Html;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         2
+//!     }
+//!
+//!     fn symbol_name(&self, id: u32) -> &str {
+//!         ["id", "class"][id as usize]
+//!     }
+//! }
+//!
+//! const ID: TypedSymbol<Html> = TypedSymbol::new(&Html, 0);
+//!
+//! // Only accepts symbols from the `Html` namespace:
+//! fn attribute_name(attribute: &TypedSymbol<Html>) -> &str {
+//!     attribute.name()
+//! }
+//!
+//! assert_eq!(attribute_name(&ID), "id");
+//!
+//! let class = TypedSymbol::<Html>::try_from(Symbol::Static(&Html, 1)).unwrap();
+//! assert_eq!(class.id(), 1);
+//! assert_eq!(Symbol::from(class), Symbol::Static(&Html, 1));
+//! ```
+//!
+
+use crate::{namespace, Symbol};
+
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+///
+/// A [Symbol] guaranteed to originate from the static namespace `NS`.
+///
+/// It has the same size as [Symbol], and dereferences to it.
+///
+pub struct TypedSymbol<NS: namespace::Static> {
+    symbol: Symbol,
+    _namespace: PhantomData<&'static NS>,
+}
+
+impl<NS: namespace::Static> TypedSymbol<NS> {
+    ///
+    /// Construct the symbol with the given `id` in `ns`.
+    ///
+    pub const fn new(ns: &'static NS, id: u32) -> Self {
+        Self {
+            symbol: Symbol::Static(ns, id),
+            _namespace: PhantomData,
+        }
+    }
+
+    ///
+    /// The symbol's namespace.
+    ///
+    pub fn namespace(&self) -> &'static NS {
+        match &self.symbol {
+            Symbol::Static(ns, _) => ns.as_any().downcast_ref::<NS>().unwrap(),
+            _ => unreachable!(),
+        }
+    }
+
+    ///
+    /// The symbol's `id` within its namespace.
+    ///
+    pub fn id(&self) -> u32 {
+        match &self.symbol {
+            Symbol::Static(_, id) => *id,
+            _ => unreachable!(),
+        }
+    }
+
+    ///
+    /// Convert a symbol in the same namespace as `ns` into a typed symbol of `ns`. Like [TryFrom], but also
+    /// accepts symbols of namespaces of other types sharing the [namespace_uid](namespace::Static::namespace_uid) of `NS`.
+    ///
+    pub fn try_from_symbol(ns: &'static NS, symbol: Symbol) -> Result<Self, Symbol> {
+        match symbol.static_id_in(ns) {
+            Some(id) => Ok(Self::new(ns, id)),
+            None => Err(symbol),
+        }
+    }
+
+    ///
+    /// The untyped symbol.
+    ///
+    pub fn as_symbol(&self) -> &Symbol {
+        &self.symbol
+    }
+}
+
+impl<NS: namespace::Static> core::ops::Deref for TypedSymbol<NS> {
+    type Target = Symbol;
+
+    fn deref(&self) -> &Symbol {
+        &self.symbol
+    }
+}
+
+impl<NS: namespace::Static> AsRef<Symbol> for TypedSymbol<NS> {
+    fn as_ref(&self) -> &Symbol {
+        &self.symbol
+    }
+}
+
+impl<NS: namespace::Static> From<TypedSymbol<NS>> for Symbol {
+    fn from(typed: TypedSymbol<NS>) -> Self {
+        typed.symbol
+    }
+}
+
+///
+/// Symbols of `NS`, including other instances of the `NS` type and dynamic symbols with a
+/// [static_equivalent](namespace::Dynamic::static_equivalent) in `NS`, are accepted.
+/// Symbols of namespaces of other types sharing the [namespace_uid](namespace::Static::namespace_uid) of `NS`
+/// need an instance of `NS`, see [TypedSymbol::try_from_symbol].
+///
+impl<NS: namespace::Static> TryFrom<Symbol> for TypedSymbol<NS> {
+    type Error = Symbol;
+
+    fn try_from(symbol: Symbol) -> Result<Self, Symbol> {
+        let ns = symbol
+            .static_form()
+            .and_then(|(ns, _)| ns.as_any().downcast_ref::<NS>());
+
+        match ns {
+            Some(ns) => TypedSymbol::try_from_symbol(ns, symbol),
+            None => Err(symbol),
+        }
+    }
+}
+
+impl<NS: namespace::Static> Clone for TypedSymbol<NS> {
+    fn clone(&self) -> Self {
+        Self {
+            symbol: self.symbol.clone(),
+            _namespace: PhantomData,
+        }
+    }
+}
+
+impl<NS: namespace::Static> core::fmt::Debug for TypedSymbol<NS> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.symbol, f)
+    }
+}
+
+impl<NS: namespace::Static> core::fmt::Display for TypedSymbol<NS> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.symbol, f)
+    }
+}
+
+impl<NS: namespace::Static> PartialEq for TypedSymbol<NS> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.id() == rhs.id()
+    }
+}
+
+impl<NS: namespace::Static> Eq for TypedSymbol<NS> {}
+
+impl<NS: namespace::Static> PartialEq<Symbol> for TypedSymbol<NS> {
+    fn eq(&self, rhs: &Symbol) -> bool {
+        self.symbol == *rhs
+    }
+}
+
+impl<NS: namespace::Static> Ord for TypedSymbol<NS> {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
+impl<NS: namespace::Static> PartialOrd for TypedSymbol<NS> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<NS: namespace::Static> core::hash::Hash for TypedSymbol<NS> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.symbol.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::_static::{ClassN, STATIC_NS_CLASS_A};
+    use crate::tests::{dynamic, STATIC_A_1, STATIC_B_0};
+
+    #[test]
+    fn test_typed_symbol() {
+        let a1 = TypedSymbol::new(&STATIC_NS_CLASS_A, 1);
+
+        assert_eq!(
+            std::mem::size_of::<TypedSymbol<ClassN<1>>>(),
+            std::mem::size_of::<Symbol>()
+        );
+        assert_eq!(a1, STATIC_A_1);
+        assert_eq!(a1.id(), 1);
+        assert_eq!(a1.name(), "1");
+        assert_eq!(format!("{:?}", a1), "A::1");
+        assert!(a1 > TypedSymbol::new(&STATIC_NS_CLASS_A, 0));
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            TypedSymbol::<ClassN<1>>::try_from(STATIC_A_1).unwrap().id(),
+            1
+        );
+        assert_eq!(
+            TypedSymbol::<ClassN<1>>::try_from(STATIC_B_0).unwrap_err(),
+            STATIC_B_0
+        );
+        assert!(TypedSymbol::<ClassN<1>>::try_from(dynamic::sym0("foo")).is_err());
+    }
+
+    #[test]
+    fn test_equivalent_symbols() {
+        struct Uid<const N: u8>;
+
+        impl<const N: u8> namespace::Static for Uid<N> {
+            fn namespace_name(&self) -> &str {
+                "typed_uid"
+            }
+
+            fn namespace_uid(&self) -> Option<u128> {
+                Some(0x7e)
+            }
+
+            fn symbol_count(&self) -> u32 {
+                1
+            }
+
+            fn symbol_name(&self, _: u32) -> &str {
+                "foo"
+            }
+        }
+
+        let a1 = TypedSymbol::<ClassN<1>>::try_from(dynamic::dynamic_a("1")).unwrap();
+        assert_eq!(a1.id(), 1);
+        assert_eq!(*a1.as_symbol(), STATIC_A_1);
+
+        let shared = Symbol::Static(&Uid::<2>, 0);
+        assert!(TypedSymbol::<Uid<1>>::try_from(shared.clone()).is_err());
+        let typed = TypedSymbol::try_from_symbol(&Uid::<1>, shared.clone()).unwrap();
+        assert_eq!(typed, shared);
+        assert!(TypedSymbol::try_from_symbol(&Uid::<1>, STATIC_A_1).is_err());
+    }
+}
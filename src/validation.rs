@@ -0,0 +1,458 @@
+//!
+//! Validation of namespace names, for catching ambiguous [Debug](std::fmt::Debug) output and name resolution early.
+//!
+//! Namespaces are registered for validation at startup, and [validate] reports namespaces of different types sharing a name,
+//! and duplicate symbol names within namespaces that claim [unique names](crate::namespace::Static::unique_names).
+//! Namespaces registered in the [registry](crate::registry) are registered for validation too.
+//!
+//! ```
+//! use dyn_symbol::validation::ValidationError;
+//! use dyn_symbol::*;
+//!
+//! struct Html;
+//! struct OtherHtml;
+//!
+//! impl namespace::Static for Html {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         1
+//!     }
+//!
+//!     fn symbol_name(&self, _: u32) -> &str {
+//!         "class"
+//!     }
+//! }
+//!
+//! impl namespace::Static for OtherHtml {
+//!     fn namespace_name(&self) -> &str {
+//!         "html"
+//!     }
+//!
+//!     fn symbol_count(&self) -> u32 {
+//!         1
+//!     }
+//!
+//!     fn symbol_name(&self, _: u32) -> &str {
+//!         "id"
+//!     }
+//! }
+//!
+//! validation::register(&Html);
+//! validation::register(&OtherHtml);
+//!
+//! assert_eq!(
+//!     dyn_symbol::validate(),
+//!     Err(vec![ValidationError::NamespaceCollision {
+//!         namespace: "html".to_string(),
+//!         type_ids: vec![std::any::TypeId::of::<Html>(), std::any::TypeId::of::<OtherHtml>()],
+//!     }])
+//! );
+//! ```
+//!
+//! A [Validator] validates a set of namespaces of its own, e.g. those of one plugin.
+//!
+
+use crate::{namespace, NamespaceId};
+
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{OnceLock, RwLock};
+
+struct Registered {
+    id: NamespaceId,
+    type_id: TypeId,
+    static_ns: Option<&'static dyn namespace::Static>,
+}
+
+///
+/// A set of namespaces to validate.
+///
+#[derive(Default)]
+pub struct Validator {
+    /// Namespaces by name, in registration order.
+    namespaces: BTreeMap<String, Vec<Registered>>,
+}
+
+impl Validator {
+    ///
+    /// Create an empty validator.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Register a static namespace. Registering the same namespace again has no effect.
+    ///
+    pub fn register(&mut self, ns: &'static dyn namespace::Static) {
+        self.insert(
+            ns.namespace_name(),
+            Registered {
+                id: NamespaceId::of_static(ns),
+                type_id: std::any::Any::type_id(ns.as_any()),
+                static_ns: Some(ns),
+            },
+        );
+    }
+
+    ///
+    /// Register a wide namespace. Registering the same namespace again has no effect.
+    ///
+    /// Only name collisions are validated for wide namespaces: their ids can't be enumerated,
+    /// so duplicate symbol names are not checked.
+    ///
+    pub fn register_wide(&mut self, ns: &'static dyn namespace::StaticWide) {
+        self.insert(
+            ns.namespace_name(),
+            Registered {
+                id: NamespaceId::of_wide(ns),
+                type_id: std::any::Any::type_id(ns.as_any()),
+                static_ns: None,
+            },
+        );
+    }
+
+    ///
+    /// Register the dynamic namespace of `instance`, which can be any symbol instance of the namespace.
+    ///
+    pub fn register_dynamic(&mut self, instance: &dyn namespace::Dynamic) {
+        self.insert(
+            instance.namespace_name(),
+            Registered {
+                id: NamespaceId::of_dynamic(instance),
+                type_id: std::any::Any::type_id(instance.as_any()),
+                static_ns: None,
+            },
+        );
+    }
+
+    fn insert(&mut self, namespace_name: &str, registered: Registered) {
+        let namespaces = self.namespaces.entry(namespace_name.into()).or_default();
+        let is_new = namespaces
+            .iter()
+            .all(|other| other.id != registered.id || other.type_id != registered.type_id);
+
+        if is_new {
+            namespaces.push(registered);
+        }
+    }
+
+    ///
+    /// Validate the registered namespaces, returning all errors found.
+    ///
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+
+        for (namespace_name, namespaces) in &self.namespaces {
+            let mut type_ids = vec![];
+            let mut ids = vec![];
+            for registered in namespaces {
+                // Namespaces sharing a uid are the same namespace.
+                if !ids.contains(&registered.id) {
+                    ids.push(registered.id);
+                    type_ids.push(registered.type_id);
+                }
+            }
+
+            if type_ids.len() > 1 {
+                errors.push(ValidationError::NamespaceCollision {
+                    namespace: namespace_name.clone(),
+                    type_ids,
+                });
+            }
+
+            for ns in namespaces
+                .iter()
+                .filter_map(|registered| registered.static_ns)
+            {
+                if claims_unique_names(ns) {
+                    duplicate_symbol_names(ns, &mut errors);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+///
+/// Whether symbol names must be unique: namespaces that claim [unique names](namespace::Static::unique_names)
+/// or [hash by name](namespace::Static::hash_by_name).
+///
+fn claims_unique_names(ns: &dyn namespace::Static) -> bool {
+    ns.unique_names() || ns.hash_by_name()
+}
+
+fn duplicate_symbol_names(ns: &dyn namespace::Static, errors: &mut Vec<ValidationError>) {
+    let mut ids_by_name: HashMap<&str, Vec<u32>> = HashMap::new();
+    for id in 0..ns.symbol_count() {
+        ids_by_name.entry(ns.symbol_name(id)).or_default().push(id);
+    }
+
+    let mut duplicates: Vec<_> = ids_by_name
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .collect();
+    duplicates.sort_by_key(|(_, ids)| ids[0]);
+
+    errors.extend(
+        duplicates
+            .into_iter()
+            .map(|(name, ids)| ValidationError::DuplicateSymbolName {
+                namespace: ns.namespace_name().into(),
+                name: name.into(),
+                ids,
+            }),
+    );
+}
+
+fn global() -> &'static RwLock<Validator> {
+    static VALIDATOR: OnceLock<RwLock<Validator>> = OnceLock::new();
+    VALIDATOR.get_or_init(Default::default)
+}
+
+///
+/// Register a static namespace for [validate].
+///
+pub fn register(ns: &'static dyn namespace::Static) {
+    global().write().unwrap().register(ns)
+}
+
+///
+/// Register a wide namespace for [validate].
+///
+pub fn register_wide(ns: &'static dyn namespace::StaticWide) {
+    global().write().unwrap().register_wide(ns)
+}
+
+///
+/// Register the dynamic namespace of `instance` for [validate].
+///
+pub fn register_dynamic(instance: &dyn namespace::Dynamic) {
+    global().write().unwrap().register_dynamic(instance)
+}
+
+///
+/// Validate all namespaces registered for validation, returning all errors found.
+///
+pub fn validate() -> Result<(), Vec<ValidationError>> {
+    global().read().unwrap().validate()
+}
+
+///
+/// A problem found by [validate].
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Namespaces of different types share a name. The type ids are in registration order.
+    NamespaceCollision {
+        namespace: String,
+        type_ids: Vec<TypeId>,
+    },
+    /// A namespace that claims unique symbol names has several symbols with the same name.
+    DuplicateSymbolName {
+        namespace: String,
+        name: String,
+        ids: Vec<u32>,
+    },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NamespaceCollision {
+                namespace,
+                type_ids,
+            } => write!(
+                f,
+                "{} namespaces of different types are named `{}`",
+                type_ids.len(),
+                namespace
+            ),
+            Self::DuplicateSymbolName {
+                namespace,
+                name,
+                ids,
+            } => write!(
+                f,
+                "symbol name `{}` is used by ids {:?} in namespace `{}`",
+                name, ids, namespace
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::_static::{ClassN, STATIC_NS_CLASS_A, STATIC_NS_CLASS_B};
+    use crate::tests::dynamic::TestDynamic;
+
+    struct Colors;
+
+    impl namespace::Static for Colors {
+        fn namespace_name(&self) -> &str {
+            "validation_colors"
+        }
+
+        fn symbol_count(&self) -> u32 {
+            4
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["red", "green", "red", "blue"][id as usize]
+        }
+
+        fn hash_by_name(&self) -> bool {
+            true
+        }
+    }
+
+    struct Sizes<const UNIQUE: bool>;
+
+    impl<const UNIQUE: bool> namespace::Static for Sizes<UNIQUE> {
+        fn namespace_name(&self) -> &str {
+            if UNIQUE {
+                "validation_unique_sizes"
+            } else {
+                "validation_sizes"
+            }
+        }
+
+        fn symbol_count(&self) -> u32 {
+            3
+        }
+
+        fn symbol_name(&self, id: u32) -> &str {
+            ["small", "large", "small"][id as usize]
+        }
+
+        fn symbol_by_name(&self, name: &str) -> Option<u32> {
+            (0..3).find(|id| self.symbol_name(*id) == name)
+        }
+
+        fn unique_names(&self) -> bool {
+            UNIQUE
+        }
+    }
+
+    struct Uid<const N: u8>;
+
+    impl<const N: u8> namespace::Static for Uid<N> {
+        fn namespace_name(&self) -> &str {
+            "validation_uid"
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            Some(0x7a11d)
+        }
+
+        fn symbol_count(&self) -> u32 {
+            0
+        }
+
+        fn symbol_name(&self, _: u32) -> &str {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_namespace_collision() {
+        let mut validator = Validator::new();
+        validator.register(&STATIC_NS_CLASS_A);
+        validator.register(&STATIC_NS_CLASS_A);
+        validator.register(&STATIC_NS_CLASS_B);
+        assert_eq!(validator.validate(), Ok(()));
+
+        validator.register_dynamic(&TestDynamic::<0>("x".into(), "A"));
+        validator.register_dynamic(&TestDynamic::<0>("y".into(), "A"));
+        assert_eq!(
+            validator.validate(),
+            Err(vec![ValidationError::NamespaceCollision {
+                namespace: "A".into(),
+                type_ids: vec![TypeId::of::<ClassN<1>>(), TypeId::of::<TestDynamic<0>>()],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_duplicate_symbol_names() {
+        let mut validator = Validator::new();
+        validator.register(&Colors);
+
+        let errors = validator.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            [ValidationError::DuplicateSymbolName {
+                namespace: "validation_colors".into(),
+                name: "red".into(),
+                ids: vec![0, 2],
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "symbol name `red` is used by ids [0, 2] in namespace `validation_colors`"
+        );
+    }
+
+    #[test]
+    fn test_unique_names() {
+        let mut validator = Validator::new();
+        validator.register(&Sizes::<false>);
+        assert_eq!(validator.validate(), Ok(()));
+
+        validator.register(&Sizes::<true>);
+        assert_eq!(
+            validator.validate(),
+            Err(vec![ValidationError::DuplicateSymbolName {
+                namespace: "validation_unique_sizes".into(),
+                name: "small".into(),
+                ids: vec![0, 2],
+            }])
+        );
+    }
+
+    struct Cells;
+
+    impl namespace::StaticWide for Cells {
+        fn namespace_name(&self) -> &str {
+            "A"
+        }
+
+        fn symbol_name(&self, _: u64) -> &str {
+            "cell"
+        }
+    }
+
+    #[test]
+    fn test_wide_collision() {
+        let mut validator = Validator::new();
+        validator.register(&STATIC_NS_CLASS_A);
+        validator.register_wide(&Cells);
+        validator.register_wide(&Cells);
+        assert_eq!(
+            validator.validate(),
+            Err(vec![ValidationError::NamespaceCollision {
+                namespace: "A".into(),
+                type_ids: vec![TypeId::of::<ClassN<1>>(), TypeId::of::<Cells>()],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_shared_uid() {
+        let mut validator = Validator::new();
+        validator.register(&Uid::<1>);
+        validator.register(&Uid::<2>);
+        assert_eq!(validator.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,113 @@
+//!
+//! [wasm-bindgen](wasm_bindgen) interop, for handing symbols to JavaScript.
+//!
+//! A [JsSymbol] is an opaque JavaScript object wrapping a [Symbol], so symbols can cross the wasm boundary
+//! without being degraded to plain strings. JavaScript code can also create symbols from registered
+//! namespaces, using the [registry](crate::registry):
+//!
+//! ```js
+//! const id = new JsSymbol("html", "id");
+//! id.namespace(); // "html"
+//! id.name(); // "id"
+//! id.equals(element.attributeSymbol()); // true
+//! ```
+//!
+
+use crate::{registry, Symbol};
+
+use wasm_bindgen::prelude::*;
+
+///
+/// A [Symbol] exported to JavaScript.
+///
+#[wasm_bindgen]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct JsSymbol(Symbol);
+
+#[wasm_bindgen]
+impl JsSymbol {
+    ///
+    /// Resolve a symbol from a registered namespace. Throws if the namespace or the symbol is unknown.
+    ///
+    #[wasm_bindgen(constructor)]
+    pub fn new(namespace: &str, name: &str) -> Result<JsSymbol, JsError> {
+        Ok(Self(registry::resolve(namespace, name)?))
+    }
+
+    ///
+    /// The symbol's name within its namespace.
+    ///
+    pub fn name(&self) -> String {
+        self.0.name().into()
+    }
+
+    ///
+    /// The name of the symbol's namespace.
+    ///
+    pub fn namespace(&self) -> String {
+        self.0.namespace().into()
+    }
+
+    ///
+    /// Whether this is the same symbol as `other`.
+    ///
+    pub fn equals(&self, other: &JsSymbol) -> bool {
+        self.0 == other.0
+    }
+
+    ///
+    /// The symbol's [Display](std::fmt::Display) representation.
+    ///
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl JsSymbol {
+    ///
+    /// The wrapped symbol.
+    ///
+    pub fn symbol(&self) -> &Symbol {
+        &self.0
+    }
+
+    ///
+    /// Unwrap the symbol.
+    ///
+    pub fn into_symbol(self) -> Symbol {
+        self.0
+    }
+}
+
+impl From<Symbol> for JsSymbol {
+    fn from(symbol: Symbol) -> Self {
+        Self(symbol)
+    }
+}
+
+impl From<JsSymbol> for Symbol {
+    fn from(js_symbol: JsSymbol) -> Self {
+        js_symbol.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{STATIC_A_0, STATIC_A_1};
+
+    #[test]
+    fn test_js_symbol() {
+        registry::register(&crate::tests::_static::STATIC_NS_CLASS_A);
+
+        let a1 = JsSymbol::new("A", "1").unwrap();
+
+        assert_eq!(a1.symbol(), &STATIC_A_1);
+        assert_eq!(a1.namespace(), "A");
+        assert_eq!(a1.name(), "1");
+        assert_eq!(a1.to_js_string(), "A::1");
+        assert!(a1.equals(&JsSymbol::from(STATIC_A_1)));
+        assert!(!a1.equals(&JsSymbol::from(STATIC_A_0)));
+    }
+}
@@ -0,0 +1,310 @@
+//!
+//! Static symbols with `u64` ids, for large machine-generated vocabularies.
+//!
+//! A [StaticWide](namespace::StaticWide) namespace identifies its symbols with a `u64`, which it can interpret as it likes,
+//! e.g. as a table id and a column id [packed](pack) together. A [WideNamespace] in a `static` points to the namespace,
+//! so that [Symbol::StaticWide] stays the usual size of a [Symbol]:
+//!
+//! ```
+//! use dyn_symbol::wide::{self, WideNamespace};
+//! use dyn_symbol::*;
+//!
+//! struct Columns;
+//!
+//! const TABLES: [(&str, &[&str]); 2] = [("users", &["users.id", "users.email"]), ("posts", &["posts.id"])];
+//!
+//! impl namespace::StaticWide for Columns {
+//!     fn namespace_name(&self) -> &str {
+//!         "column"
+//!     }
+//!
+//!     fn symbol_name(&self, id: u64) -> &str {
+//!         let (table, column) = wide::unpack(id);
+//!         TABLES[table as usize].1[column as usize]
+//!     }
+//! }
+//!
+//! static COLUMNS: WideNamespace = WideNamespace::new(&Columns);
+//!
+//! let email = COLUMNS.symbol(wide::pack(0, 1));
+//!
+//! assert_eq!(email, COLUMNS.symbol(wide::pack(0, 1)));
+//! assert_eq!(email.name(), "users.email");
+//! assert_eq!(format!("{:?}", email), "column::users.email");
+//! assert_eq!(email.downcast_wide::<Columns>().map(|(_, id)| wide::unpack(id)), Some((0, 1)));
+//! ```
+//!
+//! Wide symbols are ordered after all [Static](Symbol::Static) symbols, and are never equal to them.
+//!
+//! [StaticWide](namespace::StaticWide) has the same hooks as [Static](namespace::Static) namespaces, taking `u64` ids,
+//! so wide symbols support [kind](Symbol::kind), [metadata](Symbol::metadata), [interfaces](crate::interface),
+//! [parents](Symbol::parent) and serde. Wide namespaces are registered with
+//! [register_wide](crate::registry::register_wide) for name resolution, validation and rkyv archiving.
+//! The limitations are:
+//!
+//! * [SymbolInfo::id](crate::info::SymbolInfo::id) is a `u32`, so it's `None` for wide symbols.
+//! * Ids can't be enumerated, so [validation](crate::validation) doesn't check for duplicate symbol names.
+//!
+
+use crate::{namespace, Symbol};
+
+///
+/// A [StaticWide](namespace::StaticWide) namespace, referred to by [Symbol::StaticWide].
+///
+pub struct WideNamespace {
+    ns: &'static dyn namespace::StaticWide,
+}
+
+impl WideNamespace {
+    ///
+    /// Refer to the namespace `ns`.
+    ///
+    pub const fn new(ns: &'static dyn namespace::StaticWide) -> Self {
+        Self { ns }
+    }
+
+    ///
+    /// The namespace.
+    ///
+    pub fn namespace(&self) -> &'static dyn namespace::StaticWide {
+        self.ns
+    }
+
+    ///
+    /// Create the symbol with the given `id`.
+    ///
+    pub fn symbol(&'static self, id: u64) -> Symbol {
+        Symbol::StaticWide(self, id)
+    }
+
+    ///
+    /// Resolve a symbol by its name, using [symbol_by_name](namespace::StaticWide::symbol_by_name).
+    ///
+    pub fn resolve(&'static self, name: &str) -> Option<Symbol> {
+        self.ns.symbol_by_name(name).map(|id| self.symbol(id))
+    }
+}
+
+impl core::fmt::Debug for WideNamespace {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_tuple("WideNamespace")
+            .field(&self.ns.namespace_name())
+            .finish()
+    }
+}
+
+///
+/// Pack two `u32`s into one `u64` id, e.g. a table id and a column id.
+///
+pub const fn pack(high: u32, low: u32) -> u64 {
+    ((high as u64) << 32) | low as u64
+}
+
+///
+/// Unpack an id created with [pack].
+///
+pub const fn unpack(id: u64) -> (u32, u32) {
+    ((id >> 32) as u32, id as u32)
+}
+
+impl Symbol {
+    ///
+    /// Try to downcast this Symbol's originating _wide namespace_ to a concrete `&T`,
+    /// and if successful, return that concrete namespace along with the symbol's id.
+    ///
+    pub fn downcast_wide<T: 'static>(&self) -> Option<(&T, u64)> {
+        match self {
+            Self::StaticWide(ns, id) => ns
+                .namespace()
+                .as_any()
+                .downcast_ref::<T>()
+                .map(|t| (t, *id)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kind::SymbolKind;
+    use crate::tests::{dynamic, STATIC_A_0};
+    use std::collections::BTreeSet;
+    use std::hash::BuildHasher;
+
+    struct Grid<const N: u8>;
+
+    impl<const N: u8> namespace::StaticWide for Grid<N> {
+        fn namespace_name(&self) -> &str {
+            "grid"
+        }
+
+        fn namespace_uid(&self) -> Option<u128> {
+            Some(0x961d)
+        }
+
+        fn symbol_name(&self, _: u64) -> &str {
+            "cell"
+        }
+
+        fn fmt_debug(&self, id: u64, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let (row, column) = unpack(id);
+            write!(f, "grid::cell({}, {})", row, column)
+        }
+    }
+
+    static GRID: WideNamespace = WideNamespace::new(&Grid::<1>);
+    static GRID_AGAIN: WideNamespace = WideNamespace::new(&Grid::<2>);
+
+    #[test]
+    fn test_pack() {
+        assert_eq!(unpack(pack(7, u32::MAX)), (7, u32::MAX));
+        assert_eq!(pack(1, 0), 1 << 32);
+    }
+
+    #[test]
+    fn test_wide_symbols() {
+        let random_state = std::collections::hash_map::RandomState::new();
+        let large = GRID.symbol(u64::MAX);
+
+        assert_eq!(large, GRID.symbol(u64::MAX));
+        assert_ne!(large, GRID.symbol(u32::MAX as u64));
+        // Namespaces sharing a uid:
+        assert_eq!(large, GRID_AGAIN.symbol(u64::MAX));
+        assert_eq!(
+            random_state.hash_one(&large),
+            random_state.hash_one(GRID_AGAIN.symbol(u64::MAX))
+        );
+        assert_eq!(format!("{:?}", GRID.symbol(pack(2, 3))), "grid::cell(2, 3)");
+        assert_eq!(large.to_string(), "grid::cell");
+        assert!(large.downcast_wide::<Grid<1>>().is_some());
+        assert!(large.downcast_static::<Grid<1>>().is_none());
+    }
+
+    struct Tree;
+
+    impl namespace::StaticWide for Tree {
+        fn namespace_name(&self) -> &str {
+            "wide_tree"
+        }
+
+        fn symbol_name(&self, id: u64) -> &str {
+            if id >> 32 == 0 {
+                "root"
+            } else {
+                "leaf"
+            }
+        }
+
+        fn hash_by_name(&self) -> bool {
+            true
+        }
+
+        fn symbol_by_name(&self, name: &str) -> Option<u64> {
+            match name {
+                "root" => Some(0),
+                _ => name
+                    .strip_prefix("leaf-")?
+                    .parse()
+                    .ok()
+                    .map(|leaf| pack(1, leaf)),
+            }
+        }
+
+        fn metadata(&self, id: u64, type_id: core::any::TypeId) -> Option<&dyn core::any::Any> {
+            static DEPTHS: [u32; 2] = [0, 1];
+            if type_id == core::any::TypeId::of::<u32>() {
+                Some(&DEPTHS[unpack(id).0 as usize])
+            } else {
+                None
+            }
+        }
+
+        fn describe_metadata(&self, id: u64, entry: &mut dyn FnMut(&str, &dyn core::fmt::Display)) {
+            entry("leaf", &unpack(id).1);
+        }
+
+        fn symbol_kind(&self, _: u64) -> Option<SymbolKind> {
+            Some(SymbolKind::Other("node"))
+        }
+
+        fn parent(&self, id: u64) -> Option<u64> {
+            match unpack(id) {
+                (0, _) => None,
+                (_, _) => Some(0),
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        fn serialize_symbol(&self, id: u64) -> Option<std::borrow::Cow<'_, str>> {
+            match unpack(id) {
+                (0, _) => Some("root".into()),
+                (_, leaf) => Some(format!("leaf-{}", leaf).into()),
+            }
+        }
+    }
+
+    static TREE: WideNamespace = WideNamespace::new(&Tree);
+
+    #[test]
+    fn test_hooks() {
+        let leaf = TREE.symbol(pack(1, u32::MAX));
+
+        assert_eq!(leaf.kind(), Some(SymbolKind::Other("node")));
+        assert_eq!(leaf.metadata::<u32>(), Some(&1));
+        assert_eq!(leaf.metadata::<u8>(), None);
+        assert_eq!(leaf.parent(), Some(TREE.symbol(0)));
+        assert!(leaf.is_descendant_of(&TREE.symbol(0)));
+
+        let info = leaf.describe();
+        assert!(info.is_static);
+        assert_eq!(info.id, None);
+        assert_eq!(info.metadata_value("leaf"), Some("4294967295"));
+    }
+
+    #[test]
+    fn test_hash_by_name() {
+        let random_state = std::collections::hash_map::RandomState::new();
+        let (a, b) = (TREE.symbol(pack(1, 0)), TREE.symbol(pack(1, 1)));
+
+        // Different symbols with the same name:
+        assert_ne!(a, b);
+        assert_eq!(random_state.hash_one(&a), random_state.hash_one(&b));
+        assert_ne!(
+            random_state.hash_one(GRID.symbol(0)),
+            random_state.hash_one(GRID.symbol(1))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        crate::serialization::register_deserializer("wide_tree", |repr| TREE.resolve(repr));
+
+        let symbols = vec![TREE.symbol(0), TREE.symbol(pack(1, 7))];
+        let json = serde_json::to_string(&symbols).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Symbol>>(&json).unwrap(), symbols);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let set: BTreeSet<_> = vec![
+            dynamic::sym0("foo"),
+            GRID.symbol(2),
+            STATIC_A_0,
+            GRID.symbol(1),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            [
+                STATIC_A_0,
+                GRID.symbol(1),
+                GRID.symbol(2),
+                dynamic::sym0("foo")
+            ]
+        );
+    }
+}